@@ -0,0 +1,74 @@
+//! Bakes the puzzle inputs into the binary with the `embed-inputs` feature, see
+//! `src/etc/embedded.rs`.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The `dayNN.txt` files of a directory, with their day.
+fn day_files(dir: &Path) -> Vec<(u8, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut files: Vec<(u8, PathBuf)> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?;
+            let day = name
+                .strip_prefix("day")?
+                .strip_suffix(".txt")?
+                .parse()
+                .ok()?;
+            Some((day, path))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn main() {
+    println!("cargo::rerun-if-env-changed=AOC_INPUT_DIR");
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let dir = manifest_dir.join(env::var("AOC_INPUT_DIR").unwrap_or("input".to_owned()));
+    println!("cargo::rerun-if-changed={}", dir.display());
+
+    // inputs saved before years were supported are of the default year, `None` here
+    let mut inputs: Vec<(Option<u16>, u8, PathBuf)> = day_files(&dir)
+        .into_iter()
+        .map(|(day, path)| (None, day, path))
+        .collect();
+    let mut years: Vec<(u16, PathBuf)> = fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            Some((path.file_name()?.to_str()?.parse().ok()?, path))
+        })
+        .collect();
+    years.sort();
+    for (year, path) in years {
+        inputs.extend(
+            day_files(&path)
+                .into_iter()
+                .map(|(day, path)| (Some(year), day, path)),
+        );
+    }
+    if inputs.is_empty() {
+        println!("cargo::warning=no inputs to embed in {}", dir.display());
+    }
+
+    let mut code = String::from("const INPUTS: &[(Option<u16>, u8, &str)] = &[\n");
+    for (year, day, path) in inputs {
+        code.push_str(&format!(
+            "    ({:?}, {}, include_str!({:?})),\n",
+            year,
+            day,
+            path.display().to_string()
+        ));
+    }
+    code.push_str("];\n");
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("inputs.rs");
+    fs::write(out, code).unwrap();
+}
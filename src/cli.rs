@@ -0,0 +1,420 @@
+//! Command line interface.
+use crate::days::{DEFAULT_YEAR, YEARS};
+use crate::etc::config::FORMATS;
+use clap::{Args, Parser, Subcommand};
+
+#[derive(Debug, Parser)]
+#[command(
+    about = "Solutions of Advent of Code 2024",
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Options of `run`, the default command.
+    #[command(flatten)]
+    pub run: RunArgs,
+}
+
+impl Cli {
+    /// The command to execute, `run` when none is given.
+    pub fn into_command(self) -> Command {
+        self.command.unwrap_or(Command::Run(self.run))
+    }
+}
+
+#[derive(Debug, PartialEq, Subcommand)]
+pub enum Command {
+    /// Solve days and print their answers
+    Run(RunArgs),
+    /// Time each part of days over repeated runs
+    Bench {
+        /// Number of timed runs of each part, after a warmup run
+        #[arg(short = 'n', long, default_value_t = 10)]
+        runs: usize,
+        #[command(flatten)]
+        run: RunArgs,
+    },
+    /// Print a synthetic puzzle input
+    Gen {
+        day: u8,
+        /// Size of the input, its meaning depends on the day
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+        /// Seed of the random generator
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// List the implemented days
+    List,
+    /// Show a calendar of the days of a year, solved in the background
+    #[cfg(feature = "tui")]
+    Dashboard {
+        /// Year of the puzzles
+        #[arg(long, default_value_t = DEFAULT_YEAR, value_parser = parse_year)]
+        year: u16,
+    },
+    /// Download the puzzle input of a day into the input directory
+    Fetch {
+        day: u8,
+        /// Year of the puzzle
+        #[arg(long, default_value_t = DEFAULT_YEAR, value_parser = parse_year)]
+        year: u16,
+        /// Download the input again even if it is already there
+        #[arg(long)]
+        force: bool,
+    },
+    /// Encrypt the inputs of days next to them, with the key in the AOC_INPUT_KEY environment
+    /// variable, so that they can be published; they are decrypted when read
+    Encrypt {
+        #[arg(required = true)]
+        days: Vec<u8>,
+        /// Year of the puzzles
+        #[arg(long, default_value_t = DEFAULT_YEAR, value_parser = parse_year)]
+        year: u16,
+    },
+    /// Solve a part of a day and submit its answer to the website
+    Submit {
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Year of the puzzle
+        #[arg(long, default_value_t = DEFAULT_YEAR, value_parser = parse_year)]
+        year: u16,
+    },
+    /// Create the module of a new day from a template, and register it
+    New {
+        day: u8,
+        /// Year of the puzzle
+        #[arg(long, default_value_t = DEFAULT_YEAR, value_parser = parse_year)]
+        year: u16,
+    },
+}
+
+/// A day to solve, or the standard input holding the input of the day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Day(u8),
+    Stdin,
+}
+
+fn parse_target(arg: &str) -> Result<Target, String> {
+    match arg {
+        "-" => Ok(Target::Stdin),
+        _ => arg
+            .parse()
+            .map(Target::Day)
+            .map_err(|_| format!("not a valid day: {}", arg)),
+    }
+}
+
+fn parse_year(arg: &str) -> Result<u16, String> {
+    arg.parse()
+        .ok()
+        .filter(|year| YEARS.contains(year))
+        .ok_or_else(|| format!("no solutions for year {}", arg))
+}
+
+/// Where the frames of visualizations go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VizTarget {
+    /// Animated in the terminal.
+    Terminal,
+    /// Saved to files of a directory, one subdirectory per day.
+    Directory(String),
+    /// Saved to an animated GIF, suffixed with the day if several days are run.
+    Gif(String),
+}
+
+fn parse_viz(arg: &str) -> Result<VizTarget, String> {
+    match arg {
+        "-" => Ok(VizTarget::Terminal),
+        _ if arg.ends_with(".gif") && !cfg!(feature = "gif") => {
+            Err("GIF export needs the gif feature".to_owned())
+        }
+        _ if arg.ends_with(".gif") => Ok(VizTarget::Gif(arg.to_owned())),
+        _ => Ok(VizTarget::Directory(arg.to_owned())),
+    }
+}
+
+/// Profile names are directory names, and keys of `answers.toml` tables.
+fn parse_profile(arg: &str) -> Result<String, String> {
+    let valid = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(arg.to_owned())
+    } else {
+        Err("use letters, digits, - and _".to_owned())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Args)]
+pub struct RunArgs {
+    /// Days to solve, `-` to read the input of the day from the standard input
+    #[arg(value_parser = parse_target)]
+    pub targets: Vec<Target>,
+    /// Year of the puzzles
+    #[arg(long, default_value_t = DEFAULT_YEAR, value_parser = parse_year)]
+    pub year: u16,
+    /// Solve every implemented day, with a total runtime
+    #[arg(long, conflicts_with = "targets")]
+    pub all: bool,
+    /// Solve only one part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+    /// Read the input from a file instead of the input directory
+    #[arg(long, conflicts_with = "stdin")]
+    pub input: Option<String>,
+    /// Read the inputs of another person, from the `<profile>` subdirectory of the input directory
+    #[arg(long, conflicts_with_all = ["input", "stdin"], value_parser = parse_profile)]
+    pub profile: Option<String>,
+    /// Solve the example of the puzzle text with the given number, the first one by default
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "1",
+        conflicts_with_all = ["input", "stdin", "profile"],
+        value_parser = clap::value_parser!(u8).range(1..)
+    )]
+    pub example: Option<u8>,
+    /// Read the input from the standard input
+    #[arg(long)]
+    pub stdin: bool,
+    /// Solve the days for every file of a directory, with one row per file
+    #[arg(long, conflicts_with_all = ["input", "stdin", "example", "profile", "watch"])]
+    pub input_dir: Option<String>,
+    /// Solve day 3 reading the input file in chunks rather than whole, for inputs larger than
+    /// memory
+    #[arg(
+        long,
+        requires = "input",
+        conflicts_with_all = ["all", "part", "format", "watch", "paranoid", "explain", "viz", "record", "check"]
+    )]
+    pub stream: bool,
+    /// Re-run the days every time their source or input file changes
+    #[arg(long, conflicts_with = "stdin")]
+    pub watch: bool,
+    /// Time the parsing and each part separately
+    #[arg(long)]
+    pub time: bool,
+    /// Print one block per day, a table of all days, or one JSON object per day without the
+    /// details, `format` of aoc.toml by default
+    #[arg(long, value_parser = FORMATS)]
+    pub format: Option<String>,
+    /// Cross-validate answers of days with several implementations
+    #[arg(long)]
+    pub paranoid: bool,
+    /// Save progress of long-running searches
+    #[arg(long, conflicts_with = "resume")]
+    pub checkpoint: bool,
+    /// Resume long-running searches from saved progress
+    #[arg(long)]
+    pub resume: bool,
+    /// Narrate how days with an explanation get their answers
+    #[arg(long)]
+    pub explain: bool,
+    /// Dump intermediate grids of some days on the standard error
+    #[arg(long)]
+    pub debug: bool,
+    /// Show the visualization of days having one, animated in the terminal, or saved to a
+    /// directory, or to an animated GIF for paths ending with .gif
+    #[arg(
+        long,
+        value_name = "PATH",
+        num_args = 0..=1,
+        default_missing_value = "-",
+        value_parser = parse_viz
+    )]
+    pub viz: Option<VizTarget>,
+    /// Keep one frame every N steps of the visualized simulations
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "viz",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub viz_every: Option<u32>,
+    /// Milliseconds each frame of the visualizations stays on screen
+    #[arg(long, value_name = "MS", requires = "viz")]
+    pub viz_delay: Option<u64>,
+    /// Append answers to the results database
+    #[arg(long)]
+    pub record: bool,
+    /// Compare answers with the expected ones of answers.toml, failing on mismatches
+    #[arg(long)]
+    pub check: bool,
+}
+
+impl RunArgs {
+    /// The days to solve, without the standard input target.
+    pub fn days(&self) -> Vec<u8> {
+        self.targets
+            .iter()
+            .filter_map(|target| match target {
+                Target::Day(day) => Some(*day),
+                Target::Stdin => None,
+            })
+            .collect()
+    }
+
+    /// Whether the input is read from the standard input.
+    pub fn reads_stdin(&self) -> bool {
+        self.stdin || self.targets.contains(&Target::Stdin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cli, Command, RunArgs, Target, VizTarget};
+    use clap::{CommandFactory, Parser};
+
+    fn parse(args: &[&str]) -> Result<Command, clap::Error> {
+        Cli::try_parse_from([&["aoc24-rust"], args].concat()).map(Cli::into_command)
+    }
+
+    #[test]
+    fn definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn commands() {
+        let Command::Run(run) = parse(&["15", "-", "--part", "2", "--time"]).unwrap() else {
+            panic!("expected run");
+        };
+        assert_eq!(run.targets, vec![Target::Day(15), Target::Stdin]);
+        assert_eq!(run.days(), vec![15]);
+        assert!(run.reads_stdin());
+        assert_eq!(run.part, Some(2));
+        assert!(run.time);
+        assert_eq!(
+            parse(&["run", "3", "4"]).unwrap(),
+            Command::Run(RunArgs {
+                targets: vec![Target::Day(3), Target::Day(4)],
+                year: 2024,
+                ..RunArgs::default()
+            })
+        );
+        assert_eq!(
+            parse(&["bench", "-n", "5", "--all"]).unwrap(),
+            Command::Bench {
+                runs: 5,
+                run: RunArgs {
+                    all: true,
+                    year: 2024,
+                    ..RunArgs::default()
+                }
+            }
+        );
+        assert_eq!(
+            parse(&["gen", "7", "--seed", "3"]).unwrap(),
+            Command::Gen {
+                day: 7,
+                size: 100,
+                seed: 3
+            }
+        );
+        assert_eq!(parse(&["list"]).unwrap(), Command::List);
+        assert_eq!(
+            parse(&["submit", "3", "2"]).unwrap(),
+            Command::Submit {
+                day: 3,
+                part: 2,
+                year: 2024
+            }
+        );
+        assert!(parse(&["submit", "3", "0"]).is_err());
+        assert_eq!(
+            parse(&["fetch", "3", "--force"]).unwrap(),
+            Command::Fetch {
+                day: 3,
+                year: 2024,
+                force: true
+            }
+        );
+        assert_eq!(
+            parse(&["encrypt", "1", "2"]).unwrap(),
+            Command::Encrypt {
+                days: vec![1, 2],
+                year: 2024
+            }
+        );
+        assert!(parse(&["encrypt"]).is_err());
+        assert_eq!(
+            parse(&["new", "26"]).unwrap(),
+            Command::New {
+                day: 26,
+                year: 2024
+            }
+        );
+
+        assert!(parse(&["1", "--part", "3"]).is_err());
+        assert!(parse(&["x"]).is_err());
+        assert!(parse(&["1", "--checkpoint", "--resume"]).is_err());
+        assert!(parse(&["7", "--all"]).is_err());
+        assert!(parse(&["1", "--input", "a.txt", "--stdin"]).is_err());
+        let Command::Run(run) = parse(&["1", "--profile", "alice_2"]).unwrap() else {
+            panic!("expected run");
+        };
+        assert_eq!(run.profile.as_deref(), Some("alice_2"));
+        assert!(parse(&["1", "--profile", "../alice"]).is_err());
+        assert!(parse(&["1", "--profile", "alice", "--input", "a.txt"]).is_err());
+        let Command::Run(run) = parse(&["15", "--example"]).unwrap() else {
+            panic!("expected run");
+        };
+        assert_eq!(run.example, Some(1));
+        let Command::Run(run) = parse(&["15", "--example", "3"]).unwrap() else {
+            panic!("expected run");
+        };
+        assert_eq!(run.example, Some(3));
+        assert!(parse(&["15", "--example", "0"]).is_err());
+        assert!(parse(&["15", "--example", "--stdin"]).is_err());
+        let Command::Run(run) = parse(&["14", "--viz"]).unwrap() else {
+            panic!("expected run");
+        };
+        assert_eq!(run.viz, Some(VizTarget::Terminal));
+        let Command::Run(run) = parse(&["14", "--viz", "frames"]).unwrap() else {
+            panic!("expected run");
+        };
+        assert_eq!(run.viz, Some(VizTarget::Directory("frames".to_owned())));
+        let Command::Run(run) = parse(&["14", "--viz", "--viz-every", "100"]).unwrap() else {
+            panic!("expected run");
+        };
+        assert_eq!(
+            (run.viz, run.viz_every),
+            (Some(VizTarget::Terminal), Some(100))
+        );
+        assert!(parse(&["14", "--viz-every", "100"]).is_err());
+        assert!(parse(&["14", "--viz", "--viz-every", "0"]).is_err());
+        let Command::Run(run) = parse(&["15", "--viz", "--viz-delay", "20"]).unwrap() else {
+            panic!("expected run");
+        };
+        assert_eq!(run.viz_delay, Some(20));
+        assert!(parse(&["15", "--viz-delay", "20"]).is_err());
+        let gif = parse(&["6", "--viz", "patrol.gif"]);
+        if cfg!(feature = "gif") {
+            let Command::Run(run) = gif.unwrap() else {
+                panic!("expected run");
+            };
+            assert_eq!(run.viz, Some(VizTarget::Gif("patrol.gif".to_owned())));
+        } else {
+            assert!(gif.is_err());
+        }
+        let Command::Run(run) = parse(&["9", "--input-dir", "stress"]).unwrap() else {
+            panic!("expected run");
+        };
+        assert_eq!(run.input_dir.as_deref(), Some("stress"));
+        assert!(parse(&["9", "--input-dir", "stress", "--input", "a.txt"]).is_err());
+        let Command::Run(run) = parse(&["3", "--stream", "--input", "big.txt"]).unwrap() else {
+            panic!("expected run");
+        };
+        assert!(run.stream);
+        assert!(parse(&["3", "--stream"]).is_err());
+        assert!(parse(&["3", "--stream", "--input", "big.txt", "--paranoid"]).is_err());
+        assert!(parse(&["1", "--unknown"]).is_err());
+        assert!(parse(&["1", "--year", "2015"]).is_err());
+        assert!(parse(&["1", "--format", "html"]).is_err());
+    }
+}
@@ -1,25 +1,144 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
+use crate::etc::rng::Rng;
+use crate::etc::solution::{DayError, DayResult};
+use crate::etc::viz::Visualize;
+use crate::{Solution, Variant};
+use std::time::Instant;
+
+/// A registered day: its number and its solver.
+pub type Entry = (u8, &'static dyn Day);
+
+/// Register the solver of a day, in the module of the day, for the `days!` list of its year.
+macro_rules! register_day {
+    ($day:literal, $solver:ident) => {
+        impl crate::days::Numbered for $solver {
+            fn day(&self) -> u8 {
+                $day
+            }
+        }
+
+        pub const ENTRY: crate::days::Entry = ($day, &$solver);
+    };
+}
+
+/// Declare the modules of the days of a year, and collect their registered solvers.
+macro_rules! days {
+    ($($module:ident,)*) => {
+        $(pub mod $module;)*
+
+        /// The registered days of the year, in order.
+        pub const DAYS: &[crate::days::Entry] = &[$($module::ENTRY),*];
+
+        /// The solver of a day.
+        pub fn day(day: u8) -> Option<&'static dyn crate::days::Day> {
+            DAYS.iter()
+                .find(|(number, _)| *number == day)
+                .map(|(_, solver)| *solver)
+        }
+    };
+}
+
+pub mod y2024;
+
+/// Years with solutions.
+pub const YEARS: [u16; 1] = [2024];
+
+/// Year solved when none is given.
+pub const DEFAULT_YEAR: u16 = 2024;
+
+/// The parsed input of a day, both parts being solved from it without parsing again.
+pub struct Parsed<'a> {
+    solve: Box<dyn Fn(u8) -> Result<Solution, DayError> + 'a>,
+}
+
+impl<'a> Parsed<'a> {
+    /// The parsed input, with the functions solving each part from it.
+    pub fn new<T: 'a>(parsed: T, part1: fn(&T) -> Solution, part2: fn(&T) -> Solution) -> Self {
+        Parsed {
+            solve: Box::new(move |part| match part {
+                1 => Ok(part1(&parsed)),
+                _ => Ok(part2(&parsed)),
+            }),
+        }
+    }
+
+    /// The input parsed by a parser that can reject it, with the functions solving each part
+    /// from it, which can fail too. Both parts fail with the error of a rejected input.
+    pub fn try_new<T: 'a>(
+        parsed: Result<T, DayError>,
+        part1: fn(&T) -> Result<Solution, DayError>,
+        part2: fn(&T) -> Result<Solution, DayError>,
+    ) -> Self {
+        Parsed {
+            solve: Box::new(move |part| {
+                let parsed = parsed.as_ref().map_err(DayError::clone)?;
+                match part {
+                    1 => part1(parsed),
+                    _ => part2(parsed),
+                }
+            }),
+        }
+    }
+
+    pub fn part1(&self) -> Result<Solution, DayError> {
+        (self.solve)(1)
+    }
+
+    pub fn part2(&self) -> Result<Solution, DayError> {
+        (self.solve)(2)
+    }
+}
+
+/// The number of a day, implemented by `register_day!`.
+pub trait Numbered {
+    fn day(&self) -> u8;
+}
+
+/// Solver of the puzzle of a day, implemented by a unit struct in the module of every day.
+pub trait Day: Numbered + Sync {
+    /// Parse the input, once for both parts.
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a>;
+
+    fn part1(&self, input: &str) -> Result<Solution, DayError> {
+        self.prepare(input).part1()
+    }
+
+    fn part2(&self, input: &str) -> Result<Solution, DayError> {
+        self.prepare(input).part2()
+    }
+
+    /// Both answers, with the time to solve each part from the parsed input.
+    fn solve(&self, input: String) -> Result<DayResult, DayError> {
+        let parsed = self.prepare(&input);
+        let start = Instant::now();
+        let part1 = parsed.part1()?;
+        let time1 = start.elapsed();
+        let start = Instant::now();
+        let part2 = parsed.part2()?;
+        Ok(DayResult {
+            times: [Some(time1), Some(start.elapsed())],
+            ..DayResult::new(self.day(), part1, part2)
+        })
+    }
+
+    /// Random input of about `size` lines, or items, with the shape of the puzzle inputs.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String;
+
+    /// Whether the input has the shape of the puzzle inputs of the day, to catch inputs saved
+    /// in the wrong file.
+    fn looks_like(&self, input: &str) -> bool;
+
+    /// Implementation variants, cross-validated in paranoid mode.
+    fn variants(&self) -> &'static [Variant] {
+        &[]
+    }
+
+    /// Narration of how the answers are found, printed in explain mode.
+    fn explainer(&self) -> Option<fn(&str) -> String> {
+        None
+    }
+
+    /// Frames showing how the answers are found, shown with `--viz`.
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        None
+    }
+}
@@ -0,0 +1,236 @@
+use crate::Solution;
+use crate::days::{Day, Parsed};
+use crate::etc::counter::Counter;
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+
+/// Location ID lists, one vector per input column.
+pub type Columns = Vec<Vec<u64>>;
+
+/// Read the location IDs of a line, its `number` counting from 1 for errors.
+fn line(number: usize, input: &str) -> Result<Vec<u64>, DayError> {
+    input
+        .split_ascii_whitespace()
+        .map(|token| {
+            token.parse().map_err(|_| {
+                DayError::Malformed(format!("line {}: {:?} is not a location ID", number, token))
+            })
+        })
+        .collect()
+}
+
+/// Read the input as columns of numbers, every line must have the same number of columns.
+///
+/// Malformed lines are errors when `strict`, and are skipped otherwise, their errors being
+/// returned with the columns.
+pub fn parse(input: &str, strict: bool) -> Result<(Columns, Vec<DayError>), DayError> {
+    let mut columns: Columns = Default::default();
+    let mut skipped = vec![];
+    for (i, text) in input.lines().enumerate() {
+        let row = line(i + 1, text).and_then(|row| {
+            if columns.is_empty() || row.is_empty() || row.len() == columns.len() {
+                Ok(row)
+            } else {
+                Err(DayError::Malformed(format!(
+                    "line {}: {} location IDs instead of {}",
+                    i + 1,
+                    row.len(),
+                    columns.len()
+                )))
+            }
+        });
+        match row {
+            Ok(row) => {
+                if columns.is_empty() {
+                    columns.resize(row.len(), Vec::new());
+                }
+                for (column, value) in columns.iter_mut().zip(row) {
+                    column.push(value);
+                }
+            }
+            Err(err) if strict => return Err(err),
+            Err(err) => skipped.push(err),
+        }
+    }
+    if columns.is_empty() {
+        return Err(DayError::Malformed("no location IDs".to_owned()));
+    }
+    Ok((columns, skipped))
+}
+
+/// Read the input strictly, see [`parse`].
+pub fn prepare(input: &str) -> Result<Columns, DayError> {
+    parse(input, true).map(|(columns, _)| columns)
+}
+
+/// Sum of the distances between the smallest numbers of both lists, then the second smallest,
+/// and so on. The longest list is cut to the length of the other one.
+pub fn total_distance(left: &[u64], right: &[u64]) -> u64 {
+    let sorted = |list: &[u64]| {
+        let mut list = list.to_vec();
+        list.sort_unstable();
+        list
+    };
+    sorted(left)
+        .iter()
+        .zip(sorted(right))
+        .map(|(a, b)| a.abs_diff(b))
+        .sum()
+}
+
+/// Sum of every number of `list` multiplied by the number of times it appears in `reference`.
+pub fn similarity_score(list: &[u64], reference: &[u64]) -> u64 {
+    let counts: Counter<u64> = reference.iter().copied().collect();
+    list.iter().map(|x| x * counts.count(x)).sum()
+}
+
+/// Sum of the distances between every pair of columns.
+fn columns_distance(columns: &Columns) -> u64 {
+    let mut distance = 0;
+    for (i, a) in columns.iter().enumerate() {
+        for b in &columns[i + 1..] {
+            distance += total_distance(a, b);
+        }
+    }
+    distance
+}
+
+/// Sum of the similarity scores of the non-reference columns with the `reference` column.
+fn columns_similarity(columns: &Columns, reference: usize) -> u64 {
+    columns
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != reference)
+        .map(|(_, column)| similarity_score(column, &columns[reference]))
+        .sum()
+}
+
+fn solve_part1(columns: &Columns) -> u64 {
+    columns_distance(columns)
+}
+
+fn solve_part2(columns: &Columns) -> u64 {
+    // the right list is the reference
+    columns_similarity(columns, columns.len() - 1)
+}
+
+pub struct Day01;
+register_day!(1, Day01);
+
+impl Day for Day01 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |columns| Ok(Solution::from(solve_part1(columns))),
+            |columns| Ok(Solution::from(solve_part2(columns))),
+        )
+    }
+
+    /// Generate `size` lines of two location IDs.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| {
+                format!(
+                    "{}   {}\n",
+                    rng.between(10000, 99999),
+                    rng.between(10000, 99999)
+                )
+            })
+            .collect()
+    }
+
+    /// Lines of whitespace separated numbers.
+    fn looks_like(&self, input: &str) -> bool {
+        shape::all_lines(input, |line| {
+            line.split_whitespace().all(|n| shape::matches(n, "#"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(1, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 11);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), 31);
+    }
+
+    const THREE_COLUMNS_INPUT: &str = "3   4   1
+        4   3   3
+        2   5   3";
+
+    #[test]
+    fn three_columns() {
+        let columns = prepare(THREE_COLUMNS_INPUT).unwrap();
+        assert_eq!(columns, vec![vec![3, 4, 2], vec![4, 3, 5], vec![1, 3, 3]]);
+        // sorted: [2,3,4] [3,4,5] [1,3,3]
+        assert_eq!(columns_distance(&columns), 3 + 2 + 5);
+        assert_eq!(columns_similarity(&columns, 2), 3 * 2 + 3 * 2);
+        assert_eq!(columns_similarity(&columns, 0), 4 + 3 + 3 + 3);
+    }
+
+    #[test]
+    fn lists() {
+        assert_eq!(total_distance(&[3, 4, 2, 1, 3, 3], &[4, 3, 5, 3, 9, 3]), 11);
+        assert_eq!(
+            similarity_score(&[3, 4, 2, 1, 3, 3], &[4, 3, 5, 3, 9, 3]),
+            31
+        );
+        fn list(rng: &mut Rng, lo: i64, hi: i64) -> Vec<u64> {
+            (0..rng.below(20))
+                .map(|_| rng.between(lo, hi) as u64)
+                .collect()
+        }
+        let mut rng = Rng::new(1);
+        for _ in 0..100 {
+            let (a, b) = (list(&mut rng, 0, 50), list(&mut rng, 0, 50));
+            assert_eq!(total_distance(&a, &a), 0);
+            assert_eq!(total_distance(&a, &b), total_distance(&b, &a));
+            // reordering does not change anything
+            let mut shuffled = b.clone();
+            rng.shuffle(&mut shuffled);
+            assert_eq!(total_distance(&a, &b), total_distance(&a, &shuffled));
+            assert_eq!(similarity_score(&a, &b), similarity_score(&a, &shuffled));
+            // disjoint lists are not similar at all
+            let disjoint = list(&mut rng, 100, 150);
+            assert_eq!(similarity_score(&a, &disjoint), 0);
+            assert_eq!(similarity_score(&disjoint, &a), 0);
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        // trailing whitespace and blank lines are fine
+        assert_eq!(
+            prepare("3   4\t\n\n4   3\n").unwrap(),
+            vec![vec![3, 4], vec![4, 3]]
+        );
+        let input = "3   4\n4   x3\n2   5   3\n1   3\n";
+        assert_eq!(
+            prepare(input),
+            Err(DayError::Malformed(
+                "line 2: \"x3\" is not a location ID".to_owned()
+            ))
+        );
+        let (columns, skipped) = parse(input, false).unwrap();
+        assert_eq!(columns, vec![vec![3, 1], vec![4, 3]]);
+        assert_eq!(
+            skipped,
+            vec![
+                DayError::Malformed("line 2: \"x3\" is not a location ID".to_owned()),
+                DayError::Malformed("line 3: 3 location IDs instead of 2".to_owned()),
+            ]
+        );
+        assert!(prepare("\n").is_err());
+    }
+}
@@ -0,0 +1,366 @@
+use crate::Solution;
+use crate::days::{Day, Parsed};
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+use std::iter::Iterator;
+use std::ops::RangeInclusive;
+
+/// Read the levels of a report, its line `number` counting from 1 for errors.
+fn line(number: usize, input: &str) -> Result<Vec<u8>, DayError> {
+    input
+        .split_ascii_whitespace()
+        .map(|token| {
+            token.parse().map_err(|_| {
+                DayError::Malformed(format!("line {}: {:?} is not a level", number, token))
+            })
+        })
+        .collect()
+}
+
+/// Read the reports, one list of levels per line.
+pub fn prepare(input: &str) -> Result<Vec<Vec<u8>>, DayError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, text)| line(i + 1, text))
+        .collect()
+}
+
+/// Rules of safe reports.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules {
+    /// Allowed differences between successive levels.
+    pub steps: RangeInclusive<u8>,
+    /// Number of levels the Problem Dampener can remove.
+    pub removals: usize,
+}
+
+impl Rules {
+    /// The rules of the puzzle: steps of 1 to 3, and one level removed in part 2.
+    pub const PUZZLE: Rules = Rules {
+        steps: RangeInclusive::new(1, 3),
+        removals: 1,
+    };
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::PUZZLE
+    }
+}
+
+// check increasing or decreasing property between two successive values.
+fn check_xcreasing(increasing: bool, steps: &RangeInclusive<u8>, a: u8, b: u8) -> bool {
+    ((increasing && a < b) || (!increasing && a > b)) && steps.contains(&a.abs_diff(b))
+}
+
+/// Index of the first level breaking the increasing or decreasing property with the previous
+/// level, ignoring the level at index `skip`.
+fn first_break(
+    increasing: bool,
+    steps: &RangeInclusive<u8>,
+    report: &[u8],
+    skip: Option<usize>,
+) -> Option<usize> {
+    let mut previous = None;
+    for (i, &level) in report.iter().enumerate() {
+        if Some(i) == skip {
+            continue;
+        }
+        if let Some(previous) = previous
+            && !check_xcreasing(increasing, steps, previous, level)
+        {
+            return Some(i);
+        }
+        previous = Some(level);
+    }
+    None
+}
+
+/// Indices of the fewest levels to remove for the property to hold, keeping the longest chain of
+/// levels following each other.
+fn fewest_removals(increasing: bool, steps: &RangeInclusive<u8>, report: &[u8]) -> Vec<usize> {
+    // length of the longest chain ending at each level, with the previous level of the chain
+    let mut chains: Vec<(usize, Option<usize>)> = Vec::with_capacity(report.len());
+    for (j, &level) in report.iter().enumerate() {
+        let longest = (0..j)
+            .filter(|&i| check_xcreasing(increasing, steps, report[i], level))
+            .max_by_key(|&i| (chains[i].0, std::cmp::Reverse(i)))
+            .map_or((1, None), |i| (chains[i].0 + 1, Some(i)));
+        chains.push(longest);
+    }
+    let mut kept = vec![false; report.len()];
+    let mut last = (0..report.len()).max_by_key(|&j| (chains[j].0, std::cmp::Reverse(j)));
+    while let Some(j) = last {
+        kept[j] = true;
+        last = chains[j].1;
+    }
+    (0..report.len()).filter(|&i| !kept[i]).collect()
+}
+
+/// Safety of a report, see [`analyze_with`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Safety {
+    Safe,
+    /// Safe once the levels at these indices are removed by the Problem Dampener.
+    Dampened(Vec<usize>),
+    Unsafe,
+}
+
+/// Safety of a report in one direction.
+fn analyze_xcreasing(increasing: bool, rules: &Rules, report: &[u8]) -> Safety {
+    let steps = &rules.steps;
+    match first_break(increasing, steps, report, None) {
+        None => Safety::Safe,
+        Some(_) if rules.removals == 0 => Safety::Unsafe,
+        // the first broken pair stays unless one of its levels is removed
+        Some(i) if rules.removals == 1 => [i - 1, i]
+            .into_iter()
+            .find(|&removed| first_break(increasing, steps, report, Some(removed)).is_none())
+            .map_or(Safety::Unsafe, |removed| Safety::Dampened(vec![removed])),
+        Some(_) => {
+            let removed = fewest_removals(increasing, steps, report);
+            if removed.len() <= rules.removals {
+                Safety::Dampened(removed)
+            } else {
+                Safety::Unsafe
+            }
+        }
+    }
+}
+
+/// Safety of a report, the Problem Dampener removing as few levels as possible, the first one
+/// that makes the report safe when removing one is enough.
+pub fn analyze_with(report: &[u8], rules: &Rules) -> Safety {
+    let directions = [
+        analyze_xcreasing(true, rules, report),
+        analyze_xcreasing(false, rules, report),
+    ];
+    if directions.contains(&Safety::Safe) {
+        return Safety::Safe;
+    }
+    directions
+        .into_iter()
+        .filter_map(|safety| match safety {
+            Safety::Dampened(removed) => Some(removed),
+            _ => None,
+        })
+        .min_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)))
+        .map_or(Safety::Unsafe, Safety::Dampened)
+}
+
+/// Safety of every report.
+pub fn analyze_all(reports: &[Vec<u8>], rules: &Rules) -> Vec<Safety> {
+    reports
+        .iter()
+        .map(|report| analyze_with(report, rules))
+        .collect()
+}
+
+fn solve_part1(reports: &[Vec<u8>]) -> usize {
+    analyze_all(reports, &Rules::PUZZLE)
+        .into_iter()
+        .filter(|safety| *safety == Safety::Safe)
+        .count()
+}
+
+fn solve_part2(reports: &[Vec<u8>]) -> usize {
+    analyze_all(reports, &Rules::PUZZLE)
+        .into_iter()
+        .filter(|safety| *safety != Safety::Unsafe)
+        .count()
+}
+
+pub struct Day02;
+register_day!(2, Day02);
+
+impl Day for Day02 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |reports| Ok(Solution::from(solve_part1(reports))),
+            |reports| Ok(Solution::from(solve_part2(reports))),
+        )
+    }
+
+    /// Generate `size` reports, mostly safe or almost safe.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            let len = rng.between(5, 8) as usize;
+            let direction = if rng.chance(0.5) { 1 } else { -1 };
+            let mut levels = vec![rng.between(25, 75)];
+            for _ in 1..len {
+                levels.push(levels.last().unwrap() + direction * rng.between(1, 3));
+            }
+            if rng.chance(0.4) {
+                let i = rng.below(len);
+                levels[i] = rng.between(1, 99);
+            }
+            let report = levels.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+            input.push_str(&report.join(" "));
+            input.push('\n');
+        }
+        input
+    }
+
+    /// Lines of space separated numbers.
+    fn looks_like(&self, input: &str) -> bool {
+        shape::all_lines(input, |line| shape::is_int_list(line, " "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(2, 1);
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            prepare("1 2 3\n4 x 5\n"),
+            Err(DayError::Malformed(
+                "line 2: \"x\" is not a level".to_owned()
+            ))
+        );
+        assert_eq!(
+            prepare("1 2 300\n").unwrap_err().to_string(),
+            "malformed input: line 1: \"300\" is not a level"
+        );
+    }
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 2);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), 4);
+    }
+
+    fn analyze(report: &[u8]) -> Safety {
+        analyze_with(report, &Rules::PUZZLE)
+    }
+
+    fn check_increasing_with_dampener(report: &[u8]) -> bool {
+        analyze_xcreasing(true, &Rules::PUZZLE, report) != Safety::Unsafe
+    }
+
+    fn check_decreasing_with_dampener(report: &[u8]) -> bool {
+        analyze_xcreasing(false, &Rules::PUZZLE, report) != Safety::Unsafe
+    }
+
+    #[test]
+    fn increasing() {
+        assert!(check_increasing_with_dampener(&[50, 48, 50]));
+        assert!(!check_increasing_with_dampener(&[50, 48, 48, 50]));
+    }
+
+    #[test]
+    fn decreasing() {
+        assert!(check_decreasing_with_dampener(&[50, 48, 50]));
+        assert!(!check_decreasing_with_dampener(&[50, 48, 48, 50]));
+        assert!(check_decreasing_with_dampener(&[50, 48, 50]));
+    }
+
+    #[test]
+    fn bugs() {
+        assert!(check_decreasing_with_dampener(&[26, 25, 22, 24, 23]));
+        assert!(check_increasing_with_dampener(&[66, 68, 67, 68, 70]));
+        assert!(check_increasing_with_dampener(&[
+            53, 50, 54, 56, 59, 60, 62
+        ]));
+    }
+
+    #[test]
+    fn analysis() {
+        use Safety::*;
+        assert_eq!(
+            analyze_all(&prepare(EXAMPLE_INPUT).unwrap(), &Rules::PUZZLE),
+            vec![
+                Safe,
+                Unsafe,
+                Unsafe,
+                Dampened(vec![1]),
+                Dampened(vec![2]),
+                Safe
+            ]
+        );
+        assert_eq!(analyze(&[50, 48, 50]), Dampened(vec![0]));
+        assert_eq!(analyze(&[1, 2, 3, 9]), Dampened(vec![3]));
+        assert_eq!(analyze(&[]), Safe);
+
+        // same as removing every level in turn
+        let reports = prepare(&Day02.generate(500, &mut Rng::new(2))).unwrap();
+        for report in reports {
+            let removed = (0..report.len()).find(|&i| {
+                let mut dampened = report.clone();
+                dampened.remove(i);
+                first_break(true, &(1..=3), &dampened, None).is_none()
+                    || first_break(false, &(1..=3), &dampened, None).is_none()
+            });
+            let expected = if first_break(true, &(1..=3), &report, None).is_none()
+                || first_break(false, &(1..=3), &report, None).is_none()
+            {
+                Safe
+            } else {
+                removed.map_or(Unsafe, |i| Dampened(vec![i]))
+            };
+            assert_eq!(analyze(&report), expected, "{:?}", report);
+        }
+    }
+
+    #[test]
+    fn rules() {
+        use Safety::*;
+        let reports = prepare(EXAMPLE_INPUT).unwrap();
+        let strict = Rules {
+            removals: 0,
+            ..Rules::PUZZLE
+        };
+        assert_eq!(
+            analyze_all(&reports, &strict),
+            vec![Safe, Unsafe, Unsafe, Unsafe, Unsafe, Safe]
+        );
+        // 9 7 6 2 1 and 1 2 7 8 9 have steps of 4 and 5
+        let long_steps = Rules {
+            steps: 1..=5,
+            removals: 0,
+        };
+        assert_eq!(
+            analyze_all(&reports, &long_steps),
+            vec![Safe, Safe, Safe, Unsafe, Unsafe, Safe]
+        );
+        let two_removals = Rules {
+            removals: 2,
+            ..Rules::PUZZLE
+        };
+        assert_eq!(
+            analyze_with(&[1, 5, 2, 9, 3, 4], &two_removals),
+            Dampened(vec![1, 3])
+        );
+        assert_eq!(analyze_with(&[1, 5, 2, 9, 3, 4], &Rules::PUZZLE), Unsafe);
+        assert_eq!(
+            analyze_with(&[9, 1, 1, 8, 7], &two_removals),
+            Dampened(vec![1, 2])
+        );
+        assert_eq!(analyze_with(&[1, 1, 1, 1], &two_removals), Unsafe);
+
+        // the chain agrees with the single removal
+        for report in prepare(&Day02.generate(500, &mut Rng::new(3))).unwrap() {
+            for increasing in [true, false] {
+                let removed = fewest_removals(increasing, &(1..=3), &report);
+                let single = analyze_xcreasing(increasing, &Rules::PUZZLE, &report);
+                match removed.len() {
+                    0 => assert_eq!(single, Safe),
+                    1 => assert!(matches!(single, Dampened(_)), "{:?}", report),
+                    _ => assert_eq!(single, Unsafe, "{:?}", report),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use crate::days::{Day, Parsed};
+use crate::etc::parse::Scanner;
+use crate::etc::rng::Rng;
+use crate::etc::solution::DayError;
+use crate::{Solution, Variant};
+use std::io::{self, Read};
+
+/// A valid instruction of the corrupted memory.
+#[derive(Debug, PartialEq)]
+pub enum Instruction {
+    /// `mul(a,b)`
+    Mul(u64, u64),
+    /// `do()`, enabling the following multiplications
+    Do,
+    /// `don't()`, disabling the following multiplications
+    Dont,
+}
+
+/// Consume an operand of `mul`, a number of 1 to 3 digits.
+fn operand(scanner: &mut Scanner) -> Option<u64> {
+    let digits = scanner
+        .rest()
+        .bytes()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    (1..=3).contains(&digits).then(|| scanner.unsigned())?
+}
+
+/// Consume the instruction at the position of the scanner, if any.
+fn instruction(scanner: &mut Scanner) -> Option<Instruction> {
+    scanner
+        .attempt(|s| {
+            s.literal("mul(")?;
+            let a = operand(s)?;
+            s.literal(",")?;
+            let b = operand(s)?;
+            s.literal(")")?;
+            Some(Instruction::Mul(a, b))
+        })
+        .or_else(|| scanner.literal("do()").map(|_| Instruction::Do))
+        .or_else(|| scanner.literal("don't()").map(|_| Instruction::Dont))
+}
+
+/// Scan the valid instructions of the corrupted memory, skipping everything else.
+pub fn instructions(input: &str) -> impl Iterator<Item = Instruction> {
+    let mut scanner = Scanner::new(input);
+    std::iter::from_fn(move || {
+        loop {
+            let instruction = instruction(&mut scanner);
+            if instruction.is_some() {
+                return instruction;
+            }
+            if !scanner.skip() {
+                return None;
+            }
+        }
+    })
+}
+
+/// Length of the longest instruction, `mul(123,456)`: no instruction starting further than this
+/// from the end of a chunk can be cut by the next chunk.
+const LONGEST_INSTRUCTION: usize = 12;
+
+/// Size of the chunks read by the streaming variant.
+pub const STREAM_CHUNK: usize = 64 * 1024;
+
+/// Scan the valid instructions of corrupted memory read `chunk_size` bytes at a time, holding no
+/// more than a chunk and the few bytes of an instruction spanning chunks in memory.
+pub fn stream_instructions<R: Read>(
+    mut reader: R,
+    chunk_size: usize,
+) -> impl Iterator<Item = io::Result<Instruction>> {
+    let mut text = String::new();
+    // start of the text not scanned yet
+    let mut start = 0;
+    let mut chunk = vec![0; chunk_size.max(1)];
+    let mut end = false;
+    std::iter::from_fn(move || {
+        loop {
+            // start positions of instructions that cannot be cut by the next chunk
+            let scannable = if end {
+                text.len()
+            } else {
+                text.len().saturating_sub(LONGEST_INSTRUCTION - 1)
+            };
+            let mut scanner = Scanner::new(&text[start..]);
+            let mut found = None;
+            while text.len() - scanner.rest().len() < scannable {
+                found = instruction(&mut scanner);
+                if found.is_some() {
+                    break;
+                }
+                scanner.skip();
+            }
+            start = text.len() - scanner.rest().len();
+            if found.is_some() || end {
+                return found.map(Ok);
+            }
+            // only the unscanned tail is kept for the next chunk
+            text.drain(..start);
+            start = 0;
+            match reader.read(&mut chunk) {
+                Ok(0) => end = true,
+                // non-ASCII bytes are never part of instructions, and would otherwise risk being
+                // split between chunks
+                Ok(read) => text.extend(
+                    chunk[..read]
+                        .iter()
+                        .map(|&b| if b.is_ascii() { b as char } else { '?' }),
+                ),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => {
+                    end = true;
+                    text.clear();
+                    return Some(Err(err));
+                }
+            }
+        }
+    })
+}
+
+/// Both answers from corrupted memory read in chunks, see [`stream_instructions`].
+pub fn solve_stream<R: Read>(reader: R, chunk_size: usize) -> io::Result<(u64, u64)> {
+    let (mut part1, mut part2) = (0, 0);
+    let mut factor = 1;
+    for instruction in stream_instructions(reader, chunk_size) {
+        match instruction? {
+            Instruction::Mul(a, b) => {
+                part1 += a * b;
+                part2 += factor * a * b;
+            }
+            Instruction::Do => factor = 1,
+            Instruction::Dont => factor = 0,
+        }
+    }
+    Ok((part1, part2))
+}
+
+/// Read the valid instructions of the corrupted memory.
+pub fn prepare(input: &str) -> Vec<Instruction> {
+    instructions(input).collect()
+}
+
+fn solve_part1(instructions: &[Instruction]) -> u64 {
+    instructions
+        .iter()
+        .map(|instruction| match instruction {
+            Instruction::Mul(a, b) => a * b,
+            _ => 0,
+        })
+        .sum()
+}
+
+fn solve_part2(instructions: &[Instruction]) -> u64 {
+    let mut factor = 1;
+    instructions
+        .iter()
+        .map(|instruction| match instruction {
+            Instruction::Mul(a, b) => factor * a * b,
+            Instruction::Do => {
+                factor = 1;
+                0
+            }
+            Instruction::Dont => {
+                factor = 0;
+                0
+            }
+        })
+        .sum()
+}
+
+/// The original implementation, with regular expressions.
+#[cfg(feature = "regex")]
+mod with_regex {
+    use regex::Regex;
+
+    pub fn solve_part1(input: &str) -> u64 {
+        let re = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)").unwrap();
+        re.captures_iter(input)
+            .map(|caps| {
+                caps.get(1).unwrap().as_str().parse::<u64>().unwrap()
+                    * caps.get(2).unwrap().as_str().parse::<u64>().unwrap()
+            })
+            .sum()
+    }
+
+    pub fn solve_part2(input: &str) -> u64 {
+        let re = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)|do\(\)|don't\(\)").unwrap();
+        let mut factor = 1;
+        re.captures_iter(input)
+            .map(|caps| {
+                let all = caps.get(0).unwrap().as_str();
+                if all.starts_with("mul") {
+                    factor
+                        * caps.get(1).unwrap().as_str().parse::<u64>().unwrap()
+                        * caps.get(2).unwrap().as_str().parse::<u64>().unwrap()
+                } else if all.starts_with("don") {
+                    factor = 0;
+                    0
+                } else {
+                    assert!(all.starts_with("do"));
+                    factor = 1;
+                    0
+                }
+            })
+            .sum()
+    }
+}
+
+pub struct Day03;
+register_day!(3, Day03);
+
+impl Day for Day03 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::new(
+            prepare(input),
+            |instructions| Solution::from(solve_part1(instructions)),
+            |instructions| Solution::from(solve_part2(instructions)),
+        )
+    }
+
+    /// Generate about `size` characters of corrupted memory.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+        while input.len() < size {
+            let a = rng.between(1, 999);
+            let b = rng.between(1, 999);
+            let fragment = match rng.below(8) {
+                0..=2 => format!("mul({a},{b})"),
+                3 => format!("mul[{a},{b}]"),
+                4 => format!("mul({a}, {b})"),
+                5 => "do()".to_owned(),
+                6 => "don't()".to_owned(),
+                _ => (0..rng.between(1, 6))
+                    .map(|_| *rng.choose(&['%', '@', '^', '!', '(', ')', ',', '*', 'x', 'u', ' ']))
+                    .collect(),
+            };
+            input.push_str(&fragment);
+        }
+        input.push('\n');
+        input
+    }
+
+    /// Corrupted memory with at least one `mul` instruction.
+    fn looks_like(&self, input: &str) -> bool {
+        input.contains("mul(")
+    }
+
+    fn variants(&self) -> &'static [Variant] {
+        VARIANTS
+    }
+}
+
+/// Implementation variants, cross-validated in paranoid mode.
+const VARIANTS: &[Variant] = &[
+    ("scanner", |input| Day03.solve(input)),
+    ("streaming", |input| {
+        let (sol1, sol2) = solve_stream(input.as_bytes(), STREAM_CHUNK)
+            .map_err(|err| DayError::Malformed(err.to_string()))?;
+        Ok(crate::DayResult::new(3, sol1.into(), sol2.into()))
+    }),
+    #[cfg(feature = "regex")]
+    ("regex", |input| {
+        let sol1 = Solution::from(with_regex::solve_part1(&input));
+        let sol2 = Solution::from(with_regex::solve_part2(&input));
+        Ok(crate::DayResult::new(3, sol1, sol2))
+    }),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT1: &str = examples::example(3, 1);
+
+    const EXAMPLE_INPUT2: &str = examples::example(3, 2);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT1)), 161);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT2)), 48);
+    }
+
+    #[test]
+    fn scanning() {
+        use Instruction::*;
+        assert_eq!(
+            prepare("mul(1234,5)mul(12,345)mul(,1)mul(1,2]don't()mmul(0,999)do()do("),
+            vec![Mul(12, 345), Dont, Mul(0, 999), Do]
+        );
+        // the digits of a too long number are not an instruction either
+        assert_eq!(prepare("mul(1,2345)mul(2,3)"), vec![Mul(2, 3)]);
+    }
+
+    #[test]
+    fn streaming() {
+        let input = Day03.generate(2000, &mut Rng::new(3));
+        let expected = prepare(&input);
+        // chunks cutting instructions everywhere
+        for chunk_size in [1, 2, 5, 11, 12, 13, 100, 4096] {
+            let streamed = stream_instructions(input.as_bytes(), chunk_size)
+                .collect::<io::Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(streamed, expected, "chunks of {}", chunk_size);
+        }
+        assert_eq!(
+            solve_stream(input.as_bytes(), 7).unwrap(),
+            (solve_part1(&expected), solve_part2(&expected))
+        );
+        let memory = "é mul(2,4)ümul(1234,5)don't()mul(3,3)".as_bytes();
+        assert_eq!(solve_stream(memory, 3).unwrap(), (17, 8));
+    }
+}
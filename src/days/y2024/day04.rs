@@ -0,0 +1,243 @@
+use crate::days::{Day, Parsed};
+use crate::etc::grid::{ALL_DIRECTIONS, Point};
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+use crate::{DayResult, Grid, Solution, Variant};
+
+/// Read the word search grid.
+pub fn prepare(input: &str) -> Result<Grid, DayError> {
+    Ok(Grid::try_new(input)?)
+}
+
+fn solve_part1(grid: &Grid) -> usize {
+    grid.count_word("XMAS", &ALL_DIRECTIONS)
+}
+
+fn solve_part2(grid: &Grid) -> usize {
+    grid.count_cross("MAS")
+}
+
+mod fast {
+    //! Fast implementation, matching all the columns of a line at once with one bitset per line
+    //! and letter of the word, column `c` being bit `c % 64` of word `c / 64` of the bitset.
+    use super::*;
+
+    /// The bitsets of the letters of a word in a grid.
+    pub(super) struct Board {
+        lines: i64,
+        /// Number of words of the bitset of a line.
+        words: usize,
+        /// The bitsets of the lines, one after the other, for each letter of the word.
+        planes: Vec<Vec<u64>>,
+    }
+
+    impl Board {
+        pub(super) fn new(grid: &Grid, word: &str) -> Self {
+            let words = grid.columns.div_ceil(64);
+            let planes = word
+                .chars()
+                .map(|letter| {
+                    grid.items
+                        .chunks(grid.columns)
+                        .flat_map(|line| line.chunks(64))
+                        .map(|chunk| {
+                            chunk
+                                .iter()
+                                .enumerate()
+                                .fold(0, |bits, (i, &c)| bits | (u64::from(c == letter) << i))
+                        })
+                        .collect()
+                })
+                .collect();
+            Board {
+                lines: grid.lines as i64,
+                words,
+                planes,
+            }
+        }
+
+        /// 64 bits of a line of the bitset of a letter, starting at `column`, cleared outside of
+        /// the grid.
+        fn bits(&self, plane: usize, line: i64, column: i64) -> u64 {
+            if !(0..self.lines).contains(&line) {
+                return 0;
+            }
+            let word = |w: i64| {
+                if (0..self.words as i64).contains(&w) {
+                    self.planes[plane][line as usize * self.words + w as usize]
+                } else {
+                    0
+                }
+            };
+            let (w, shift) = (column.div_euclid(64), column.rem_euclid(64));
+            let high = if shift == 0 {
+                0
+            } else {
+                word(w + 1) << (64 - shift)
+            };
+            (word(w) >> shift) | high
+        }
+
+        /// The columns of a line from which the word is read, starting at `offset` from them and
+        /// applying `step` between letters, possibly from its last letter.
+        fn matches(&self, line: i64, offset: Point, step: Point, reversed: bool) -> Vec<u64> {
+            let len = self.planes.len();
+            let mut columns = vec![!0; self.words];
+            for i in 0..len {
+                let plane = if reversed { len - 1 - i } else { i };
+                let at = offset + step * i as i64;
+                for (w, bits) in columns.iter_mut().enumerate() {
+                    *bits &= self.bits(plane, line + at.0, (w * 64) as i64 + at.1);
+                }
+            }
+            columns
+        }
+
+        /// See [`Grid::count_word`].
+        pub(super) fn count_word(&self, directions: &[Point]) -> usize {
+            let mut count = 0;
+            for &step in directions {
+                for line in 0..self.lines {
+                    let columns = self.matches(line, Point(0, 0), step, false);
+                    count += columns
+                        .iter()
+                        .map(|bits| bits.count_ones() as usize)
+                        .sum::<usize>();
+                }
+            }
+            count
+        }
+
+        /// See [`Grid::count_cross`].
+        pub(super) fn count_cross(&self) -> usize {
+            let half = (self.planes.len() / 2) as i64;
+            let diagonal = |line, step: Point| {
+                let forward = self.matches(line, step * -half, step, false);
+                let backward = self.matches(line, step * -half, step, true);
+                forward.into_iter().zip(backward).map(|(f, b)| f | b)
+            };
+            let mut count = 0;
+            for line in 0..self.lines {
+                let crosses = diagonal(line, Point::SOUTH_EAST)
+                    .zip(diagonal(line, Point::SOUTH_WEST))
+                    .enumerate()
+                    // the middle letter, also keeping the columns inside the grid
+                    .map(|(w, (a, b))| a & b & self.bits(half as usize, line, w as i64 * 64));
+                count += crosses
+                    .map(|bits| bits.count_ones() as usize)
+                    .sum::<usize>();
+            }
+            count
+        }
+    }
+
+    pub(super) fn solve_part1(grid: &Grid) -> usize {
+        Board::new(grid, "XMAS").count_word(&ALL_DIRECTIONS)
+    }
+
+    pub(super) fn solve_part2(grid: &Grid) -> usize {
+        Board::new(grid, "MAS").count_cross()
+    }
+}
+
+pub struct Day04;
+register_day!(4, Day04);
+
+impl Day for Day04 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |grid| Ok(Solution::from(fast::solve_part1(grid))),
+            |grid| Ok(Solution::from(fast::solve_part2(grid))),
+        )
+    }
+
+    /// Generate a `size` x `size` word search.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| {
+                let mut line = (0..size)
+                    .map(|_| *rng.choose(&['X', 'M', 'A', 'S']))
+                    .collect::<String>();
+                line.push('\n');
+                line
+            })
+            .collect()
+    }
+
+    /// A grid of `XMAS` letters.
+    fn looks_like(&self, input: &str) -> bool {
+        matches!(shape::blocks(input).as_slice(), [grid] if shape::is_grid(grid, "XMAS"))
+    }
+
+    fn variants(&self) -> &'static [Variant] {
+        VARIANTS
+    }
+}
+
+/// Implementation variants, cross-validated in paranoid mode.
+const VARIANTS: &[Variant] = &[
+    ("fast", |input| Day04.solve(input)),
+    ("grid", |input| {
+        let grid = prepare(&input)?;
+        Ok(DayResult::new(
+            4,
+            Solution::from(solve_part1(&grid)),
+            Solution::from(solve_part2(&grid)),
+        ))
+    }),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(4, 1);
+
+    #[test]
+    fn test_prepare() {
+        let grid = prepare(EXAMPLE_INPUT).unwrap();
+        assert_eq!(grid.lines, 10);
+        assert_eq!(grid.columns, 10);
+        assert_eq!(grid.items[0], 'M');
+        assert_eq!(grid.items[10], 'M');
+        assert_eq!(grid.items[11], 'S');
+        assert_eq!(grid.items[20], 'A')
+    }
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 18);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), 9);
+    }
+
+    #[test]
+    fn bitboards() {
+        let grid = prepare(EXAMPLE_INPUT).unwrap();
+        assert_eq!(fast::solve_part1(&grid), 18);
+        assert_eq!(fast::solve_part2(&grid), 9);
+        // lines over several words
+        let mut rng = Rng::new(4);
+        for size in [1, 3, 63, 64, 65, 130] {
+            let grid = prepare(&Day04.generate(size, &mut rng)).unwrap();
+            assert_eq!(
+                fast::solve_part1(&grid),
+                solve_part1(&grid),
+                "size {}",
+                size
+            );
+            assert_eq!(
+                fast::solve_part2(&grid),
+                solve_part2(&grid),
+                "size {}",
+                size
+            );
+        }
+    }
+}
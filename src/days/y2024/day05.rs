@@ -0,0 +1,382 @@
+use crate::Solution;
+use crate::days::{Day, Parsed};
+use crate::etc::graph::{AdjGraph, topo_sort};
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+/// A page number.
+pub type Page = u32;
+/// The page ordering rules, `[a, b]` meaning `a` must be printed before `b`.
+pub type PageOrdering = BTreeSet<[Page; 2]>;
+/// The pages of every update, in order.
+pub type Updates = Vec<Vec<Page>>;
+
+fn page(text: &str) -> Result<Page, DayError> {
+    text.parse()
+        .map_err(|_| DayError::Malformed(format!("{:?} is not a page number", text)))
+}
+
+/// Read the page ordering rules and the updates.
+pub fn prepare(input: &str) -> Result<(PageOrdering, Updates), DayError> {
+    let (orderings, updates) = input.split_once("\n\n").ok_or_else(|| {
+        DayError::Malformed("no empty line between the rules and the updates".to_owned())
+    })?;
+    let orderings = orderings
+        .split_ascii_whitespace()
+        .map(|ordering| {
+            let (a, b) = ordering.split_once('|').ok_or_else(|| {
+                DayError::Malformed(format!("{:?} is not a page ordering rule", ordering))
+            })?;
+            Ok([page(a)?, page(b)?])
+        })
+        .collect::<Result<_, DayError>>()?;
+    let updates = updates
+        .split_ascii_whitespace()
+        .map(|update| update.split(',').map(page).collect())
+        .collect::<Result<_, _>>()?;
+    Ok((orderings, updates))
+}
+
+/// The rules between the pages of an update: the pages are the nodes, in the order of the
+/// update, with an edge from every page to each page that must be printed after it.
+pub fn precedence_graph(orderings: &PageOrdering, update: &[Page]) -> AdjGraph<Page> {
+    let mut graph = AdjGraph::new();
+    for &page in update {
+        graph.add_node(page);
+    }
+    for (i, a) in update.iter().enumerate() {
+        for (j, b) in update.iter().enumerate() {
+            if orderings.contains(&[*a, *b]) {
+                graph.add_edge(i, j, ());
+            }
+        }
+    }
+    graph
+}
+
+/// Whether no rule between the pages of an update is broken.
+fn check_update(orderings: &PageOrdering, update: &[Page]) -> bool {
+    let graph = precedence_graph(orderings, update);
+    graph.edge_ids().all(|edge| {
+        let (before, after, _) = graph.edge(edge);
+        before < after
+    })
+}
+
+/// Order the pages of an update following the rules between them, pages without rules between
+/// them staying in the order of the update. Fail if the rules have a cycle.
+pub fn reorder_update(orderings: &PageOrdering, update: &[Page]) -> Result<Vec<Page>, DayError> {
+    let graph = precedence_graph(orderings, update);
+    let edges = graph.edge_ids().map(|edge| {
+        let (before, after, _) = graph.edge(edge);
+        (before, after)
+    });
+    match topo_sort(graph.node_ids(), edges) {
+        Ok(order) => Ok(order.into_iter().map(|node| graph[node]).collect()),
+        Err(cycle) => {
+            let pages = cycle.unsorted.iter().map(|&node| graph[node]);
+            Err(DayError::Malformed(format!(
+                "the page ordering rules of update {:?} have a cycle among pages {:?}",
+                update,
+                pages.collect::<Vec<_>>()
+            )))
+        }
+    }
+}
+
+/// The order of all the pages of the rules, when the rules order every pair of them, directly
+/// or not, without cycles. The rules of puzzle inputs usually have cycles, only the pages of each
+/// update being ordered.
+pub fn global_order(orderings: &PageOrdering) -> Option<Vec<Page>> {
+    let order = topo_sort(std::iter::empty(), orderings.iter().map(|[a, b]| (*a, *b))).ok()?;
+    // with a rule between successive pages, the rules order every pair of pages
+    order
+        .windows(2)
+        .all(|pages| orderings.contains(&[pages[0], pages[1]]))
+        .then_some(order)
+}
+
+/// Rank of every page in the global order, see [`global_order`].
+pub type Ranks = HashMap<Page, usize>;
+
+/// The ranks of the pages, if the rules order them all.
+pub fn ranks(orderings: &PageOrdering) -> Option<Ranks> {
+    global_order(orderings).map(|order| order.into_iter().zip(0..).collect())
+}
+
+/// The ranks of the pages of an update, if they all have one.
+fn update_ranks(ranks: Option<&Ranks>, update: &[Page]) -> Option<Vec<usize>> {
+    update
+        .iter()
+        .map(|page| ranks?.get(page).copied())
+        .collect()
+}
+
+/// Whether an update is correctly ordered, by looking up the ranks of its pages when they all
+/// have one, and by checking the rules between its pages otherwise.
+fn is_ordered(orderings: &PageOrdering, ranks: Option<&Ranks>, update: &[Page]) -> bool {
+    match update_ranks(ranks, update) {
+        Some(ranks) => ranks.is_sorted(),
+        None => check_update(orderings, update),
+    }
+}
+
+/// The update correctly ordered, see [`is_ordered`].
+fn fix_update(
+    orderings: &PageOrdering,
+    ranks: Option<&Ranks>,
+    update: &[Page],
+) -> Result<Vec<Page>, DayError> {
+    match update_ranks(ranks, update) {
+        Some(page_ranks) => {
+            let mut ranked = page_ranks
+                .into_iter()
+                .zip(update.iter().copied())
+                .collect::<Vec<_>>();
+            ranked.sort_unstable();
+            Ok(ranked.into_iter().map(|(_, page)| page).collect())
+        }
+        None => reorder_update(orderings, update),
+    }
+}
+
+fn solve_part1_with(
+    (orderings, updates): &(PageOrdering, Updates),
+    ranks: Option<&Ranks>,
+) -> usize {
+    updates
+        .iter()
+        .filter(|update| is_ordered(orderings, ranks, update))
+        .map(|update| update[update.len() / 2] as usize)
+        .sum()
+}
+
+fn solve_part2_with(
+    (orderings, updates): &(PageOrdering, Updates),
+    ranks: Option<&Ranks>,
+) -> Result<usize, DayError> {
+    let mut sum = 0;
+    for update in updates
+        .iter()
+        .filter(|update| !is_ordered(orderings, ranks, update))
+    {
+        let update = fix_update(orderings, ranks, update)?;
+        sum += update[update.len() / 2] as usize;
+    }
+    Ok(sum)
+}
+
+fn solve_part1(parsed: &(PageOrdering, Updates)) -> usize {
+    solve_part1_with(parsed, ranks(&parsed.0).as_ref())
+}
+
+fn solve_part2(parsed: &(PageOrdering, Updates)) -> Result<usize, DayError> {
+    solve_part2_with(parsed, ranks(&parsed.0).as_ref())
+}
+
+/// The global order of the pages if any, then the fixed updates.
+fn explain(input: &str) -> String {
+    let (orderings, updates) = match prepare(input) {
+        Ok(parsed) => parsed,
+        Err(err) => return err.to_string(),
+    };
+    let ranks = ranks(&orderings);
+    let mut out = String::new();
+    match global_order(&orderings) {
+        Some(order) => writeln!(out, "Page order: {:?}", order).unwrap(),
+        None => writeln!(
+            out,
+            "No page order: the rules have a cycle or leave pages unordered, updates are checked \
+             rule by rule"
+        )
+        .unwrap(),
+    }
+    for (i, update) in updates.iter().enumerate() {
+        if is_ordered(&orderings, ranks.as_ref(), update) {
+            writeln!(out, "Update {}: {:?} is in order", i + 1, update).unwrap();
+        } else {
+            match fix_update(&orderings, ranks.as_ref(), update) {
+                Ok(fixed) => writeln!(out, "Update {}: {:?} fixed to {:?}", i + 1, update, fixed),
+                Err(err) => writeln!(out, "Update {}: {}", i + 1, err),
+            }
+            .unwrap();
+        }
+    }
+    out
+}
+
+pub struct Day05;
+register_day!(5, Day05);
+
+impl Day for Day05 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |parsed| Ok(Solution::from(solve_part1(parsed))),
+            |parsed| solve_part2(parsed).map(Solution::from),
+        )
+    }
+
+    /// Generate rules of a random total ordering of pages and `size` updates.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut pages: Vec<Page> = (10..100).collect();
+        rng.shuffle(&mut pages);
+        pages.truncate(49);
+
+        let mut input = String::new();
+        for (i, a) in pages.iter().enumerate() {
+            for b in &pages[i + 1..] {
+                input.push_str(&format!("{a}|{b}\n"));
+            }
+        }
+        input.push('\n');
+
+        for _ in 0..size {
+            let len = 2 * rng.below(10) + 5;
+            let mut update = pages.clone();
+            rng.shuffle(&mut update);
+            update.truncate(len);
+            if rng.chance(0.5) {
+                // correctly-ordered update
+                update.sort_by_key(|page| pages.iter().position(|p| p == page));
+            }
+            let update = update.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+            input.push_str(&update.join(","));
+            input.push('\n');
+        }
+        input
+    }
+
+    /// Page ordering rules, then updates.
+    fn looks_like(&self, input: &str) -> bool {
+        match shape::blocks(input).as_slice() {
+            [rules, updates] => {
+                rules.iter().all(|line| shape::matches(line, "#|#"))
+                    && updates.iter().all(|line| shape::is_int_list(line, ","))
+            }
+            _ => false,
+        }
+    }
+
+    fn explainer(&self) -> Option<fn(&str) -> String> {
+        Some(explain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(5, 1);
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            prepare("1|2\n2|3\n"),
+            Err(DayError::Malformed(
+                "no empty line between the rules and the updates".to_owned()
+            ))
+        );
+        assert_eq!(
+            prepare("1|2\n2-3\n\n1,2\n"),
+            Err(DayError::Malformed(
+                "\"2-3\" is not a page ordering rule".to_owned()
+            ))
+        );
+        assert_eq!(
+            prepare("1|2\n\n1,x\n"),
+            Err(DayError::Malformed("\"x\" is not a page number".to_owned()))
+        );
+    }
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 143);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), Ok(123));
+    }
+
+    #[test]
+    fn precedence() {
+        let (orderings, _) = prepare(EXAMPLE_INPUT).unwrap();
+        let graph = precedence_graph(&orderings, &[97, 13, 75, 29, 47]);
+        assert_eq!(graph.node_count(), 5);
+        // every pair of pages has a rule
+        assert_eq!(graph.edge_count(), 10);
+        assert_eq!(
+            reorder_update(&orderings, &[97, 13, 75, 29, 47]),
+            Ok(vec![97, 75, 47, 29, 13])
+        );
+
+        // partial rules: pages without rules keep their order
+        let orderings = PageOrdering::from([[1, 2], [3, 2]]);
+        assert!(check_update(&orderings, &[1, 4, 3, 2]));
+        assert!(!check_update(&orderings, &[2, 4, 1, 3]));
+        assert_eq!(
+            reorder_update(&orderings, &[2, 4, 1, 3]),
+            Ok(vec![4, 1, 3, 2])
+        );
+
+        let cycle = PageOrdering::from([[1, 2], [2, 3], [3, 1]]);
+        assert_eq!(
+            reorder_update(&cycle, &[4, 1, 2, 3]),
+            Err(DayError::Malformed(
+                "the page ordering rules of update [4, 1, 2, 3] have a cycle among pages [1, 2, 3]"
+                    .to_owned()
+            ))
+        );
+        assert!(solve_part2(&(cycle, vec![vec![3, 2, 1]])).is_err());
+    }
+
+    #[test]
+    fn page_order() {
+        let parsed = prepare(EXAMPLE_INPUT).unwrap();
+        assert_eq!(
+            global_order(&parsed.0),
+            Some(vec![97, 75, 47, 61, 53, 29, 13])
+        );
+        assert!(explain(EXAMPLE_INPUT).starts_with(
+            "Page order: [97, 75, 47, 61, 53, 29, 13]\n\
+             Update 1: [75, 47, 61, 53, 29] is in order\n"
+        ));
+        // cycles, and pages without rules between them
+        assert_eq!(
+            global_order(&PageOrdering::from([[1, 2], [2, 3], [3, 1]])),
+            None
+        );
+        assert_eq!(global_order(&PageOrdering::from([[1, 2], [3, 2]])), None);
+        assert_eq!(
+            global_order(&PageOrdering::from([[1, 2], [2, 3]])),
+            Some(vec![1, 2, 3])
+        );
+
+        // same answers with the ranks as with the rules
+        let parsed = prepare(&Day05.generate(100, &mut Rng::new(5))).unwrap();
+        let ranks = ranks(&parsed.0);
+        assert!(ranks.is_some());
+        assert_eq!(
+            solve_part1_with(&parsed, ranks.as_ref()),
+            solve_part1_with(&parsed, None)
+        );
+        assert_eq!(
+            solve_part2_with(&parsed, ranks.as_ref()),
+            solve_part2_with(&parsed, None)
+        );
+    }
+
+    #[test]
+    fn preparation() {
+        let (orderings, updates) = prepare(EXAMPLE_INPUT).unwrap();
+        assert!(orderings.contains(&[97, 13]));
+        assert!(orderings.contains(&[53, 13]));
+        assert_eq!(orderings.len(), 21);
+        assert!(updates.contains(&vec![61, 13, 29]));
+    }
+}
@@ -0,0 +1,478 @@
+use crate::days::{Day, Parsed};
+use crate::etc::checkpoint::{self, Checkpoint, Resumable};
+use crate::etc::grid::{BitGrid, Direction};
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::simulation::{Harness, Simulation};
+use crate::etc::solution::{DayError, DayResult};
+use crate::etc::viz::{Frame, Visualize};
+use crate::{Grid, Point, Solution, Variant};
+use rayon::prelude::*;
+
+/// A position of the lab map.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Cell {
+    #[default]
+    Empty,
+    Obstruction,
+}
+
+pub type Map = Grid<Cell>;
+
+/// Read the lab map and the starting position of the guard, who faces north.
+pub fn prepare(input: &str) -> Result<(Map, Point), DayError> {
+    let grid = Grid::try_new(input)?;
+    if let Some(c) = grid.iter().find(|c| !matches!(c, '.' | '#' | '^')) {
+        return Err(DayError::Malformed(format!(
+            "unexpected {:?} on the lab map",
+            c
+        )));
+    }
+    let guard = grid
+        .position(|&x| x == '^')
+        .ok_or_else(|| DayError::Malformed("no guard on the lab map".to_owned()))?;
+    Ok((
+        grid.new_from(|&x| {
+            if x == '#' {
+                Cell::Obstruction
+            } else {
+                Cell::Empty
+            }
+        }),
+        guard,
+    ))
+}
+
+mod slow {
+    //! Simple but slow implementation
+    #![allow(dead_code)]
+    use super::*;
+
+    /// Execute the guard's patrol, return the set of positions visited by the guard
+    /// and whether the patrol is a loop.
+    fn patrol(map: &Map, mut guard: Point) -> (std::collections::BTreeSet<Point>, bool) {
+        let mut direction = Point::NORTH;
+        let mut patrolled = std::collections::BTreeSet::new();
+        let mut locations = std::collections::BTreeSet::new();
+        loop {
+            if !patrolled.insert((guard, direction)) {
+                return (locations, true);
+            }
+
+            locations.insert(guard);
+
+            if let Some(ahead) = map.step(&guard, &direction) {
+                guard = match map[ahead] {
+                    Cell::Empty => ahead,
+                    Cell::Obstruction => {
+                        direction = direction.rotate_90_clockwise();
+                        guard
+                    }
+                };
+            } else {
+                return (locations, false);
+            }
+        }
+    }
+
+    pub fn solve_part1((map, guard): &(Map, Point)) -> usize {
+        patrol(map, *guard).0.len()
+    }
+
+    pub fn solve_part2((map, guard): &(Map, Point)) -> usize {
+        let (mut map, guard) = (map.clone(), *guard);
+        let mut positions = patrol(&map, guard).0;
+        positions.remove(&guard);
+        positions
+            .iter()
+            .filter(|obstruction| {
+                map[**obstruction] = Cell::Obstruction;
+                let is_loop = patrol(&map, guard).1;
+                map[**obstruction] = Cell::Empty;
+                is_loop
+            })
+            .count()
+    }
+}
+
+/// The guard's state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Guard {
+    position: Point,
+    direction: Direction,
+}
+
+/// The guard's patrol, one move or turn per step.
+struct Patrol<'a> {
+    map: &'a Map,
+    guard: Guard,
+    /// the `Set<(position, direction)>` of patrolled locations, one plane per direction.
+    patrolled: BitGrid,
+    /// whether the guard left the area
+    left: bool,
+    /// whether the guard is back on an already patrolled location and direction
+    is_loop: bool,
+}
+
+impl<'a> Patrol<'a> {
+    /// A patrol recording the patrolled locations in the given grid, cleared first.
+    fn new(map: &'a Map, start: Point, mut patrolled: BitGrid) -> Self {
+        patrolled.clear_all();
+        let mut patrol = Patrol {
+            map,
+            guard: Guard {
+                position: start,
+                direction: Direction::North,
+            },
+            patrolled,
+            left: false,
+            is_loop: false,
+        };
+        patrol.mark();
+        patrol
+    }
+
+    /// Mark the current guard location as patrolled, detecting loops.
+    fn mark(&mut self) {
+        let Guard {
+            position,
+            direction,
+        } = self.guard;
+        // if we already patrolled this location with current direction, the patrol is a loop
+        self.is_loop = self.patrolled.set(&position, direction.index());
+    }
+}
+
+impl Simulation for Patrol<'_> {
+    type State = Guard;
+
+    fn step(&mut self) {
+        if self.is_done() {
+            return;
+        }
+        let Guard {
+            position,
+            direction,
+        } = self.guard;
+        if let Some(ahead) = self.map.step(&position, &direction.to_point()) {
+            match self.map.unchecked_get(&ahead) {
+                Cell::Empty => self.guard.position = ahead,
+                Cell::Obstruction => self.guard.direction = direction.rotate_cw(),
+            }
+            self.mark();
+        } else {
+            self.left = true;
+        }
+    }
+
+    fn state(&self) -> &Guard {
+        &self.guard
+    }
+
+    fn is_done(&self) -> bool {
+        self.left || self.is_loop
+    }
+}
+
+mod fast {
+    //! Fast implementation
+    use super::*;
+
+    /// Execute the guard's patrol, return the set of positions visited by the guard
+    /// and whether the patrol is a loop.
+    pub(super) fn patrol(map: &Map, guard: Point, patrolled: BitGrid) -> (BitGrid, bool) {
+        let mut harness = Harness::new(Patrol::new(map, guard, patrolled));
+        harness.run();
+        let patrol = harness.into_inner();
+        (patrol.patrolled, patrol.is_loop)
+    }
+
+    /// The positions of the guard along the patrol, in order, up to leaving the area or looping.
+    pub(super) fn patrol_path(map: &Map, guard: Point) -> Vec<Point> {
+        let patrolled = BitGrid::new(map.lines, map.columns, 4);
+        let mut harness = Harness::new(Patrol::new(map, guard, patrolled));
+        let mut path: Vec<Point> = harness
+            .record(1, None)
+            .into_iter()
+            .map(|(_, guard)| guard.position)
+            .collect();
+        // turning in place
+        path.dedup();
+        path
+    }
+
+    pub fn solve_part1((map, guard): &(Map, Point)) -> usize {
+        patrol(map, *guard, BitGrid::new(map.lines, map.columns, 4))
+            .0
+            .positions()
+            .count()
+    }
+
+    /// Number of candidate obstructions tried in parallel by each step of the search.
+    const BATCH: usize = 256;
+
+    /// Try the candidate obstruction positions, a batch at a time, counting the ones causing a
+    /// loop.
+    struct ObstructionSearch {
+        map: Map,
+        guard: Point,
+        /// positions on the guard's original patrol, except the starting position.
+        candidates: Vec<usize>,
+        /// next candidate to try
+        next: usize,
+        /// number of candidates causing a loop so far
+        loops: usize,
+    }
+
+    impl ObstructionSearch {
+        fn new(map: Map, guard: Point) -> Self {
+            let (patrolled, _) = patrol(&map, guard, BitGrid::new(map.lines, map.columns, 4));
+            let candidates = patrolled
+                .positions()
+                .filter(|pos| *pos != guard)
+                .map(|pos| map.unchecked_index(&pos))
+                .collect::<Vec<_>>();
+            ObstructionSearch {
+                map,
+                guard,
+                candidates,
+                next: 0,
+                loops: 0,
+            }
+        }
+    }
+
+    impl Resumable for ObstructionSearch {
+        fn advance(&mut self) -> bool {
+            if self.next >= self.candidates.len() {
+                return false;
+            }
+            let end = self.candidates.len().min(self.next + BATCH);
+            let batch = &self.candidates[self.next..end];
+            let (map, guard) = (&self.map, self.guard);
+            // each thread has its own map to add obstructions to, and patrolled locations reused
+            // across candidates
+            self.loops += batch
+                .par_iter()
+                .map_init(
+                    || (map.clone(), BitGrid::new(map.lines, map.columns, 4)),
+                    |(map, patrolled), &obstruction| {
+                        map.set_at(obstruction, Cell::Obstruction);
+                        let (reused, is_loop) = patrol(map, guard, std::mem::take(patrolled));
+                        *patrolled = reused;
+                        map.set_at(obstruction, Cell::Empty);
+                        is_loop
+                    },
+                )
+                .filter(|&is_loop| is_loop)
+                .count();
+            self.next = end;
+            true
+        }
+
+        fn progress(&self) -> Vec<String> {
+            vec![self.next.to_string(), self.loops.to_string()]
+        }
+
+        fn restore(&mut self, progress: &[String]) -> Option<()> {
+            self.next = progress.first()?.parse().ok()?;
+            self.loops = progress.get(1)?.parse().ok()?;
+            Some(())
+        }
+    }
+
+    /// Count the obstructions trapping the guard in a loop, checkpointing the search under the
+    /// hash of the raw input.
+    pub fn solve_part2(parsed: &(Map, Point), input_hash: u64) -> usize {
+        let (map, guard) = parsed.clone();
+        let mut search = ObstructionSearch::new(map, guard);
+        Checkpoint::open("day06-part2", input_hash).run(&mut search);
+        search.loops
+    }
+}
+
+/// Colors of the patrol visualization.
+const FLOOR: [u8; 3] = [24, 24, 32];
+const OBSTRUCTION: [u8; 3] = [120, 120, 120];
+const VISITED: [u8; 3] = [230, 180, 40];
+const LOOP_CAUSING: [u8; 3] = [60, 200, 90];
+const GUARD: [u8; 3] = [230, 40, 40];
+
+/// Default number of frames of the patrol visualization, besides the last one once the guard is
+/// gone.
+const PATROL_FRAMES: usize = 200;
+
+/// The guard's patrol: the visited positions, the ones where an obstruction would trap the guard
+/// in a loop being checked as the guard first reaches them.
+struct PatrolViz;
+
+impl Visualize for PatrolViz {
+    fn frames(&self, input: &str, every: Option<usize>, sink: &mut dyn FnMut(Frame)) {
+        let Ok((mut map, start)) = prepare(input) else {
+            return;
+        };
+        let path = fast::patrol_path(&map, start);
+        let every = every.unwrap_or(path.len().div_ceil(PATROL_FRAMES).max(1));
+        let mut image = map.new_from(|cell| match cell {
+            Cell::Empty => FLOOR,
+            Cell::Obstruction => OBSTRUCTION,
+        });
+        let mut patrolled = BitGrid::new(map.lines, map.columns, 4);
+        for (step, &position) in path.iter().enumerate() {
+            if image[position] == FLOOR {
+                image[position] = VISITED;
+                if position != start {
+                    map[position] = Cell::Obstruction;
+                    let is_loop;
+                    (patrolled, is_loop) = fast::patrol(&map, start, patrolled);
+                    map[position] = Cell::Empty;
+                    if is_loop {
+                        image[position] = LOOP_CAUSING;
+                    }
+                }
+            }
+            if step % every == 0 {
+                let mut frame = image.clone();
+                frame[position] = GUARD;
+                sink(Frame::Image(frame));
+            }
+        }
+        sink(Frame::Image(image));
+    }
+}
+
+pub struct Day06;
+register_day!(6, Day06);
+
+impl Day for Day06 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input).map(|parsed| (parsed, checkpoint::input_hash(input))),
+            |(parsed, _)| Ok(Solution::from(fast::solve_part1(parsed))),
+            |(parsed, input_hash)| Ok(Solution::from(fast::solve_part2(parsed, *input_hash))),
+        )
+    }
+
+    /// Generate a `size` x `size` lab with a few obstructions and the guard.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut cells = (0..size * size)
+            .map(|_| if rng.chance(0.08) { '#' } else { '.' })
+            .collect::<Vec<_>>();
+        let empty = (0..cells.len())
+            .filter(|i| cells[*i] == '.')
+            .collect::<Vec<_>>();
+        cells[*rng.choose(&empty)] = '^';
+        cells
+            .chunks(size)
+            .map(|line| line.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    /// A map of obstructions with a guard.
+    fn looks_like(&self, input: &str) -> bool {
+        matches!(shape::blocks(input).as_slice(), [grid] if shape::is_grid(grid, ".#^"))
+    }
+
+    fn variants(&self) -> &'static [Variant] {
+        VARIANTS
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(&PatrolViz)
+    }
+}
+
+/// Implementation variants, cross-validated in paranoid mode.
+const VARIANTS: &[Variant] = &[
+    ("fast", |input| Day06.solve(input)),
+    ("slow", |input| {
+        let parsed = prepare(&input)?;
+        let sol1 = slow::solve_part1(&parsed);
+        let sol2 = slow::solve_part2(&parsed);
+        Ok(DayResult::new(
+            6,
+            Solution::from(sol1),
+            Solution::from(sol2),
+        ))
+    }),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(6, 1);
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            prepare("..#\n.?.\n"),
+            Err(DayError::Malformed("unexpected '?' on the lab map".to_owned()))
+        );
+        assert_eq!(
+            prepare("..#\n...\n"),
+            Err(DayError::Malformed("no guard on the lab map".to_owned()))
+        );
+    }
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(slow::solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 41);
+        assert_eq!(fast::solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 41);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(slow::solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), 6);
+        assert_eq!(
+            fast::solve_part2(
+                &prepare(EXAMPLE_INPUT).unwrap(),
+                checkpoint::input_hash(EXAMPLE_INPUT)
+            ),
+            6
+        );
+    }
+
+    #[test]
+    fn simulation() {
+        let (map, guard) = prepare(EXAMPLE_INPUT).unwrap();
+        let patrolled = BitGrid::new(map.lines, map.columns, 4);
+        let mut harness = Harness::new(Patrol::new(&map, guard, patrolled));
+        // walk north up to the first obstruction, then turn
+        assert_eq!(
+            harness.run_until(|g| g.direction == Direction::East),
+            Some(6)
+        );
+        assert_eq!(harness.state().position, Point(1, 4));
+        harness.run();
+        assert!(harness.simulation().left);
+        assert!(!harness.simulation().is_loop);
+    }
+
+    #[test]
+    fn visualization() {
+        let (map, guard) = prepare(EXAMPLE_INPUT).unwrap();
+        let path = fast::patrol_path(&map, guard);
+        assert_eq!(path[..3], [guard, Point(5, 4), Point(4, 4)]);
+        assert_eq!(path.last(), Some(&Point(9, 7)));
+        let mut frames = vec![];
+        PatrolViz.frames(EXAMPLE_INPUT, None, &mut |frame| frames.push(frame));
+        assert_eq!(frames.len(), path.len() + 1);
+        let Some(Frame::Image(last)) = frames.last() else {
+            panic!("expected an image");
+        };
+        let count = |color| last.iter().filter(|&&pixel| pixel == color).count();
+        assert_eq!(count(LOOP_CAUSING), 6);
+        assert_eq!(count(VISITED) + count(LOOP_CAUSING), 41);
+    }
+
+    #[test]
+    fn preparation() {
+        let (map, guard) = prepare(EXAMPLE_INPUT).unwrap();
+        assert_eq!(guard, Point(6, 4));
+        assert_eq!(map.get(&Point(0, 0)), Some(&Cell::Empty));
+        assert_eq!(map.get(&Point(3, 2)), Some(&Cell::Obstruction));
+        assert_eq!(map.get(&Point(6, 4)), Some(&Cell::Empty));
+    }
+}
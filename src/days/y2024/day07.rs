@@ -0,0 +1,175 @@
+use crate::Solution;
+use crate::days::{Day, Parsed};
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+
+/// A calibration equation missing its operators.
+pub struct Equation {
+    /// The expected result.
+    pub test_value: u64,
+    /// The numbers to combine, from left to right.
+    pub operands: Vec<u64>,
+}
+
+pub type Equations = Vec<Equation>;
+
+/// Read a number of the equation of a line, its `number` counting from 1 for errors.
+fn number(line: usize, text: &str) -> Result<u64, DayError> {
+    text.trim()
+        .parse()
+        .map_err(|_| DayError::Malformed(format!("line {}: {:?} is not a number", line, text)))
+}
+
+/// Read the calibration equations, one per line.
+pub fn prepare(input: &str) -> Result<Equations, DayError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let (test_value, operands) = line
+                .split_once(':')
+                .ok_or_else(|| DayError::Malformed(format!("line {}: no test value", i + 1)))?;
+            Ok(Equation {
+                test_value: number(i + 1, test_value)?,
+                operands: operands
+                    .split_ascii_whitespace()
+                    .map(|operand| number(i + 1, operand))
+                    .collect::<Result<_, _>>()?,
+            })
+        })
+        .collect()
+}
+
+fn solve_equation_rec(
+    operations: &[&dyn Fn(u64, u64) -> u64],
+    test_value: u64,
+    lhs: u64,
+    operands: &[u64],
+) -> bool {
+    if lhs > test_value {
+        return false;
+    }
+    if let Some((first, rest)) = operands.split_first() {
+        operations
+            .iter()
+            .any(|op| solve_equation_rec(operations, test_value, op(lhs, *first), rest))
+    } else {
+        lhs == test_value
+    }
+}
+
+fn solve_equation(operations: &[&dyn Fn(u64, u64) -> u64], eq: &Equation) -> bool {
+    solve_equation_rec(operations, eq.test_value, 0, eq.operands.as_slice())
+}
+
+fn add(x: u64, y: u64) -> u64 {
+    x + y
+}
+fn mul(x: u64, y: u64) -> u64 {
+    x * y
+}
+
+fn solve_part1(eqs: &Equations) -> u64 {
+    let operations: &[&dyn Fn(u64, u64) -> u64] = &[&add, &mul];
+    eqs.iter()
+        .filter(|eq| solve_equation(operations, eq))
+        .map(|eq| eq.test_value)
+        .sum()
+}
+
+fn con(x: u64, y: u64) -> u64 {
+    format!("{}{}", x, y).parse().unwrap()
+}
+
+fn solve_part2(eqs: &Equations) -> u64 {
+    let operations: &[&dyn Fn(u64, u64) -> u64] = &[&add, &mul, &con];
+    eqs.iter()
+        .filter(|eq| solve_equation(operations, eq))
+        .map(|eq| eq.test_value)
+        .sum()
+}
+
+pub struct Day07;
+register_day!(7, Day07);
+
+impl Day for Day07 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |eqs| Ok(Solution::from(solve_part1(eqs))),
+            |eqs| Ok(Solution::from(solve_part2(eqs))),
+        )
+    }
+
+    /// Generate `size` equations, about half of them solvable.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            let operands = (0..rng.between(2, 8))
+                .map(|_| rng.between(1, 99) as u64)
+                .collect::<Vec<_>>();
+            let mut test_value = operands[0];
+            for operand in &operands[1..] {
+                test_value = match rng.below(3) {
+                    0 => add(test_value, *operand),
+                    1 => mul(test_value, *operand),
+                    _ => con(test_value, *operand),
+                };
+            }
+            if rng.chance(0.5) {
+                test_value += rng.between(1, 9) as u64;
+            }
+            let operands = operands.iter().map(|o| o.to_string()).collect::<Vec<_>>();
+            input.push_str(&format!("{test_value}: {}\n", operands.join(" ")));
+        }
+        input
+    }
+
+    /// Calibration equations, a test value followed by numbers.
+    fn looks_like(&self, input: &str) -> bool {
+        shape::all_lines(input, |line| {
+            line.split_once(": ").is_some_and(|(value, numbers)| {
+                shape::matches(value, "#") && shape::is_int_list(numbers, " ")
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(7, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 3749);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), 11387);
+    }
+
+    #[test]
+    fn preparation() {
+        let eqs = prepare(EXAMPLE_INPUT).unwrap();
+        assert_eq!(eqs[0].test_value, 190);
+        assert_eq!(eqs[0].operands, vec![10, 19]);
+        assert_eq!(eqs[8].test_value, 292);
+        assert_eq!(eqs[8].operands, vec![11, 6, 16, 20]);
+        assert_eq!(
+            prepare("3: 1 2\n10 19\n").err(),
+            Some(DayError::Malformed("line 2: no test value".to_owned()))
+        );
+        assert_eq!(
+            prepare("3: 1 x\n").err(),
+            Some(DayError::Malformed(
+                "line 1: \"x\" is not a number".to_owned()
+            ))
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use crate::days::{Day, Parsed};
+use crate::etc::grid::Point;
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+use crate::{Grid, Solution};
+use itertools::Itertools;
+use std::ops::Sub;
+
+/// The positions of the antennas of every frequency.
+pub type Antennas = std::collections::HashMap<char, std::collections::HashSet<Point>>;
+
+/// Read the map and the antennas on it.
+pub fn prepare(input: &str) -> Result<(Grid<char>, Antennas), DayError> {
+    let grid = Grid::try_new(input)?;
+    let mut antennas: Antennas = Default::default();
+    for (pos, &cell) in grid.iter_with_position().filter(|(_, cell)| **cell != '.') {
+        antennas.entry(cell).or_default().insert(pos);
+    }
+    Ok((grid, antennas))
+}
+
+fn solve_part1((grid, antennas): &(Grid<char>, Antennas)) -> usize {
+    let mut antinodes: std::collections::HashSet<Point> = Default::default();
+    for (_, positions) in antennas {
+        for [a1, a2] in positions.iter().array_combinations() {
+            if let Some(h1) = grid.step(a1, &(a1.sub(*a2))) {
+                antinodes.insert(h1);
+            }
+            if let Some(h2) = grid.step(a2, &(a2.sub(*a1))) {
+                antinodes.insert(h2);
+            }
+        }
+    }
+    antinodes.len()
+}
+
+fn solve_part2((grid, antennas): &(Grid<char>, Antennas)) -> usize {
+    let mut antinodes: std::collections::HashSet<Point> = Default::default();
+    for (_, positions) in antennas {
+        for [a1, a2] in positions.iter().array_combinations() {
+            // every lattice point in the grid on the line of the antennas
+            let d = a1.sub(*a2).reduced();
+            antinodes.extend(std::iter::successors(Some(*a1), |h| grid.step(h, &d)));
+            let d = d.rotate_180();
+            antinodes.extend(std::iter::successors(Some(*a1), |h| grid.step(h, &d)));
+        }
+    }
+    antinodes.len()
+}
+
+pub struct Day08;
+register_day!(8, Day08);
+
+impl Day for Day08 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |parsed| Ok(Solution::from(solve_part1(parsed))),
+            |parsed| Ok(Solution::from(solve_part2(parsed))),
+        )
+    }
+
+    /// Generate a `size` x `size` map with a few antennas of various frequencies.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let frequencies = ('0'..='9')
+            .chain('a'..='z')
+            .chain('A'..='Z')
+            .collect::<Vec<_>>();
+        (0..size)
+            .map(|_| {
+                let mut line = (0..size)
+                    .map(|_| {
+                        if rng.chance(0.05) {
+                            *rng.choose(&frequencies)
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>();
+                line.push('\n');
+                line
+            })
+            .collect()
+    }
+
+    /// A map of antennas.
+    fn looks_like(&self, input: &str) -> bool {
+        match shape::blocks(input).as_slice() {
+            [grid] => grid
+                .iter()
+                .all(|line| line.chars().all(|c| c == '.' || c.is_ascii_alphanumeric())),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(8, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 14);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), 34);
+    }
+}
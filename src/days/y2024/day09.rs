@@ -0,0 +1,392 @@
+use crate::days::{Day, Parsed};
+use crate::etc::render::{self, Color, Style};
+use crate::etc::rng::Rng;
+use crate::etc::solution::DayError;
+use crate::etc::viz::{Frame, Visualize};
+use crate::{Grid, Point, Solution};
+
+/// A block of the disk.
+#[derive(Copy, Clone, PartialEq, PartialOrd)]
+pub enum Block {
+    Free,
+    /// A block of the file with this ID.
+    File(u64),
+}
+
+/// The blocks of the disk, in order.
+#[derive(Clone, Default)]
+pub struct Disk(pub Vec<Block>);
+
+impl std::fmt::Debug for Disk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().for_each(|block| block.fmt(f).unwrap());
+        Ok(())
+    }
+}
+
+impl std::ops::Deref for Disk {
+    type Target = Vec<Block>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for Disk {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Glyphs of the files, by file ID, starting over after the last one.
+const FILE_GLYPHS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Colours of the files, the files with the same glyph having different colours.
+const FILE_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Yellow,
+    Color::Green,
+    Color::Magenta,
+    Color::Red,
+    Color::Blue,
+];
+
+impl Block {
+    /// A dot for free blocks, the digits then the letters for files.
+    fn glyph(&self) -> char {
+        match self {
+            Self::Free => '.',
+            Self::File(file_id) => FILE_GLYPHS[*file_id as usize % FILE_GLYPHS.len()] as char,
+        }
+    }
+
+    fn style(&self) -> Style {
+        match self {
+            Self::Free => Style::plain('.'),
+            Self::File(file_id) => {
+                let round = *file_id as usize / FILE_GLYPHS.len();
+                Style::plain(self.glyph()).fg(FILE_COLORS[round % FILE_COLORS.len()])
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Block {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.glyph())
+    }
+}
+
+impl Disk {
+    fn checksum(&self) -> u64 {
+        self.iter().enumerate().fold(0, |h, (pos, block)| {
+            h + match block {
+                Block::Free => 0,
+                Block::File(file_id) => (pos as u64) * *file_id,
+            }
+        })
+    }
+}
+
+/// Expand the disk map into its blocks.
+pub fn prepare(input: &str) -> Result<Disk, DayError> {
+    let mut disk = Disk::default();
+    let mut file_id = 0;
+    let mut is_free = false;
+    for c in input.trim_ascii_end().chars() {
+        let num = c
+            .to_digit(10)
+            .ok_or_else(|| DayError::Malformed(format!("{:?} is not a block count", c)))?;
+        let block = if is_free {
+            Block::Free
+        } else {
+            Block::File(file_id)
+        };
+        for _ in 0..num {
+            disk.push(block);
+        }
+        if !is_free {
+            file_id += 1;
+        }
+        is_free = !is_free;
+    }
+    if !disk.iter().any(|block| matches!(block, Block::File(_))) {
+        return Err(DayError::Malformed("the disk holds no file".to_owned()));
+    }
+    Ok(disk)
+}
+
+/// Move file blocks one by one to the leftmost free block, calling `on_move` after every move.
+fn defragment<F: FnMut(&Disk)>(disk: &mut Disk, mut on_move: F) {
+    let mut left = 0;
+    let mut right = disk.len() - 1;
+    while left < right {
+        if matches!(disk[left], Block::File(_)) {
+            left += 1;
+        } else if matches!(disk[right], Block::Free) {
+            right -= 1;
+        } else {
+            disk[left] = disk[right];
+            disk[right] = Block::Free;
+            on_move(disk);
+        }
+    }
+}
+
+fn solve_part1(disk: &Disk) -> u64 {
+    let mut disk = disk.clone();
+    defragment(&mut disk, |_| {});
+    disk.checksum()
+}
+
+/// Find position of next free block, the length of the disk if there is none.
+fn find_next_free(disk: &Disk, from: usize) -> usize {
+    (from..disk.len())
+        .find(|pos| disk[*pos] == Block::Free)
+        .unwrap_or(disk.len())
+}
+
+/// Find position of next free span of length at least `min_len`.
+fn find_next_free_span(disk: &Disk, start: usize, end: usize, min_len: usize) -> Option<usize> {
+    let mut from = start;
+    loop {
+        from = find_next_free(disk, from);
+        if from + min_len - 1 >= end {
+            return None;
+        }
+        if let Some(non_free_pos) = (from..(from + min_len)).find(|pos| disk[*pos] != Block::Free) {
+            from = non_free_pos;
+        } else {
+            return Some(from);
+        }
+    }
+}
+
+/// Move file blocks to free span
+fn move_file(disk: &mut Disk, free_start: usize, file_start: usize) {
+    let mut free = free_start;
+    let mut file = file_start;
+    if let Block::File(file_id) = disk[file] {
+        while file < disk.len() && disk[file] == Block::File(file_id) {
+            if disk[free] == Block::Free {
+                disk[free] = disk[file];
+                disk[file] = Block::Free;
+                free += 1;
+                file += 1;
+            } else {
+                panic!("not enough free blocks")
+            }
+        }
+    } else {
+        panic!("no file at start position")
+    }
+}
+
+/// Move whole files to the leftmost free span large enough, in decreasing file ID order, calling
+/// `on_move` after every move.
+fn compact<F: FnMut(&Disk)>(disk: &mut Disk, mut on_move: F) {
+    let mut leftmost_free = find_next_free(disk, 0);
+    let mut right = disk.len() - 1;
+    let mut next_file_id = *disk
+        .iter()
+        .filter_map(|block| match block {
+            Block::Free => None,
+            Block::File(file_id) => Some(file_id),
+        })
+        .max()
+        .unwrap();
+    while leftmost_free < right {
+        if disk[right] == Block::File(next_file_id) {
+            let mut file_len = 1;
+            while right > 0 && disk[right - 1] == Block::File(next_file_id) {
+                right -= 1;
+                file_len += 1;
+            }
+            let file_start = right;
+            if let Some(free_span) = find_next_free_span(disk, leftmost_free, file_start, file_len)
+            {
+                move_file(disk, free_span, file_start);
+                on_move(disk);
+                leftmost_free = find_next_free(disk, leftmost_free);
+            }
+            if next_file_id == 0 {
+                break;
+            } else {
+                next_file_id -= 1;
+            }
+        }
+        right -= 1;
+    }
+}
+
+fn solve_part2(disk: &Disk) -> u64 {
+    let mut disk = disk.clone();
+    compact(&mut disk, |_| {});
+    disk.checksum()
+}
+
+/// Number of blocks per line of the visualization.
+const LINE_BLOCKS: usize = 100;
+
+/// Draw a disk with a title, wrapping long disks.
+fn draw(title: &str, disk: &Disk) -> String {
+    let lines = disk.len().div_ceil(LINE_BLOCKS);
+    let columns = disk.len().min(LINE_BLOCKS);
+    let blocks = Grid::from_fn(lines, columns, |Point(line, column)| {
+        disk.get(line as usize * LINE_BLOCKS + column as usize)
+            .copied()
+    });
+    let drawing = render::render(&blocks, |_, block| {
+        block.map_or(Style::plain(' '), |block| block.style())
+    });
+    format!("{}\n{}", title, drawing)
+}
+
+/// The disk defragmented for part 1 then compacted for part 2, one frame per move for small disks.
+struct DiskViz;
+
+impl Visualize for DiskViz {
+    fn frames(&self, input: &str, every: Option<usize>, sink: &mut dyn FnMut(Frame)) {
+        let Ok(disk) = prepare(input) else {
+            return;
+        };
+        // about 200 frames for the moves of part 1 on large disks
+        let every = every.unwrap_or((disk.len() / 400).max(1));
+        for part in [1, 2] {
+            let mut moved = disk.clone();
+            sink(Frame::Text(draw(&format!("Part {part}"), &moved)));
+            let mut moves = 0;
+            let mut on_move = |disk: &Disk| {
+                moves += 1;
+                if moves % every == 0 {
+                    sink(Frame::Text(draw(
+                        &format!("Part {part}, move {moves}"),
+                        disk,
+                    )));
+                }
+            };
+            if part == 1 {
+                defragment(&mut moved, &mut on_move);
+            } else {
+                compact(&mut moved, &mut on_move);
+            }
+            if moves % every != 0 {
+                sink(Frame::Text(draw(
+                    &format!("Part {part}, move {moves}"),
+                    &moved,
+                )));
+            }
+        }
+    }
+}
+
+pub struct Day09;
+register_day!(9, Day09);
+
+impl Day for Day09 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |disk| Ok(Solution::from(solve_part1(disk))),
+            |disk| Ok(Solution::from(solve_part2(disk))),
+        )
+    }
+
+    /// Generate a disk map of `size` files.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+        for i in 0..size {
+            if i > 0 {
+                input.push_str(&rng.between(0, 9).to_string());
+            }
+            input.push_str(&rng.between(1, 9).to_string());
+        }
+        input.push('\n');
+        input
+    }
+
+    /// A single line of digits.
+    fn looks_like(&self, input: &str) -> bool {
+        let input = input.trim();
+        !input.is_empty() && input.bytes().all(|b| b.is_ascii_digit())
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(&DiskViz)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(9, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 1928);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), 2858);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            prepare("12a4\n").err(),
+            Some(DayError::Malformed("'a' is not a block count".to_owned()))
+        );
+        assert_eq!(
+            prepare("05\n").unwrap_err().to_string(),
+            "malformed input: the disk holds no file"
+        );
+        // no free block to move files to
+        let disk = prepare("3\n").unwrap();
+        assert_eq!((solve_part1(&disk), solve_part2(&disk)), (0, 0));
+        let disk = prepare("102\n").unwrap();
+        assert_eq!((solve_part1(&disk), solve_part2(&disk)), (3, 3));
+    }
+
+    #[test]
+    fn moves() {
+        let disk = prepare(EXAMPLE_INPUT).unwrap();
+        let mut steps = vec![format!("{:?}", disk)];
+        defragment(&mut disk.clone(), |disk| steps.push(format!("{:?}", disk)));
+        assert_eq!(steps.len(), 13);
+        assert_eq!(steps[0], "00...111...2...333.44.5555.6666.777.888899");
+        assert_eq!(steps[1], "009..111...2...333.44.5555.6666.777.88889.");
+        assert_eq!(steps[12], "0099811188827773336446555566..............");
+
+        let mut steps = vec![];
+        compact(&mut disk.clone(), |disk| steps.push(format!("{:?}", disk)));
+        assert_eq!(
+            steps,
+            vec![
+                "0099.111...2...333.44.5555.6666.777.8888..",
+                "0099.1117772...333.44.5555.6666.....8888..",
+                "0099.111777244.333....5555.6666.....8888..",
+                "00992111777.44.333....5555.6666.....8888..",
+            ]
+        );
+
+        // large file IDs
+        assert_eq!(format!("{:?}", Block::File(10)), "a");
+        assert_eq!(format!("{:?}", Block::File(61)), "Z");
+        assert_eq!(Block::File(62).glyph(), '0');
+        assert_ne!(Block::File(62).style(), Block::File(0).style());
+    }
+
+    #[test]
+    fn visualization() {
+        let mut frames = vec![];
+        DiskViz.frames(EXAMPLE_INPUT, None, &mut |frame| frames.push(frame));
+        // both initial disks, then every move
+        assert_eq!(frames.len(), 2 + 12 + 4);
+        let Frame::Text(last) = &frames[17] else {
+            panic!("expected text");
+        };
+        assert!(last.starts_with("Part 2, move 4\n\x1b[36m0\x1b[0m"));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::days::{Day, Parsed};
+use crate::etc::grid::Topology;
+use crate::etc::pathfinding::bfs;
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+use crate::{Grid, Point, Solution};
+
+/// The height of every position of the topographic map.
+pub type Map = Grid<u32>;
+
+/// Read the topographic map.
+pub fn prepare(input: &str) -> Result<Map, DayError> {
+    let map = Grid::try_new(input)?.new_from(|c| c.to_digit(10).unwrap());
+    Ok(map)
+}
+
+fn solve_part1(map: &Map) -> usize {
+    // breadth-first search from each zero-cell to every reachable nine-cell.
+    map.iter_with_position()
+        .filter(|(_, level)| **level == 0)
+        .map(|(root, _)| {
+            let search = bfs(
+                root,
+                |pos| {
+                    let level = map[*pos];
+                    map.neighbours(pos, Topology::Orthogonal)
+                        .filter(move |(_, lvl)| **lvl == level + 1)
+                        .map(|(neigh, _)| neigh)
+                },
+                |_| false,
+            );
+            search
+                .distances
+                .keys()
+                .filter(|pos| map[**pos] == 9)
+                .count()
+        })
+        .sum()
+}
+
+type Ratings = std::collections::HashMap<Point, usize>;
+
+fn dfs(map: &Map, ratings: &mut Ratings, pos: &Point, level: u32) -> usize {
+    if let Some(rating) = ratings.get(pos) {
+        *rating
+    } else if *map.unchecked_get(pos) == 9 {
+        ratings.insert(*pos, 1);
+        1
+    } else {
+        ratings.insert(*pos, 0);
+        let rating = map
+            .neighbours(pos, Topology::Orthogonal)
+            .filter(|(_, lvl)| **lvl == level + 1)
+            .map(|(neigh, &lvl)| dfs(map, ratings, &neigh, lvl))
+            .sum();
+        ratings.insert(*pos, rating);
+        rating
+    }
+}
+
+fn solve_part2(map: &Map) -> usize {
+    // depth-first search from each cell to every reachable nine-cell
+    let mut ratings = std::collections::HashMap::new();
+    let mut total = 0;
+    map.for_each_with_position(|root, &level| {
+        let rating = dfs(map, &mut ratings, &root, level);
+        if level == 0 {
+            total += rating;
+        }
+    });
+    total
+}
+
+pub struct Day10;
+register_day!(10, Day10);
+
+impl Day for Day10 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |map| Ok(Solution::from(solve_part1(map))),
+            |map| Ok(Solution::from(solve_part2(map))),
+        )
+    }
+
+    /// Generate a `size` x `size` topographic map with smooth slopes.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut heights = vec![0i64; size * size];
+        for l in 0..size {
+            for c in 0..size {
+                let base = match (l, c) {
+                    (0, 0) => rng.between(0, 9),
+                    (0, _) => heights[c - 1],
+                    (_, 0) => heights[(l - 1) * size],
+                    _ => *rng.choose(&[heights[l * size + c - 1], heights[(l - 1) * size + c]]),
+                };
+                heights[l * size + c] = (base + rng.between(-1, 1)).clamp(0, 9);
+            }
+        }
+        heights
+            .chunks(size)
+            .map(|line| line.iter().map(|h| h.to_string()).collect::<String>() + "\n")
+            .collect()
+    }
+
+    /// A topographic map of heights.
+    fn looks_like(&self, input: &str) -> bool {
+        matches!(shape::blocks(input).as_slice(), [grid] if shape::is_grid(grid, "0123456789."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(10, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 36);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), 81);
+    }
+}
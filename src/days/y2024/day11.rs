@@ -0,0 +1,145 @@
+use crate::Solution;
+use crate::days::{Day, Parsed};
+use crate::etc::counter::Counter;
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+
+/// The numbers engraved on the stones, in order.
+pub type Stones = Vec<u64>;
+
+/// Read the stones.
+pub fn prepare(input: &str) -> Result<Stones, DayError> {
+    input
+        .split_ascii_whitespace()
+        .map(|s| {
+            s.parse()
+                .map_err(|_| DayError::Malformed(format!("{:?} is not a stone number", s)))
+        })
+        .collect()
+}
+
+/// stone evolution after a single blink
+fn blink_once(stone: u64) -> (u64, Option<u64>) {
+    if stone == 0 {
+        (1, None)
+    } else {
+        let digits = stone.ilog10() + 1;
+        if digits % 2 == 0 {
+            let mut left = stone;
+            let mut right = 0;
+            for dec in 0..(digits / 2) {
+                right = right + 10u64.pow(dec) * (left % 10);
+                left = left / 10;
+            }
+            (left, Some(right))
+        } else {
+            (2024 * stone, None)
+        }
+    }
+}
+
+/// stones evolution after a single blink
+fn blink_all(stones: &Stones) -> Stones {
+    let mut result = Stones::with_capacity(stones.len() * 2);
+    for stone in stones {
+        let (left, maybe_right) = blink_once(*stone);
+        result.push(left);
+        if let Some(right) = maybe_right {
+            result.push(right);
+        }
+    }
+    result
+}
+
+fn solve_part1(stones: &Stones, blinks_times: usize) -> usize {
+    stones
+        .iter()
+        .map(|seed| {
+            let mut v = vec![*seed];
+            for _ in 0..blinks_times {
+                let vprime = blink_all(&v);
+                v = vprime;
+            }
+            v.len()
+        })
+        .sum()
+}
+
+/// Count the number of stones after the given number of blinks, blinking once for all the
+/// stones engraved with the same number.
+fn solve_part2(stones: &Stones, blinks_times: usize) -> usize {
+    let mut counter: Counter<u64> = stones.iter().copied().collect();
+    for _ in 0..blinks_times {
+        let mut next = Counter::new();
+        for (&stone, count) in counter.iter() {
+            let (left, maybe_right) = blink_once(stone);
+            next.add_n(left, count);
+            if let Some(right) = maybe_right {
+                next.add_n(right, count);
+            }
+        }
+        counter = next;
+    }
+    counter.total() as usize
+}
+
+pub struct Day11;
+register_day!(11, Day11);
+
+impl Day for Day11 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |stones| Ok(Solution::from(solve_part1(stones, 25))),
+            |stones| Ok(Solution::from(solve_part2(stones, 75))),
+        )
+    }
+
+    /// Generate `size` stones.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let stones = (0..size)
+            .map(|_| rng.between(0, 999999).to_string())
+            .collect::<Vec<_>>();
+        stones.join(" ") + "\n"
+    }
+
+    /// A single line of numbers.
+    fn looks_like(&self, input: &str) -> bool {
+        shape::is_int_list(input.trim(), " ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(11, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap(), 6), 22);
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap(), 25), 55312);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap(), 1), 3);
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap(), 2), 4);
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap(), 3), 5);
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap(), 4), 9);
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap(), 6), 22);
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap(), 25), 55312);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            prepare("125 -17"),
+            Err(DayError::Malformed(
+                "\"-17\" is not a stone number".to_owned()
+            ))
+        );
+    }
+}
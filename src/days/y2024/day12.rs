@@ -0,0 +1,191 @@
+use crate::days::{Day, Parsed};
+use crate::etc::grid::{RegionId, Topology};
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+use crate::{Grid, Point, Solution};
+use std::ops::Add;
+
+/// The plant growing on every plot of the garden.
+pub type Farm = Grid<char>;
+
+/// Read the garden map.
+pub fn prepare(input: &str) -> Result<Farm, DayError> {
+    Ok(Grid::try_new(input)?)
+}
+
+/// The region of every plot, touching plots growing the same type of plant being in the same
+/// region, with the number of regions.
+fn regions(farm: &Farm) -> (Grid<RegionId>, usize) {
+    let regions = farm.label_regions(|a, b| a == b);
+    let count = regions.iter().max().map_or(0, |last| last + 1);
+    (regions, count)
+}
+
+fn solve_part1(farm: &Farm) -> u64 {
+    let (regions, count) = regions(farm);
+    let mut areas = vec![0u64; count];
+    let mut perimeters = vec![0u64; count];
+    regions.for_each_with_position(|plot, &region| {
+        areas[region] += 1;
+        // at most four fences, none with the neighbours in the same region
+        let same = regions
+            .neighbours(&plot, Topology::Orthogonal)
+            .filter(|(_, neighbour)| **neighbour == region)
+            .count();
+        perimeters[region] += 4 - same as u64;
+    });
+    areas
+        .iter()
+        .zip(perimeters)
+        .map(|(area, perimeter)| area * perimeter)
+        .sum()
+}
+
+fn solve_part2(farm: &Farm) -> u64 {
+    let (regions, count) = regions(farm);
+    let mut areas = vec![0u64; count];
+    let mut sides = vec![0u64; count];
+    regions.for_each_with_position(|pos, &region| {
+        let not_same_region = |delta| regions.get(&pos.add(delta)) != Some(&region);
+        let same_region = |delta| regions.get(&pos.add(delta)) == Some(&region);
+
+        let mut corns: u64 = 0;
+        // detect the following 8 corner patterns:
+        //
+        // .x  x.  .?  ?.
+        // ?.  .?  x.  .x
+        //
+        // xX  Xx  x.  .x
+        // .x  x.  Xx  xX
+        //
+        // each pattern count as 1 corner for the region X, where `.` is not part of region
+        // X and `?` is of any region.
+        //
+        corns += (not_same_region(Point::WEST) && not_same_region(Point::SOUTH)) as u64;
+        corns += (not_same_region(Point::EAST) && not_same_region(Point::SOUTH)) as u64;
+        corns += (not_same_region(Point::NORTH) && not_same_region(Point::EAST)) as u64;
+        corns += (not_same_region(Point::NORTH) && not_same_region(Point::WEST)) as u64;
+
+        corns += (same_region(Point::WEST)
+            && same_region(Point::SOUTH)
+            && not_same_region(Point::SOUTH_WEST)) as u64;
+        corns += (same_region(Point::EAST)
+            && same_region(Point::SOUTH)
+            && not_same_region(Point::SOUTH_EAST)) as u64;
+        corns += (same_region(Point::NORTH)
+            && same_region(Point::EAST)
+            && not_same_region(Point::NORTH_EAST)) as u64;
+        corns += (same_region(Point::NORTH)
+            && same_region(Point::WEST)
+            && not_same_region(Point::NORTH_WEST)) as u64;
+        areas[region] += 1;
+        // as many sides as corners
+        sides[region] += corns;
+    });
+    areas
+        .iter()
+        .zip(sides)
+        .map(|(area, sides)| area * sides)
+        .sum()
+}
+
+pub struct Day12;
+register_day!(12, Day12);
+
+impl Day for Day12 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |farm| Ok(Solution::from(solve_part1(farm))),
+            |farm| Ok(Solution::from(solve_part2(farm))),
+        )
+    }
+
+    /// Generate a `size` x `size` garden with irregular regions.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let plants = ['A', 'B', 'C', 'D', 'E', 'F'];
+        let mut farm = vec!['A'; size * size];
+        for l in 0..size {
+            for c in 0..size {
+                farm[l * size + c] = if l > 0 && rng.chance(0.35) {
+                    farm[(l - 1) * size + c]
+                } else if c > 0 && rng.chance(0.5) {
+                    farm[l * size + c - 1]
+                } else {
+                    *rng.choose(&plants)
+                };
+            }
+        }
+        farm.chunks(size)
+            .map(|line| line.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    /// A map of garden plots.
+    fn looks_like(&self, input: &str) -> bool {
+        match shape::blocks(input).as_slice() {
+            [grid] => grid
+                .iter()
+                .all(|line| line.bytes().all(|b| b.is_ascii_uppercase())),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(12, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 1930);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            solve_part2(
+                &prepare(
+                    "AAAA
+        BBCD
+        BBCC
+        EEEC"
+                )
+                .unwrap()
+            ),
+            80
+        );
+        assert_eq!(
+            solve_part2(
+                &prepare(
+                    "EEEEE
+        EXXXX
+        EEEEE
+        EXXXX
+        EEEEE"
+                )
+                .unwrap()
+            ),
+            236
+        );
+        assert_eq!(
+            solve_part2(
+                &prepare(
+                    "AAAAAA
+        AAABBA
+        AAABBA
+        ABBAAA
+        ABBAAA
+        AAAAAA"
+                )
+                .unwrap()
+            ),
+            368
+        );
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), 1206);
+    }
+}
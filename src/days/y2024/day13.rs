@@ -0,0 +1,324 @@
+use crate::days::{Day, Parsed};
+use crate::etc::parse::unsigned_ints;
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::{DayError, DayResult};
+use crate::{Solution, Variant};
+use std::fmt::Write;
+
+/// A claw machine: the moves of its two buttons and the position of its prize.
+#[derive(Copy, Clone, Debug)]
+pub struct Machine {
+    pub a_x: i64,
+    pub a_y: i64,
+    pub b_x: i64,
+    pub b_y: i64,
+    pub prize_x: i64,
+    pub prize_y: i64,
+}
+
+/// Read the claw machines.
+pub fn prepare(input: &str) -> Result<Vec<Machine>, DayError> {
+    let ints = unsigned_ints(input).collect::<Result<Vec<i64>, _>>()?;
+    Ok(ints
+        .chunks_exact(6)
+        .map(|machine| Machine {
+            a_x: machine[0],
+            a_y: machine[1],
+            b_x: machine[2],
+            b_y: machine[3],
+            prize_x: machine[4],
+            prize_y: machine[5],
+        })
+        .collect())
+}
+
+/// Fewest tokens to win the prize with a brute-force test.
+/// From the puzzle, each button is pressed at most 100 times.
+fn brute_force(
+    &Machine {
+        a_x,
+        a_y,
+        b_x,
+        b_y,
+        prize_x,
+        prize_y,
+    }: &Machine,
+) -> Option<i64> {
+    let mut best_tokens = None;
+    for a in 0..=100 {
+        // try to skip as early as possible
+        let a_a_x = a * a_x;
+        let a_a_y = a * a_y;
+        if a_a_x > prize_x || a_a_y > prize_y {
+            continue;
+        }
+
+        for b in 0..=100 {
+            // try to leave b loop as early as possible
+            if best_tokens.is_some_and(|best| 3 * a + b > best) {
+                break;
+            }
+            if best_tokens.is_none_or(|best| (3 * a + b) < best)
+                && (a_a_x + b * b_x == prize_x)
+                && (a_a_y + b * b_y == prize_y)
+            {
+                best_tokens = Some(3 * a + b);
+            }
+        }
+    }
+    best_tokens
+}
+
+/// Fewest tokens to win the prize, solving the equation system:
+///
+/// ```text
+/// A*a + B*b = X
+/// A*c + B*d = Y
+/// ```
+/// where `a = a_x, b = b_y, c = a_y, d = b_y, X = prize_x, Y = prize_y`.
+/// and all variables are integers.
+///
+/// ```text
+/// A = (dX - bY)/(ad - cb)
+/// B = (X - aA)/b = (Y - Ac)/d
+/// ```
+/// We don't need to minimise for `3a+b` since these equations have either no solution
+/// or a single solution for `a` and `b`, unless the buttons move the claw along the same line:
+/// such machines get `None`.
+///
+/// Returns the number of presses of buttons A and B.
+fn algebraic(
+    &Machine {
+        a_x,
+        a_y,
+        b_x,
+        b_y,
+        prize_x,
+        prize_y,
+    }: &Machine,
+) -> Option<(i64, i64)> {
+    let denominator = a_x * b_y - b_x * a_y;
+    if denominator == 0 {
+        return None;
+    }
+    let a_numerator = b_y * prize_x - b_x * prize_y;
+    if a_numerator.rem_euclid(denominator) == 0 {
+        let a = a_numerator.div_euclid(denominator);
+        if b_y * (prize_x - a * a_x) == b_x * (prize_y - a * a_y) {
+            // along Y when button B does not move the claw along X
+            let (b_numerator, b_step) = if b_x != 0 {
+                (prize_x - a * a_x, b_x)
+            } else {
+                (prize_y - a * a_y, b_y)
+            };
+            if b_numerator.rem_euclid(b_step) == 0 {
+                let b = b_numerator.div_euclid(b_step);
+                return (a >= 0 && b >= 0).then_some((a, b));
+            }
+        }
+    }
+    None
+}
+
+fn solve_part1(machines: &[Machine]) -> i64 {
+    machines.iter().filter_map(brute_force).sum()
+}
+
+/// Offset of the prize positions in part 2, fixing the unit conversion error.
+const PRIZE_OFFSET: i64 = 10000000000000;
+
+fn solve_part2(machines: &[Machine]) -> i64 {
+    let mut machines = machines.to_vec();
+    for machine in machines.iter_mut() {
+        machine.prize_x += PRIZE_OFFSET;
+        machine.prize_y += PRIZE_OFFSET;
+    }
+    machines
+        .iter()
+        .filter_map(algebraic)
+        .map(|(a, b)| 3 * a + b)
+        .sum()
+}
+
+/// Part 1 with the algebraic solution, limited to 100 presses of each button.
+fn solve_part1_algebraic(machines: &[Machine]) -> i64 {
+    machines
+        .iter()
+        .filter_map(algebraic)
+        .filter(|(a, b)| *a <= 100 && *b <= 100)
+        .map(|(a, b)| 3 * a + b)
+        .sum()
+}
+
+pub struct Day13;
+register_day!(13, Day13);
+
+impl Day for Day13 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |machines| Ok(Solution::from(solve_part1(machines))),
+            |machines| Ok(Solution::from(solve_part2(machines))),
+        )
+    }
+
+    /// Generate `size` claw machines, about two thirds of them winnable.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut machines = vec![];
+        for _ in 0..size {
+            let (a_x, a_y, b_x, b_y) = loop {
+                let buttons = (
+                    rng.between(10, 99),
+                    rng.between(10, 99),
+                    rng.between(10, 99),
+                    rng.between(10, 99),
+                );
+                // avoid colinear buttons
+                if buttons.0 * buttons.3 != buttons.1 * buttons.2 {
+                    break buttons;
+                }
+            };
+            let a = rng.between(0, 100);
+            let b = rng.between(0, 100);
+            let offset = if rng.chance(0.66) {
+                0
+            } else {
+                rng.between(1, 9)
+            };
+            let prize_x = a * a_x + b * b_x + offset;
+            let prize_y = a * a_y + b * b_y;
+            machines.push(format!(
+                "Button A: X+{a_x}, Y+{a_y}\nButton B: X+{b_x}, Y+{b_y}\nPrize: X={prize_x}, Y={prize_y}\n"
+            ));
+        }
+        machines.join("\n")
+    }
+
+    /// Blocks of two buttons and a prize.
+    fn looks_like(&self, input: &str) -> bool {
+        let blocks = shape::blocks(input);
+        !blocks.is_empty()
+            && blocks.iter().all(|block| {
+                matches!(block.as_slice(), [a, b, prize]
+                    if shape::matches(a, "Button A: X+#, Y+#")
+                        && shape::matches(b, "Button B: X+#, Y+#")
+                        && shape::matches(prize, "Prize: X=#, Y=#"))
+            })
+    }
+
+    fn variants(&self) -> &'static [Variant] {
+        VARIANTS
+    }
+
+    fn explainer(&self) -> Option<fn(&str) -> String> {
+        Some(explain)
+    }
+}
+
+/// Implementation variants, cross-validated in paranoid mode.
+const VARIANTS: &[Variant] = &[
+    ("algebraic", |input| {
+        let machines = prepare(&input)?;
+        let sol1 = solve_part1_algebraic(&machines);
+        let sol2 = solve_part2(&machines);
+        Ok(DayResult::new(13, Solution::from(sol1), Solution::from(sol2)))
+    }),
+    // too slow for part 2
+    ("brute-force", |input| {
+        Ok(DayResult::new(
+            13,
+            Solution::from(solve_part1(&prepare(&input)?)),
+            Solution::Todo(),
+        ))
+    }),
+];
+
+/// The equation system of every machine, with its solution in both parts.
+fn explain(input: &str) -> String {
+    let machines = match prepare(input) {
+        Ok(machines) => machines,
+        Err(err) => return err.to_string(),
+    };
+    let mut out = String::new();
+    for (i, machine) in machines.iter().enumerate() {
+        let Machine {
+            a_x,
+            a_y,
+            b_x,
+            b_y,
+            prize_x,
+            prize_y,
+        } = *machine;
+        writeln!(out, "Machine {}:", i + 1).unwrap();
+        writeln!(out, "   {a_x}*A + {b_x}*B = {prize_x} (+ {PRIZE_OFFSET})").unwrap();
+        writeln!(out, "   {a_y}*A + {b_y}*B = {prize_y} (+ {PRIZE_OFFSET})").unwrap();
+        let far = Machine {
+            prize_x: prize_x + PRIZE_OFFSET,
+            prize_y: prize_y + PRIZE_OFFSET,
+            ..*machine
+        };
+        for (part, machine) in [(1, machine), (2, &far)] {
+            match algebraic(machine) {
+                Some((a, b)) if part == 1 && (a > 100 || b > 100) => {
+                    writeln!(out, "   part {part}: A = {a}, B = {b}, over 100 presses").unwrap()
+                }
+                Some((a, b)) => {
+                    let tokens = 3 * a + b;
+                    writeln!(out, "   part {part}: A = {a}, B = {b}, {tokens} tokens").unwrap()
+                }
+                None => writeln!(out, "   part {part}: no solution in natural numbers").unwrap(),
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(13, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 480);
+    }
+
+    #[test]
+    fn explain_example() {
+        let explanation = explain(EXAMPLE_INPUT);
+        assert!(explanation.starts_with(
+            "Machine 1:\n   94*A + 22*B = 8400 (+ 10000000000000)\n   34*A + 67*B = 5400 (+ 10000000000000)\n   part 1: A = 80, B = 40, 280 tokens\n   part 2: no solution"
+        ));
+        assert_eq!(explanation.matches("Machine").count(), 4);
+    }
+
+    #[test]
+    fn example_part1_algebraic() {
+        assert_eq!(solve_part1_algebraic(&prepare(EXAMPLE_INPUT).unwrap()), 480);
+    }
+
+    #[test]
+    fn degenerate_buttons() {
+        let machine = |a_x, a_y, b_x, b_y, prize_x, prize_y| Machine {
+            a_x,
+            a_y,
+            b_x,
+            b_y,
+            prize_x,
+            prize_y,
+        };
+        // colinear buttons
+        assert_eq!(algebraic(&machine(1, 2, 2, 4, 6, 12)), None);
+        // button B moving along Y only
+        assert_eq!(algebraic(&machine(2, 1, 0, 3, 8, 19)), Some((4, 5)));
+        assert_eq!(algebraic(&machine(2, 1, 0, 3, 8, 18)), None);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), 875318608908);
+    }
+}
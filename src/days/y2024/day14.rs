@@ -0,0 +1,277 @@
+use crate::days::{Day, Parsed};
+use crate::etc::math;
+use crate::etc::parse::signed_ints;
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::simulation::{Harness, Simulation};
+use crate::etc::solution::DayError;
+use crate::etc::viz::{Frame, Visualize};
+use crate::{Grid, Point, Solution};
+
+/// A security robot, positions being `Point(x, y)`.
+#[derive(Clone)]
+pub struct Robot {
+    pub position: Point,
+    /// Tiles moved every second.
+    pub velocity: Point,
+}
+
+pub type Robots = Vec<Robot>;
+
+/// Read the robots, one per line.
+pub fn prepare(input: &str) -> Result<Robots, DayError> {
+    let ints = signed_ints(input).collect::<Result<Vec<i64>, _>>()?;
+    Ok(ints
+        .chunks_exact(4)
+        .map(|robot| Robot {
+            position: Point(robot[0], robot[1]),
+            velocity: Point(robot[2], robot[3]),
+        })
+        .collect())
+}
+
+/// Update robots position as a vector transposition.
+fn transpose_robots(robots: &mut Robots, columns: u64, lines: u64, steps: u64) {
+    let limit = Point(columns as i64, lines as i64);
+    robots.iter_mut().for_each(|robot| {
+        robot.position = (robot.position + robot.velocity * (steps as i64)).rem_euclid(limit)
+    })
+}
+
+/// Compute the safety factor for the given robot positions.
+fn safety_factor(robots: &Robots, columns: u64, lines: u64) -> u64 {
+    let mid_column = (columns / 2) as i64;
+    let mid_line = (lines / 2) as i64;
+    robots
+        .iter()
+        .fold(
+            [0u64, 0u64, 0u64, 0u64],
+            |[mut q1, mut q2, mut q3, mut q4],
+             Robot {
+                 position,
+                 velocity: _,
+             }| {
+                if position.0 < mid_column {
+                    if position.1 < mid_line {
+                        q1 += 1;
+                    } else if position.1 > mid_line {
+                        q2 += 1;
+                    }
+                } else if position.0 > mid_column {
+                    if position.1 < mid_line {
+                        q3 += 1;
+                    } else if position.1 > mid_line {
+                        q4 += 1;
+                    }
+                }
+                [q1, q2, q3, q4]
+            },
+        )
+        .iter()
+        .product()
+}
+
+fn solve_part1(robots: &Robots, columns: u64, lines: u64) -> u64 {
+    let mut robots = robots.clone();
+    transpose_robots(&mut robots, columns, lines, 100);
+    safety_factor(&robots, columns, lines)
+}
+
+fn has_overlap(robots: &Robots) -> bool {
+    let mut positions = std::collections::BTreeSet::<Point>::new();
+    for &Robot {
+        position,
+        velocity: _,
+    } in robots
+    {
+        if !positions.insert(position) {
+            return true;
+        }
+    }
+    return false;
+}
+
+/// Robots moving in the bathroom, one second per step.
+///
+/// Robots positions are periodic, the simulation is done after a full period.
+struct Bathroom {
+    robots: Robots,
+    columns: u64,
+    lines: u64,
+    elapsed: u64,
+}
+
+impl Bathroom {
+    fn new(robots: Robots, columns: u64, lines: u64) -> Self {
+        Bathroom {
+            robots,
+            columns,
+            lines,
+            elapsed: 0,
+        }
+    }
+}
+
+impl Simulation for Bathroom {
+    type State = Robots;
+
+    fn step(&mut self) {
+        if !self.is_done() {
+            transpose_robots(&mut self.robots, self.columns, self.lines, 1);
+            self.elapsed += 1;
+        }
+    }
+
+    fn state(&self) -> &Robots {
+        &self.robots
+    }
+
+    fn is_done(&self) -> bool {
+        self.elapsed >= math::lcm(self.columns as i64, self.lines as i64) as u64
+    }
+}
+
+/// Find the number of steps required to have no robots overlapping.
+fn solve_part2(robots: &Robots) -> Result<u64, DayError> {
+    let mut harness = Harness::new(Bathroom::new(robots.clone(), 101, 103));
+    harness
+        .run_until(|robots| !has_overlap(robots))
+        .map(|steps| steps as u64)
+        .ok_or_else(|| {
+            DayError::Unsupported("the robots always overlap, there is no tree".to_owned())
+        })
+}
+
+/// Picture of the bathroom, robots being lit pixels.
+fn picture(robots: &Robots, columns: u64, lines: u64) -> Grid<[u8; 3]> {
+    let mut picture = Grid::from_fn(lines as usize, columns as usize, |_| [0, 0, 0]);
+    for robot in robots {
+        picture[Point(robot.position.1, robot.position.0)] = [40, 220, 80];
+    }
+    picture
+}
+
+/// The robots up to the tree: only the tree, or one second every given number of seconds, the
+/// tree included.
+struct BathroomViz;
+
+impl Visualize for BathroomViz {
+    fn frames(&self, input: &str, every: Option<usize>, sink: &mut dyn FnMut(Frame)) {
+        let Ok(robots) = prepare(input) else {
+            return;
+        };
+        let Ok(tree) = solve_part2(&robots) else {
+            return;
+        };
+        let tree = tree as usize;
+        let mut harness = Harness::new(Bathroom::new(robots, 101, 103));
+        harness.for_each_frame(1, Some(tree), |step, robots| {
+            if step == tree || every.is_some_and(|every| step.is_multiple_of(every)) {
+                sink(Frame::Image(picture(robots, 101, 103)));
+            }
+        });
+    }
+}
+
+pub struct Day14;
+register_day!(14, Day14);
+
+impl Day for Day14 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |robots| Ok(Solution::from(solve_part1(robots, 101, 103))),
+            |robots| solve_part2(robots).map(Solution::from),
+        )
+    }
+
+    /// Generate `size` robots in the 101 x 103 bathroom (at most 10403 robots).
+    ///
+    /// Robots are placed so that they do not overlap at some random step.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let (columns, lines) = (101, 103);
+        let mut cells = (0..columns * lines).collect::<Vec<i64>>();
+        rng.shuffle(&mut cells);
+        let steps = rng.between(1, columns * lines - 1);
+        cells
+            .iter()
+            .take(size)
+            .map(|cell| {
+                let v = Point(rng.between(-99, 99), rng.between(-99, 99));
+                let target = Point(cell % columns, cell / columns);
+                let limit = Point(columns, lines);
+                let p = (target - v * steps).rem_euclid(limit);
+                format!("p={},{} v={},{}\n", p.0, p.1, v.0, v.1)
+            })
+            .collect()
+    }
+
+    /// Robot positions and velocities.
+    fn looks_like(&self, input: &str) -> bool {
+        shape::all_lines(input, |line| shape::matches(line, "p=#,# v=#,#"))
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(&BathroomViz)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::Day;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(14, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap(), 7, 11), 12);
+    }
+
+    #[test]
+    fn simulation() {
+        let mut harness = Harness::new(Bathroom::new(prepare(EXAMPLE_INPUT).unwrap(), 11, 7));
+        assert!(harness.seek(50));
+        let mut robots = prepare(EXAMPLE_INPUT).unwrap();
+        transpose_robots(&mut robots, 11, 7, 50);
+        assert!(
+            robots
+                .iter()
+                .zip(harness.state())
+                .all(|(a, b)| a.position == b.position)
+        );
+        assert_eq!(harness.run(), 77);
+        assert!(
+            prepare(EXAMPLE_INPUT)
+                .unwrap()
+                .iter()
+                .zip(harness.state())
+                .all(|(a, b)| a.position == b.position)
+        );
+    }
+
+    #[test]
+    fn visualization() {
+        let lit = |frame: &Frame| match frame {
+            Frame::Image(picture) => picture.iter().filter(|&&pixel| pixel != [0, 0, 0]).count(),
+            _ => panic!("expected an image"),
+        };
+        let robots = prepare(EXAMPLE_INPUT).unwrap();
+        assert_eq!(lit(&Frame::Image(picture(&robots, 11, 7))), 11);
+
+        let input = Day14.generate(300, &mut Rng::new(14));
+        let tree = solve_part2(&prepare(&input).unwrap()).unwrap() as usize;
+        let mut frames = vec![];
+        BathroomViz.frames(&input, None, &mut |frame| frames.push(frame));
+        assert_eq!(frames.len(), 1);
+        assert_eq!(lit(&frames[0]), 300);
+        frames.clear();
+        BathroomViz.frames(&input, Some(50), &mut |frame| frames.push(frame));
+        assert_eq!(
+            frames.len(),
+            tree / 50 + 1 + usize::from(!tree.is_multiple_of(50))
+        );
+        assert_eq!(lit(frames.last().unwrap()), 300);
+    }
+}
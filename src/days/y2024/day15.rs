@@ -0,0 +1,530 @@
+use crate::days::{Day, Parsed};
+use crate::etc::grid::Direction;
+use crate::etc::parse;
+use crate::etc::render::{self, Color, Style};
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::simulation::{Harness, Simulation};
+use crate::etc::solution::DayError;
+use crate::etc::viz::{Frame, Visualize};
+use crate::{Grid, Point, Solution};
+
+/// A position of the warehouse.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Cell {
+    /// a free space
+    Free,
+
+    /// a narrow box
+    Pack,
+
+    /// a wall
+    Wall,
+
+    /// the left part of a wide box
+    BoxLeft,
+    /// the right part of a wide box
+    BoxRight,
+}
+
+impl std::fmt::Debug for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::Free => f.write_str(" "),
+            Self::Pack => f.write_str("O"),
+            Self::Wall => f.write_str("#"),
+            Self::BoxLeft => f.write_str("["),
+            Self::BoxRight => f.write_str("]"),
+        }
+    }
+}
+
+pub type Map = Grid<Cell>;
+
+/// The moves of the robot.
+pub type Moves = Vec<Direction>;
+
+/// Read the grid of cells, the robot starting point and the moves.
+pub fn prepare(input: &str) -> Result<(Map, Point, Moves), DayError> {
+    let malformed = |msg: &str| DayError::Malformed(msg.to_owned());
+    let (grid, moves) = parse::grid_and_rest(input)?;
+
+    let start = grid
+        .position(|c| *c == '@')
+        .ok_or_else(|| malformed("missing robot in input grid"))?;
+
+    if let Some(c) = grid.iter().find(|c| !".@O#[]".contains(**c)) {
+        return Err(malformed(&format!("unexpected char {c:?} in input grid")));
+    }
+    let map = grid.new_from(|c| match c {
+        '.' | '@' => Cell::Free,
+        'O' => Cell::Pack,
+        '#' => Cell::Wall,
+        '[' => Cell::BoxLeft,
+        _ => Cell::BoxRight,
+    });
+
+    let moves = moves.chars().filter_map(Direction::from_char).collect();
+    Ok((map, start, moves))
+}
+
+/// Some changes to be applied to a map.
+#[derive(Default)]
+struct Changes {
+    free: Vec<Point>,
+    update: Vec<(Point, Cell)>,
+}
+
+impl Changes {
+    /// Append some changes to this one.
+    fn append(&mut self, other: &mut Changes) {
+        self.free.append(&mut other.free);
+        self.update.append(&mut other.update);
+    }
+
+    /// Apply the changes to the given map.
+    ///
+    /// First apply the `free` changes and then the updates.
+    fn apply(&self, map: &mut Map) {
+        for point in &self.free {
+            map[*point] = Cell::Free;
+        }
+        for (point, cell) in &self.update {
+            map[*point] = *cell;
+        }
+    }
+}
+
+/// Try to make `target` free by pushing in given direction.
+///
+/// Return the changes to apply to the grid when possible.
+fn try_make_free(grid: &Map, direction: &Point, target: &Point) -> Option<Changes> {
+    match grid.get(target) {
+        Some(Cell::Free) => return Some(Changes::default()),
+        Some(Cell::Wall) | None => return None,
+        Some(Cell::Pack) => {
+            let next_target = *target + *direction;
+            try_make_free(grid, direction, &next_target).map(|mut changes| {
+                changes.update.push((next_target, Cell::Pack));
+                changes.free.push(*target);
+                changes
+            })
+        }
+        Some(c @ Cell::BoxLeft) | Some(c @ Cell::BoxRight)
+            if *direction == Point::WEST || *direction == Point::EAST =>
+        {
+            // east or west pushes are trivial
+            let next_target = *target + *direction;
+            try_make_free(grid, direction, &next_target).map(|mut changes| {
+                changes.update.push((next_target, *c));
+                changes.free.push(*target);
+                changes
+            })
+        }
+        Some(c @ Cell::BoxLeft) | Some(c @ Cell::BoxRight) => {
+            // north and south pushes
+            let left = if *c == Cell::BoxLeft {
+                *target
+            } else {
+                *target + Point::WEST
+            };
+            let right = left + Point::EAST;
+            let left_target = left + *direction;
+            let right_target = right + *direction;
+
+            if let Some(mut left_changes) = try_make_free(grid, direction, &left_target) {
+                if let Some(mut right_changes) = try_make_free(grid, direction, &right_target) {
+                    let mut changes = Changes::default();
+                    changes.append(&mut left_changes);
+                    changes.append(&mut right_changes);
+                    changes.update.push((left_target, Cell::BoxLeft));
+                    changes.update.push((right_target, Cell::BoxRight));
+                    changes.free.push(left);
+                    changes.free.push(right);
+                    return Some(changes);
+                }
+            }
+            None
+        }
+    }
+}
+
+/// Move the robot if possible, return the changes applied to the grid when it moved.
+fn try_move(grid: &mut Map, direction: Direction, robot: &mut Point) -> Option<Changes> {
+    let direction = direction.to_point();
+    let target = grid.step(robot, &direction)?;
+    let changes = try_make_free(grid, &direction, &target)?;
+    changes.apply(grid);
+    *robot = target;
+    Some(changes)
+}
+
+fn compute_score(grid: &Map) -> u64 {
+    let score: i64 = grid
+        .iter_with_position()
+        .map(|(pos, cell)| match cell {
+            Cell::Pack | Cell::BoxLeft => 100 * pos.0 + pos.1,
+            _ => 0,
+        })
+        .sum();
+    score.try_into().unwrap()
+}
+
+/// The warehouse floor: the map and the robot position.
+#[derive(Clone, Debug)]
+struct Floor {
+    map: Map,
+    robot: Point,
+}
+
+/// The robot attempting its moves, one move per step.
+struct Warehouse {
+    floor: Floor,
+    moves: Moves,
+    next_move: usize,
+    /// changes of the last move, none if the robot was blocked
+    last_changes: Changes,
+}
+
+impl Warehouse {
+    fn new(map: Map, robot: Point, moves: Moves) -> Self {
+        Warehouse {
+            floor: Floor { map, robot },
+            moves,
+            next_move: 0,
+            last_changes: Changes::default(),
+        }
+    }
+}
+
+impl Simulation for Warehouse {
+    type State = Floor;
+
+    fn step(&mut self) {
+        if let Some(m) = self.moves.get(self.next_move) {
+            self.last_changes =
+                try_move(&mut self.floor.map, *m, &mut self.floor.robot).unwrap_or_default();
+            self.next_move += 1;
+        }
+    }
+
+    fn state(&self) -> &Floor {
+        &self.floor
+    }
+
+    fn is_done(&self) -> bool {
+        self.next_move >= self.moves.len()
+    }
+}
+
+/// Execute all the robot's moves and compute the final score.
+fn simulate(map: Map, robot: Point, moves: Moves) -> u64 {
+    let mut harness = Harness::new(Warehouse::new(map, robot, moves));
+    harness.run();
+    compute_score(&harness.state().map)
+}
+
+fn solve_part1((map, robot, moves): &(Map, Point, Moves)) -> u64 {
+    simulate(map.clone(), *robot, moves.clone())
+}
+
+/// Scale up the warehouse, every cell but the robot being twice as wide.
+fn widen(map: &Map, robot: Point) -> (Map, Point) {
+    let map = map.scale_horizontal(|cell| match cell {
+        Cell::Pack => [Cell::BoxLeft, Cell::BoxRight],
+        cell => [*cell, *cell],
+    });
+    (map, Point(robot.0, robot.1 * 2))
+}
+
+fn solve_part2((map, robot, moves): &(Map, Point, Moves)) -> u64 {
+    let (map, robot) = widen(map, *robot);
+    simulate(map, robot, moves.clone())
+}
+
+/// Draw the warehouse with a step counter, the boxes pushed by the last move highlighted.
+fn draw(warehouse: &Warehouse) -> String {
+    let Floor { map, robot } = &warehouse.floor;
+    let pushed: Vec<Point> = warehouse
+        .last_changes
+        .update
+        .iter()
+        .map(|(p, _)| *p)
+        .collect();
+    let last_move = match warehouse.next_move {
+        0 => ' ',
+        n => warehouse.moves[n - 1].to_char(),
+    };
+    let header = format!(
+        "Step {}/{} {}\n",
+        warehouse.next_move,
+        warehouse.moves.len(),
+        last_move
+    );
+    header
+        + &render::render(map, |pos, cell| match cell {
+            _ if pos == *robot => Style::plain('@').fg(Color::Red).bold(),
+            Cell::Wall => Style::plain('#').fg(Color::White),
+            Cell::Free => Style::plain('.'),
+            cell if pushed.contains(&pos) => Style::plain(box_glyph(cell)).fg(Color::Yellow).bold(),
+            cell => Style::plain(box_glyph(cell)).fg(Color::Cyan),
+        })
+}
+
+fn box_glyph(cell: &Cell) -> char {
+    match cell {
+        Cell::Pack => 'O',
+        Cell::BoxLeft => '[',
+        _ => ']',
+    }
+}
+
+/// The robot moving in the wide warehouse of part 2, one frame per move by default.
+struct WarehouseViz;
+
+impl Visualize for WarehouseViz {
+    fn frames(&self, input: &str, every: Option<usize>, sink: &mut dyn FnMut(Frame)) {
+        let Ok((map, robot, moves)) = prepare(input) else {
+            return;
+        };
+        let (map, robot) = widen(&map, robot);
+        let every = every.unwrap_or(1);
+        let mut harness = Harness::new(Warehouse::new(map, robot, moves));
+        loop {
+            if harness.steps().is_multiple_of(every) || harness.is_done() {
+                sink(Frame::Text(draw(harness.simulation())));
+            }
+            if !harness.step() {
+                break;
+            }
+        }
+    }
+}
+
+pub struct Day15;
+register_day!(15, Day15);
+
+impl Day for Day15 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |parsed| Ok(Solution::from(solve_part1(parsed))),
+            |parsed| Ok(Solution::from(solve_part2(parsed))),
+        )
+    }
+
+    /// Generate a `size` x `size` warehouse with `size * size` moves.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut cells = vec!['.'; size * size];
+        for l in 0..size {
+            for c in 0..size {
+                let border = l == 0 || c == 0 || l == size - 1 || c == size - 1;
+                cells[l * size + c] = if border || rng.chance(0.05) {
+                    '#'
+                } else if rng.chance(0.25) {
+                    'O'
+                } else {
+                    '.'
+                }
+            }
+        }
+        let free = (0..cells.len())
+            .filter(|i| cells[*i] == '.')
+            .collect::<Vec<_>>();
+        cells[*rng.choose(&free)] = '@';
+
+        let mut input = cells
+            .chunks(size)
+            .map(|line| line.iter().collect::<String>() + "\n")
+            .collect::<String>();
+        input.push('\n');
+        for _ in 0..size {
+            let moves = (0..size)
+                .map(|_| *rng.choose(&['<', '^', '>', 'v']))
+                .collect::<String>();
+            input.push_str(&moves);
+            input.push('\n');
+        }
+        input
+    }
+
+    /// A warehouse map, then moves.
+    fn looks_like(&self, input: &str) -> bool {
+        match shape::blocks(input).as_slice() {
+            [grid, moves] => {
+                shape::is_grid(grid, "#.O@[]")
+                    && moves
+                        .iter()
+                        .all(|line| line.chars().all(|c| "<>^v".contains(c)))
+            }
+            _ => false,
+        }
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(&WarehouseViz)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const SMALLER_EXAMPLE_INPUT: &str = examples::example(15, 1);
+
+    const EXAMPLE_INPUT: &str = examples::example(15, 2);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(SMALLER_EXAMPLE_INPUT).unwrap()), 2028);
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 10092);
+    }
+
+    #[test]
+    fn simulation() {
+        let (map, robot, moves) = prepare(SMALLER_EXAMPLE_INPUT).unwrap();
+        let mut harness = Harness::new(Warehouse::new(map, robot, moves));
+        // blocked by the wall
+        assert!(harness.seek(1));
+        assert_eq!(harness.state().robot, Point(2, 2));
+        assert!(harness.seek(2));
+        assert_eq!(harness.state().robot, Point(1, 2));
+        assert_eq!(harness.run(), 15);
+        assert_eq!(compute_score(&harness.state().map), 2028);
+
+        let (expected, robot, _) = prepare(
+            "########
+#....OO#
+##.....#
+#.....O#
+#.#O@..#
+#...O..#
+#...O..#
+########
+
+",
+        )
+        .unwrap();
+        assert_eq!(harness.state().robot, robot);
+        let map = &harness.state().map;
+        assert_eq!(map.diff(&expected).collect::<Vec<_>>(), vec![]);
+        assert!(*map == expected);
+    }
+
+    #[test]
+    fn visualization() {
+        let input = "#######\n#.....#\n#..O..#\n#..@..#\n#######\n\n>^";
+        let mut frames = vec![];
+        WarehouseViz.frames(input, None, &mut |frame| frames.push(frame));
+        let frames: Vec<String> = frames
+            .into_iter()
+            .map(|frame| match frame {
+                Frame::Text(text) => text,
+                _ => panic!("expected text"),
+            })
+            .collect();
+        assert_eq!(frames.len(), 3);
+        assert!(frames[0].starts_with("Step 0/2  \n"));
+        assert!(frames[2].starts_with("Step 2/2 ^\n"));
+        // the wide box is highlighted only once pushed
+        let highlighted = "\x1b[1;33m[";
+        assert!(!frames[1].contains(highlighted));
+        assert!(frames[2].contains(highlighted));
+    }
+
+    const EXAMPLE_INPUT_2: &str = examples::example(15, 3);
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            solve_part2(&prepare(EXAMPLE_INPUT_2).unwrap()),
+            105 + 207 + 306
+        );
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), 9021);
+    }
+
+    #[test]
+    fn units() {
+        let u: &str = "#######
+#.....#
+#..O..#
+#..@..#
+#######
+
+^";
+        assert_eq!(solve_part2(&prepare(u).unwrap()), 106);
+
+        let u: &str = "#######
+#.....#
+#..O..#
+#..@..#
+#######
+
+>^";
+        assert_eq!(solve_part2(&prepare(u).unwrap()), 106);
+
+        let u: &str = "#######
+#..@..#
+#..O..#
+#.....#
+#######
+
+v";
+        assert_eq!(solve_part2(&prepare(u).unwrap()), 306);
+
+        let u: &str = "#######
+#..@..#
+#..O..#
+#.....#
+#######
+
+>v";
+        assert_eq!(solve_part2(&prepare(u).unwrap()), 306);
+
+        let u: &str = "#######
+#.....#
+#.OO.@#
+#.....#
+#######
+
+<<<<<";
+        assert_eq!(solve_part2(&prepare(u).unwrap()), 406);
+
+        let u: &str = "#######
+#.....#
+#@.OO.#
+#.....#
+#######
+
+>>>>>";
+        assert_eq!(solve_part2(&prepare(u).unwrap()), 418);
+
+        let u: &str = "######
+#....#
+#OOO.#
+#O.O.#
+#OOO.#
+#.OO@#
+#.O..#
+#....#
+######
+
+<vv<<^";
+        let expect: &str = "######
+#O.O.#
+#OOO.#
+#OOO.#
+#.OO@#
+#.O..#
+#....#
+######
+
+<";
+
+        assert_eq!(
+            solve_part2(&prepare(u).unwrap()),
+            solve_part2(&prepare(expect).unwrap())
+        );
+    }
+}
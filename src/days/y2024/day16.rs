@@ -0,0 +1,284 @@
+use crate::days::{Day, Parsed};
+use crate::etc::grid::{Direction, parse_maze};
+use crate::etc::pathfinding::{astar, dijkstra};
+use crate::etc::render::{self, Color, Style};
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+use crate::etc::viz::{self, Frame, Visualize};
+use crate::{Grid, Point, Solution};
+use std::collections::HashSet;
+
+/// A tile of the maze, with the cost of reaching it once explored.
+#[derive(Copy, Clone)]
+pub enum Cell {
+    /// A wall
+    Wall,
+    /// Not reached yet
+    Unreached,
+    /// Reached with minimum cost
+    Reached(u64),
+}
+
+impl std::fmt::Debug for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Wall => f.write_str("#######"),
+            Self::Unreached => f.write_str("       "),
+            Self::Reached(points) => f.write_fmt(format_args!("{points:6} ")),
+        }
+    }
+}
+
+pub type Map = Grid<Cell>;
+
+/// Read the maze, with its start and end tiles.
+pub fn prepare(input: &str) -> Result<(Map, Point, Point), DayError> {
+    let (walls, markers) = parse_maze(input, '#')?;
+    let marker = |c: char, name: &str| {
+        markers
+            .get(&c)
+            .copied()
+            .ok_or_else(|| DayError::Malformed(format!("missing {} cell", name)))
+    };
+    let start = marker('S', "start")?;
+    let end = marker('E', "end")?;
+    let map = walls.new_from(|&wall| if wall { Cell::Wall } else { Cell::Unreached });
+    Ok((map, start, end))
+}
+
+/// The reindeer, on a tile and facing a direction.
+type Reindeer = (Point, Direction);
+
+/// Moves of the reindeer with their points: forward, or turning in place.
+fn moves(map: &Map, (at, direction): Reindeer) -> impl Iterator<Item = (Reindeer, u64)> {
+    let ahead = at + direction.to_point();
+    let forward =
+        matches!(map.get(&ahead), Some(Cell::Unreached)).then_some(((ahead, direction), 1));
+    forward.into_iter().chain([
+        // turn right
+        ((at, direction.rotate_cw()), 1000),
+        // turn left
+        ((at, direction.rotate_ccw()), 1000),
+    ])
+}
+
+fn no_path() -> DayError {
+    DayError::Unsupported("no path from the start to the end".to_owned())
+}
+
+fn solve_part1((map, start, end): &(Map, Point, Point)) -> Result<u64, DayError> {
+    astar(
+        (*start, Direction::East),
+        |reindeer| moves(map, *reindeer),
+        |(at, _)| at.taxicab_distance(end),
+        |(at, _)| at == end,
+    )
+    .goal_distance()
+    .ok_or_else(no_path)
+}
+
+/// The tiles on a best path, with the least points to reach every tile.
+struct BestPaths {
+    tiles: HashSet<Point>,
+    least_distances: Map,
+    /// points of the best paths
+    best: u64,
+}
+
+impl BestPaths {
+    /// How to draw a tile: on a best path or not, over the gradient of its least points.
+    fn style(&self, pos: Point, cell: &Cell) -> Style {
+        match cell {
+            Cell::Wall => Style::plain('#').fg(Color::White),
+            Cell::Unreached => Style::plain(' '),
+            Cell::Reached(points) if self.tiles.contains(&pos) => Style::plain('O')
+                .fg(Color::Yellow)
+                .bold()
+                .bg(Color::gradient(*points, self.best)),
+            Cell::Reached(points) => Style::plain('.').bg(Color::gradient(*points, self.best)),
+        }
+    }
+}
+
+fn best_paths((map, start, end): &(Map, Point, Point)) -> Result<BestPaths, DayError> {
+    // least points to reach every state of the reindeer, starting facing east
+    let search = dijkstra(
+        (*start, Direction::East),
+        |reindeer| moves(map, *reindeer),
+        |_| false,
+    );
+    let arrivals = Direction::ALL.map(|d| (*end, d));
+    let best = arrivals
+        .iter()
+        .filter_map(|arrival| search.distances.get(arrival))
+        .min()
+        .copied()
+        .ok_or_else(no_path)?;
+    let best_arrivals: Vec<Reindeer> = arrivals
+        .into_iter()
+        .filter(|arrival| search.distances.get(arrival) == Some(&best))
+        .collect();
+    let on_a_best_path: HashSet<Point> = search
+        .on_best_paths(&best_arrivals)
+        .into_iter()
+        .map(|(at, _)| at)
+        .collect();
+
+    let least_distances = Grid::from_fn(map.lines, map.columns, |pos| {
+        Direction::ALL
+            .iter()
+            .filter_map(|d| search.distances.get(&(pos, *d)))
+            .min()
+            .map_or(map[pos], |points| Cell::Reached(*points))
+    });
+    Ok(BestPaths {
+        tiles: on_a_best_path,
+        least_distances,
+        best,
+    })
+}
+
+fn solve_part2(parsed: &(Map, Point, Point)) -> Result<u64, DayError> {
+    let paths = best_paths(parsed)?;
+    render::debug_dump(
+        "Least distances and best paths",
+        &paths.least_distances,
+        |pos, cell| paths.style(pos, cell),
+    );
+    Ok(paths.tiles.len() as u64)
+}
+
+/// The maze as an SVG drawing, with the best path tiles over the gradient of least points.
+struct MazeViz;
+
+impl Visualize for MazeViz {
+    fn frames(&self, input: &str, _every: Option<usize>, sink: &mut dyn FnMut(Frame)) {
+        let Ok(paths) = prepare(input).and_then(|parsed| best_paths(&parsed)) else {
+            return;
+        };
+        sink(Frame::Svg(viz::svg(&paths.least_distances, |pos, cell| {
+            paths.style(pos, cell)
+        })));
+    }
+}
+
+pub struct Day16;
+register_day!(16, Day16);
+
+impl Day for Day16 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |parsed| solve_part1(parsed).map(Solution::from),
+            |parsed| solve_part2(parsed).map(Solution::from),
+        )
+    }
+
+    /// Generate a `size` x `size` maze (at least 5, odd), with a few loops.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let size = (size.max(5) - 1) / 2 * 2 + 1;
+        let mut maze = Grid::from_fn(size, size, |_| '#');
+
+        // carve a perfect maze with a randomized depth-first search over odd coordinates
+        let start = Point(size as i64 - 2, 1);
+        let mut stack = vec![start];
+        maze.update(&start, '.');
+        while let Some(&at) = stack.last() {
+            let mut next = [Point::NORTH, Point::EAST, Point::SOUTH, Point::WEST]
+                .into_iter()
+                .filter(|d| {
+                    let to = at + *d * 2;
+                    to.0 > 0
+                        && to.1 > 0
+                        && to.0 < size as i64 - 1
+                        && to.1 < size as i64 - 1
+                        && maze.get(&to) == Some(&'#')
+                })
+                .collect::<Vec<_>>();
+            if next.is_empty() {
+                stack.pop();
+            } else {
+                rng.shuffle(&mut next);
+                maze.update(&(at + next[0]), '.');
+                maze.update(&(at + next[0] * 2), '.');
+                stack.push(at + next[0] * 2);
+            }
+        }
+
+        // break some inner walls to create loops
+        for l in 1..size as i64 - 1 {
+            for c in 1..size as i64 - 1 {
+                if (l + c) % 2 == 1 && rng.chance(0.1) {
+                    maze.update(&Point(l, c), '.');
+                }
+            }
+        }
+
+        maze.update(&start, 'S');
+        maze.update(&Point(1, size as i64 - 2), 'E');
+        maze.items
+            .chunks(size)
+            .map(|line| line.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    /// A maze with a start and an end.
+    fn looks_like(&self, input: &str) -> bool {
+        matches!(shape::blocks(input).as_slice(), [grid] if shape::is_grid(grid, "#.SE"))
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(&MazeViz)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(16, 1);
+
+    const EXAMPLE_INPUT_2: &str = examples::example(16, 2);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), Ok(7036));
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT_2).unwrap()), Ok(11048));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), Ok(45));
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT_2).unwrap()), Ok(64));
+    }
+
+    #[test]
+    fn unreachable_end() {
+        let walled_in = prepare("#####\n#S#E#\n#####\n").unwrap();
+        assert_eq!(solve_part1(&walled_in), Err(no_path()));
+        assert_eq!(solve_part2(&walled_in), Err(no_path()));
+    }
+
+    #[test]
+    fn best_path_tiles() {
+        let parsed = prepare(EXAMPLE_INPUT).unwrap();
+        let paths = best_paths(&parsed).unwrap();
+        assert_eq!(paths.best, 7036);
+        assert!(paths.tiles.contains(&parsed.1) && paths.tiles.contains(&parsed.2));
+        assert!(matches!(paths.least_distances[parsed.1], Cell::Reached(0)));
+        assert!(matches!(
+            paths.least_distances[parsed.2],
+            Cell::Reached(7036)
+        ));
+        assert!(matches!(paths.least_distances[Point(0, 0)], Cell::Wall));
+
+        let mut frames = vec![];
+        MazeViz.frames(EXAMPLE_INPUT, None, &mut |frame| frames.push(frame));
+        let [Frame::Svg(drawing)] = frames.as_slice() else {
+            panic!("expected a drawing");
+        };
+        assert_eq!(drawing.matches(">O</text>").count(), 45);
+    }
+}
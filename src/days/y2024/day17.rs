@@ -0,0 +1,452 @@
+use crate::Solution;
+use crate::days::{Day, Parsed};
+use crate::etc::parse::unsigned_ints;
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+
+/// The 3-bit computer, with its program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Machine {
+    /// register A
+    pub a: u64,
+    /// register B
+    pub b: u64,
+    /// register C
+    pub c: u64,
+    /// instruction pointer
+    pub ip: usize,
+    /// program
+    pub program: Vec<u8>,
+}
+
+/// Read the registers and the program, the instruction pointer starting at 0.
+pub fn prepare(input: &str) -> Result<Machine, DayError> {
+    let mut ints = unsigned_ints(input);
+    let mut register = |name: &str| -> Result<u64, DayError> {
+        let value = ints
+            .next()
+            .ok_or_else(|| DayError::Malformed(format!("missing register {}", name)))?;
+        Ok(value?)
+    };
+    let a = register("A")?;
+    let b = register("B")?;
+    let c = register("C")?;
+    let program = ints
+        .map(|op| {
+            let op: u64 = op?;
+            u8::try_from(op)
+                .ok()
+                .filter(|op| *op <= 7)
+                .ok_or_else(|| DayError::Malformed(format!("{} is not a 3-bit number", op)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Machine {
+        a,
+        b,
+        c,
+        ip: 0,
+        program,
+    })
+}
+
+// opcodes
+const ADV: u8 = 0;
+const BXL: u8 = 1;
+const BST: u8 = 2;
+const JNZ: u8 = 3;
+const BXC: u8 = 4;
+const OUT: u8 = 5;
+const BDV: u8 = 6;
+const CDV: u8 = 7;
+
+/// Division by a power of two, the quotient being 0 for shifts of 64 bits or more.
+fn shr(value: u64, shift: u64) -> u64 {
+    u32::try_from(shift)
+        .ok()
+        .and_then(|shift| value.checked_shr(shift))
+        .unwrap_or(0)
+}
+
+/// Run the program until it halts, return its output.
+fn execute(machine: &mut Machine) -> Result<Vec<u8>, DayError> {
+    let mut out = vec![];
+    let end_ip = machine.program.len();
+    while machine.ip < end_ip {
+        let op = machine.program[machine.ip];
+        let Some(&arg) = machine.program.get(machine.ip + 1) else {
+            return Err(DayError::Malformed(format!(
+                "instruction {} has no operand",
+                machine.ip
+            )));
+        };
+        machine.ip += 2;
+
+        let literal = || arg as u64;
+
+        let combo = || match arg {
+            0..=3 => Ok(arg as u64),
+            4 => Ok(machine.a),
+            5 => Ok(machine.b),
+            6 => Ok(machine.c),
+            _ => Err(DayError::Malformed(format!(
+                "reserved combo operand {}",
+                arg
+            ))),
+        };
+
+        match op {
+            // 0
+            ADV => {
+                machine.a = shr(machine.a, combo()?);
+            }
+            // 1
+            BXL => {
+                machine.b = machine.b ^ literal();
+            }
+            // 2
+            BST => {
+                machine.b = combo()? & 0x7;
+            }
+            // 3
+            JNZ => {
+                if machine.a > 0 {
+                    machine.ip = literal() as usize;
+                }
+            }
+            // 4
+            BXC => {
+                machine.b = machine.b ^ machine.c;
+            }
+            // 5
+            OUT => {
+                out.push((combo()? & 0x7) as u8);
+            }
+            // 6
+            BDV => {
+                machine.b = shr(machine.a, combo()?);
+            }
+            // 7
+            CDV => {
+                machine.c = shr(machine.a, combo()?);
+            }
+            _ => return Err(DayError::Malformed(format!("unknown opcode {}", op))),
+        }
+    }
+    Ok(out)
+}
+
+fn solve_part1(machine: &Machine) -> Result<String, DayError> {
+    let mut machine = machine.clone();
+    let out = execute(&mut machine)?;
+    Ok(out
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+fn dfs(
+    digit_to_ten_bits: &[Vec<u16>; 8],
+    expected: &[u8],
+    low_seven_bits: Option<u16>,
+) -> Option<u64> {
+    if expected.is_empty() {
+        return Some(low_seven_bits.unwrap().into());
+    }
+
+    let has_constraint = low_seven_bits.is_some();
+    let low_seven_bits = low_seven_bits.unwrap_or_default();
+    let mut best_solution: Option<u64> = None;
+
+    let digit = expected[0];
+    for &ten_bits in &digit_to_ten_bits[digit as usize] {
+        if !has_constraint || (ten_bits & 0o177) == low_seven_bits {
+            if let Some(solution) = dfs(
+                digit_to_ten_bits,
+                expected.split_at(1).1,
+                Some((ten_bits >> 3) & 0o177),
+            ) {
+                let solution = (solution << 3) + Into::<u64>::into(ten_bits & 0o7);
+                if best_solution.is_none_or(|best| best > solution) {
+                    best_solution = Some(solution)
+                }
+            }
+        }
+    }
+
+    best_solution
+}
+
+/// solve my specific problem input by hand.
+fn solve_part2(machine: &Machine) -> Result<u64, DayError> {
+    //
+    //          0   2   4   6   8   10  12  14
+    //          --- --- --- --- --- --- --- ---
+    // Program: 2,4,1,3,7,5,0,3,1,5,4,4,5,5,3,0
+    //          --- --- --- --- --- --- --- ---
+    //          BST BXL CDV ADV BXL BXC OUT JNZ
+    //          (A) (3) (B) (3) (5) (_) (B) (0)
+    //
+    // entry: A0 = phi(A, A1)
+    //     0: B0 = A0 & 7
+    //     2: B1 = B0 ^ 3
+    //     4: C0 = A0 >> B1
+    //     6: A1 = A0 >> 3
+    //     8: B2 = B1 ^ 5
+    //    10: B3 = B2 ^ C0
+    //    12: OUT(B3)
+    //    14: if A1 > 0 goto entry
+    //        else halt
+    //
+    // B3 = B2 ^ C0
+    //    = (B1 ^ 5) ^ C0
+    //    = ((B0 ^ 3) ^ 5) ^ C0
+    //    = (((A0 & 7) ^ 3) ^ 5) ^ C0
+    //    = (((A0 & 7) ^ 3) ^ 5) ^ (A0 >> B1)
+    //    = (((A0 & 7) ^ 3) ^ 5) ^ (A0 >> (B0 ^ 3))
+    //    = (((A0 & 7) ^ 3) ^ 5) ^ (A0 >> ((A0 & 7) ^ 3))
+    //  B3 & 7 -> out
+    //
+    //  So each step of the loop reads up to 10 bits of A, consumes 3 bits of A.
+    //
+    let mut machine = machine.clone();
+    find_quine(&mut machine)?.ok_or_else(|| {
+        DayError::Unsupported("the program does not output itself for any register A".to_owned())
+    })
+}
+
+/// Find the smallest value of register A for which the machine outputs its own program.
+fn find_quine(machine: &mut Machine) -> Result<Option<u64>, DayError> {
+    // Mapping from next octal digit that the machine would output to the set of possible 10 bits of register A.
+    let mut digit_to_ten_bits: [Vec<u16>; 8] = Default::default();
+
+    // Build the mapping from all 10 bits patterns to the next output of the machine.
+    for a in 0..(1 << 10) {
+        machine.a = a;
+        machine.ip = 0;
+        let Some(&first_out) = execute(machine)?.first() else {
+            return Ok(None);
+        };
+        digit_to_ten_bits[first_out as usize].push(a as u16);
+    }
+
+    // search the smallest value of A, using the patterns.
+    let expected = machine.program.clone();
+    let Some(a) = dfs(&digit_to_ten_bits, expected.as_slice(), None) else {
+        return Ok(None);
+    };
+    machine.a = a;
+    machine.ip = 0;
+    let out = execute(machine)?;
+    Ok((machine.program == out).then_some(a))
+}
+
+pub struct Day17;
+register_day!(17, Day17);
+
+impl Day for Day17 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |machine| solve_part1(machine).map(Solution::from),
+            |machine| solve_part2(machine).map(Solution::from),
+        )
+    }
+
+    /// Generate a program with the same shape as my puzzle input, and a random register A.
+    ///
+    /// The `size` is ignored. The program constants are chosen so that part 2 has a solution.
+    fn generate(&self, _size: usize, rng: &mut Rng) -> String {
+        loop {
+            let k1 = rng.below(8) as u8;
+            let k2 = rng.below(8) as u8;
+            let mut machine = Machine {
+                a: 0,
+                b: 0,
+                c: 0,
+                ip: 0,
+                program: vec![
+                    BST, 4, BXL, k1, CDV, 5, ADV, 3, BXL, k2, BXC, 4, OUT, 5, JNZ, 0,
+                ],
+            };
+            if matches!(find_quine(&mut machine), Ok(Some(_))) {
+                let program = machine
+                    .program
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>();
+                return format!(
+                    "Register A: {}\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n",
+                    rng.between(1, 1 << 40),
+                    program.join(",")
+                );
+            }
+        }
+    }
+
+    /// Registers, then a program.
+    fn looks_like(&self, input: &str) -> bool {
+        let blocks = shape::blocks(input);
+        let [registers, program] = blocks.as_slice() else {
+            return false;
+        };
+        match (registers.as_slice(), program.as_slice()) {
+            ([a, b, c], [program]) => {
+                shape::matches(a, "Register A: #")
+                    && shape::matches(b, "Register B: #")
+                    && shape::matches(c, "Register C: #")
+                    && program
+                        .strip_prefix("Program: ")
+                        .is_some_and(|program| shape::is_int_list(program, ","))
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(17, 1);
+
+    const EXAMPLE_INPUT_2: &str = examples::example(17, 2);
+
+    #[test]
+    fn example_part1() {
+        let mut machine = Machine {
+            a: 0,
+            b: 0,
+            c: 9,
+            ip: 0,
+            program: vec![2, 6],
+        };
+        execute(&mut machine).unwrap();
+        assert_eq!(1, machine.b);
+
+        let mut machine = Machine {
+            a: 10,
+            b: 0,
+            c: 0,
+            ip: 0,
+            program: vec![5, 0, 5, 1, 5, 4],
+        };
+        assert_eq!(Ok(vec![0, 1, 2]), execute(&mut machine));
+
+        let mut machine = Machine {
+            a: 2024,
+            b: 0,
+            c: 0,
+            ip: 0,
+            program: vec![0, 1, 5, 4, 3, 0],
+        };
+        assert_eq!(
+            Ok(vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]),
+            execute(&mut machine)
+        );
+        assert_eq!(0, machine.a);
+
+        let mut machine = Machine {
+            a: 0,
+            b: 29,
+            c: 0,
+            ip: 0,
+            program: vec![1, 7],
+        };
+        execute(&mut machine).unwrap();
+        assert_eq!(26, machine.b);
+
+        let mut machine = Machine {
+            a: 0,
+            b: 2024,
+            c: 43690,
+            ip: 0,
+            program: vec![4, 0],
+        };
+        execute(&mut machine).unwrap();
+        assert_eq!(44354, machine.b);
+
+        assert_eq!(
+            solve_part1(&prepare(EXAMPLE_INPUT).unwrap()).as_deref(),
+            Ok("4,6,3,5,6,3,5,2,1,0")
+        );
+    }
+
+    #[test]
+    fn example_part2() {
+        let mut machine = Machine {
+            a: 117440,
+            b: 0,
+            c: 0,
+            ip: 0,
+            program: vec![0, 3, 5, 4, 3, 0],
+        };
+        let out = execute(&mut machine).unwrap();
+        assert_eq!(machine.program, out);
+
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT_2).unwrap()), Ok(117440));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            prepare("Register A: 729\n"),
+            Err(DayError::Malformed("missing register B".to_owned()))
+        );
+        let registers = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: ";
+        assert_eq!(
+            prepare(&format!("{registers}0,260")),
+            Err(DayError::Malformed("260 is not a 3-bit number".to_owned()))
+        );
+        assert!(prepare(&format!("{registers}0,8")).is_err());
+        assert!(prepare(&format!("{registers}0,99999999999999999999")).is_err());
+        assert!(matches!(
+            Day17.solve(EXAMPLE_INPUT.to_owned()),
+            Err(DayError::Unsupported(_))
+        ));
+
+        let run = |program: Vec<u8>| {
+            execute(&mut Machine {
+                a: 1 << 40,
+                b: 0,
+                c: 0,
+                ip: 0,
+                program,
+            })
+        };
+        assert_eq!(
+            run(vec![5, 7]),
+            Err(DayError::Malformed("reserved combo operand 7".to_owned()))
+        );
+        assert_eq!(
+            run(vec![5, 4, 5]),
+            Err(DayError::Malformed(
+                "instruction 2 has no operand".to_owned()
+            ))
+        );
+        assert_eq!(
+            run(vec![8, 0]),
+            Err(DayError::Malformed("unknown opcode 8".to_owned()))
+        );
+        // shifting register A by register A
+        assert_eq!(run(vec![0, 4, 5, 4]), Ok(vec![0]));
+    }
+
+    #[test]
+    fn preparation() {
+        let machine = prepare(EXAMPLE_INPUT).unwrap();
+        assert_eq!(
+            Machine {
+                a: 729,
+                b: 0,
+                c: 0,
+                ip: 0,
+                program: vec![0, 1, 5, 4, 3, 0]
+            },
+            machine
+        );
+    }
+}
@@ -0,0 +1,399 @@
+use crate::days::{Day, Parsed};
+use crate::etc::dsu::UnionFind;
+use crate::etc::grid::Topology;
+use crate::etc::parse::unsigned_ints;
+use crate::etc::pathfinding::{self, Dijkstra, astar};
+use crate::etc::rng::Rng;
+use crate::etc::search;
+use crate::etc::shape;
+use crate::etc::simulation::{Harness, Simulation};
+use crate::etc::solution::{DayError, DayResult};
+use crate::etc::viz::{Frame, Visualize};
+use crate::{Grid, Point, Solution, Variant};
+
+/// Read the falling bytes, in order, positions being `Point(x, y)`.
+pub fn prepare(input: &str) -> Result<Vec<Point>, DayError> {
+    let ints = unsigned_ints(input).collect::<Result<Vec<i64>, _>>()?;
+    Ok(ints
+        .chunks_exact(2)
+        .map(|point| Point(point[0], point[1]))
+        .collect())
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+enum Cell {
+    #[default]
+    Free,
+    Corrupted,
+}
+
+/// Length of the shortest path from the entrance to the exit, if any.
+fn shortest_path(map: &Grid<Cell>) -> Option<u64> {
+    escape(map).goal_distance()
+}
+
+/// The positions of a shortest path from the entrance to the exit, both included, if any.
+fn escape_path(map: &Grid<Cell>) -> Option<Vec<Point>> {
+    escape(map).path()
+}
+
+/// Search from the entrance to the exit.
+fn escape(map: &Grid<Cell>) -> Dijkstra<Point> {
+    let exit = Point((map.columns - 1) as i64, (map.lines - 1) as i64);
+    astar(
+        Point(0, 0),
+        |pos| {
+            map.neighbours(pos, Topology::Orthogonal)
+                .filter(|(_, cell)| **cell == Cell::Free)
+                .map(|(neigh, _)| (neigh, 1))
+        },
+        |pos| pos.taxicab_distance(&exit),
+        |pos| *pos == exit,
+    )
+}
+
+/// Bytes falling into the memory space, one byte per step.
+struct Memory {
+    map: Grid<Cell>,
+    corruptions: Vec<Point>,
+    fallen: usize,
+}
+
+impl Memory {
+    fn new(corruptions: Vec<Point>, lines: usize, columns: usize) -> Self {
+        Memory {
+            map: Grid::<Cell>::default(lines, columns),
+            corruptions,
+            fallen: 0,
+        }
+    }
+}
+
+impl Simulation for Memory {
+    type State = Grid<Cell>;
+
+    fn step(&mut self) {
+        if let Some(corrupt) = self.corruptions.get(self.fallen) {
+            self.map.update(corrupt, Cell::Corrupted);
+            self.fallen += 1;
+        }
+    }
+
+    fn state(&self) -> &Grid<Cell> {
+        &self.map
+    }
+
+    fn is_done(&self) -> bool {
+        self.fallen >= self.corruptions.len()
+    }
+}
+
+/// Whether the exit is reachable in the given memory space.
+fn exit_reachable(map: &Grid<Cell>) -> bool {
+    let exit = Point((map.columns - 1) as i64, (map.lines - 1) as i64);
+    if map[Point(0, 0)] != Cell::Free {
+        return false;
+    }
+    let search = pathfinding::bfs(
+        Point(0, 0),
+        |pos| {
+            map.neighbours(pos, Topology::Orthogonal)
+                .filter(|(_, cell)| **cell == Cell::Free)
+                .map(|(neigh, _)| neigh)
+        },
+        |pos| *pos == exit,
+    );
+    search.goal.is_some()
+}
+
+fn solve_part1(
+    corruptions: &[Point],
+    columns: usize,
+    lines: usize,
+    steps: u64,
+) -> Result<u64, DayError> {
+    let mut harness = Harness::new(Memory::new(corruptions.to_vec(), lines, columns));
+    if !harness.seek(steps as usize) {
+        return Err(DayError::Malformed(format!(
+            "{} falling bytes instead of at least {}",
+            corruptions.len(),
+            steps
+        )));
+    }
+    shortest_path(harness.state())
+        .ok_or_else(|| DayError::Unsupported("the exit is not reachable".to_owned()))
+}
+
+fn never_cut() -> DayError {
+    DayError::Unsupported("no byte cuts the exit".to_owned())
+}
+
+/// Binary search the number of fallen bytes cutting the exit: once cut, it stays cut.
+fn solve_part2(corruptions: &[Point], lines: usize, columns: usize) -> Result<String, DayError> {
+    let fallen = search::partition_point(0, corruptions.len() + 1, |fallen| {
+        let mut harness = Harness::new(Memory::new(corruptions.to_vec(), lines, columns));
+        harness.seek(fallen);
+        exit_reachable(harness.state())
+    });
+    let corrupt = fallen
+        .checked_sub(1)
+        .and_then(|last| corruptions.get(last))
+        .ok_or_else(never_cut)?;
+    Ok(format!("{},{}", corrupt.0, corrupt.1))
+}
+
+/// Find the first byte cutting the exit with a union-find: bytes are removed in reverse order
+/// until the entrance and the exit are in the same set.
+fn solve_part2_union_find(
+    corruptions: &[Point],
+    lines: usize,
+    columns: usize,
+) -> Result<String, DayError> {
+    // step at which each cell is corrupted, bytes falling outside of the memory space being
+    // ignored as by the simulation
+    let mut fallen_at = Grid::<Option<usize>>::default(lines, columns);
+    for (step, corrupt) in corruptions.iter().enumerate() {
+        if let Some(fallen) = fallen_at.get_mut(corrupt) {
+            fallen.get_or_insert(step);
+        }
+    }
+
+    let mut sets = UnionFind::new(fallen_at.size());
+    let free = |fallen_at: &Grid<Option<usize>>, sets: &mut UnionFind, pos: Point| {
+        for dir in [Point::NORTH, Point::EAST, Point::SOUTH, Point::WEST] {
+            if let Some(None) = fallen_at.get(&(pos + dir)) {
+                sets.union(
+                    fallen_at.unchecked_index(&pos),
+                    fallen_at.unchecked_index(&(pos + dir)),
+                );
+            }
+        }
+    };
+    fallen_at.for_each_with_position(|pos, fallen| {
+        if fallen.is_none() {
+            free(&fallen_at, &mut sets, pos);
+        }
+    });
+
+    let entrance = fallen_at.unchecked_index(&Point(0, 0));
+    let exit = fallen_at.unchecked_index(&Point((columns - 1) as i64, (lines - 1) as i64));
+    if sets.same_set(entrance, exit) {
+        return Err(never_cut());
+    }
+    for (step, corrupt) in corruptions.iter().enumerate().rev() {
+        if fallen_at.get(corrupt) == Some(&Some(step)) {
+            fallen_at.update(corrupt, None);
+            free(&fallen_at, &mut sets, *corrupt);
+            if sets.same_set(entrance, exit) {
+                return Ok(format!("{},{}", corrupt.0, corrupt.1));
+            }
+        }
+    }
+    Err(never_cut())
+}
+
+/// Colors of the memory visualization.
+const FREE: [u8; 3] = [16, 16, 32];
+const CORRUPTED: [u8; 3] = [110, 110, 110];
+const PATH: [u8; 3] = [60, 200, 90];
+const REROUTING: [u8; 3] = [230, 180, 40];
+const CUTTING: [u8; 3] = [230, 40, 40];
+
+/// Picture of the memory space with an escape path, and the last fallen byte in some color.
+fn picture(map: &Grid<Cell>, path: &[Point], last: Option<(Point, [u8; 3])>) -> Grid<[u8; 3]> {
+    // positions are `Point(x, y)`
+    let mut picture = Grid::from_fn(map.columns, map.lines, |Point(y, x)| {
+        match map[Point(x, y)] {
+            Cell::Free => FREE,
+            Cell::Corrupted => CORRUPTED,
+        }
+    });
+    for pos in path {
+        picture[Point(pos.1, pos.0)] = PATH;
+    }
+    if let Some((pos, color)) = last {
+        picture[Point(pos.1, pos.0)] = color;
+    }
+    picture
+}
+
+/// Bytes falling into a `side` x `side` memory space with the current shortest escape path, up to
+/// the byte cutting the exit.
+///
+/// By default, there is one frame every time a byte falls on the path, forcing another route.
+struct MemoryViz {
+    side: usize,
+}
+
+impl Visualize for MemoryViz {
+    fn frames(&self, input: &str, every: Option<usize>, sink: &mut dyn FnMut(Frame)) {
+        let Ok(corruptions) = prepare(input) else {
+            return;
+        };
+        let mut harness = Harness::new(Memory::new(corruptions.clone(), self.side, self.side));
+        let Some(mut path) = escape_path(harness.state()) else {
+            return;
+        };
+        sink(Frame::Image(picture(harness.state(), &path, None)));
+        while harness.step() {
+            let byte = corruptions[harness.steps() - 1];
+            let rerouted = path.contains(&byte);
+            if rerouted {
+                let Some(new_path) = escape_path(harness.state()) else {
+                    sink(Frame::Image(picture(
+                        harness.state(),
+                        &path,
+                        Some((byte, CUTTING)),
+                    )));
+                    return;
+                };
+                path = new_path;
+            }
+            let shown = match every {
+                Some(every) => harness.steps().is_multiple_of(every),
+                None => rerouted,
+            };
+            if shown {
+                let last = rerouted.then_some((byte, REROUTING));
+                sink(Frame::Image(picture(harness.state(), &path, last)));
+            }
+        }
+    }
+}
+
+pub struct Day18;
+register_day!(18, Day18);
+
+impl Day for Day18 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |corruptions| solve_part1(corruptions, 71, 71, 1024).map(Solution::from),
+            |corruptions| solve_part2(corruptions, 71, 71).map(Solution::from),
+        )
+    }
+
+    /// Generate the falling bytes of the 71 x 71 memory space, until no byte can fall.
+    ///
+    /// The `size` is ignored. The exit remains reachable after the first 1024 bytes.
+    fn generate(&self, _size: usize, rng: &mut Rng) -> String {
+        let side = 71;
+        let mut cells = (1..side * side - 1)
+            .map(|i| Point(i / side, i % side))
+            .collect::<Vec<_>>();
+        loop {
+            rng.shuffle(&mut cells);
+            let mut harness =
+                Harness::new(Memory::new(cells.clone(), side as usize, side as usize));
+            harness.seek(1024);
+            if exit_reachable(harness.state()) {
+                break;
+            }
+        }
+        cells.iter().map(|p| format!("{},{}\n", p.0, p.1)).collect()
+    }
+
+    /// Coordinates of falling bytes.
+    fn looks_like(&self, input: &str) -> bool {
+        shape::all_lines(input, |line| shape::matches(line, "#,#"))
+    }
+
+    fn variants(&self) -> &'static [Variant] {
+        VARIANTS
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(&MemoryViz { side: 71 })
+    }
+}
+
+/// Implementation variants, cross-validated in paranoid mode.
+const VARIANTS: &[Variant] = &[
+    ("bfs", |input| Day18.solve(input)),
+    // only for part 2
+    ("union-find", |input| {
+        let sol2 = solve_part2_union_find(&prepare(&input)?, 71, 71)?;
+        Ok(DayResult::new(18, Solution::Todo(), Solution::from(sol2)))
+    }),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(18, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(
+            solve_part1(&prepare(EXAMPLE_INPUT).unwrap(), 7, 7, 12),
+            Ok(22)
+        );
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            solve_part2(&prepare(EXAMPLE_INPUT).unwrap(), 7, 7).as_deref(),
+            Ok("6,1")
+        );
+    }
+
+    #[test]
+    fn example_part2_union_find() {
+        assert_eq!(
+            solve_part2_union_find(&prepare(EXAMPLE_INPUT).unwrap(), 7, 7).as_deref(),
+            Ok("6,1")
+        );
+    }
+
+    #[test]
+    fn unsolvable() {
+        let corruptions = prepare("1,0\n0,1\n").unwrap();
+        assert!(matches!(
+            solve_part1(&corruptions, 3, 3, 5),
+            Err(DayError::Malformed(_))
+        ));
+        assert!(matches!(
+            solve_part1(&corruptions, 3, 3, 2),
+            Err(DayError::Unsupported(_))
+        ));
+        let corruptions = prepare("1,1\n9,9\n").unwrap();
+        assert_eq!(solve_part2(&corruptions, 3, 3), Err(never_cut()));
+        assert_eq!(solve_part2_union_find(&corruptions, 3, 3), Err(never_cut()));
+    }
+
+    #[test]
+    fn escape() {
+        let mut harness = Harness::new(Memory::new(prepare(EXAMPLE_INPUT).unwrap(), 7, 7));
+        assert!(harness.seek(12));
+        let path = escape_path(harness.state()).unwrap();
+        assert_eq!(path.len(), 23);
+        assert_eq!((path[0], path[22]), (Point(0, 0), Point(6, 6)));
+        assert!(
+            path.windows(2)
+                .all(|step| step[0].taxicab_distance(&step[1]) == 1)
+        );
+
+        let mut frames = vec![];
+        MemoryViz { side: 7 }.frames(EXAMPLE_INPUT, None, &mut |frame| frames.push(frame));
+        let Some(Frame::Image(last)) = frames.last() else {
+            panic!("expected an image");
+        };
+        // the byte at 6,1 cuts the exit
+        assert_eq!(last[Point(1, 6)], CUTTING);
+        assert_eq!(last.iter().filter(|&&pixel| pixel == CUTTING).count(), 1);
+        assert!(frames.len() > 2);
+    }
+
+    #[test]
+    fn simulation() {
+        let mut harness = Harness::new(Memory::new(prepare(EXAMPLE_INPUT).unwrap(), 7, 7));
+        assert!(harness.seek(1));
+        assert_eq!(harness.state().get(&Point(5, 4)), Some(&Cell::Corrupted));
+        assert_eq!(harness.state().get(&Point(4, 2)), Some(&Cell::Free));
+        assert_eq!(harness.run(), 25);
+        assert!(!exit_reachable(harness.state()));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::Solution;
+use crate::days::{Day, Parsed};
+use crate::etc::rng::Rng;
+use crate::etc::search::count_memo;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+use std::collections::BinaryHeap;
+
+/// A towel pattern, as its stripe colours.
+pub type Pattern<'a> = &'a str;
+/// A design to arrange with towels.
+pub type Design<'a> = &'a str;
+
+pub type Patterns<'a> = Vec<Pattern<'a>>;
+pub type Designs<'a> = Vec<Design<'a>>;
+
+/// Read the available patterns and the designs.
+pub fn prepare(input: &str) -> Result<(Patterns<'_>, Designs<'_>), DayError> {
+    // patterns
+    let mut lines = input.lines();
+    let line = lines
+        .next()
+        .ok_or_else(|| DayError::Malformed("no towel patterns".to_owned()))?;
+    let patterns: Patterns = line.split(", ").collect();
+    // an empty pattern would cover nothing, forever
+    if patterns.iter().any(|pattern| pattern.is_empty()) {
+        return Err(DayError::Malformed("empty towel pattern".to_owned()));
+    }
+    lines.next();
+    let designs = lines.collect();
+
+    Ok((patterns, designs))
+}
+
+/// Some sort of 1D DFS where we apply patterns from left to right on each design.
+fn solve_part1((patterns, designs): &(Patterns, Designs)) -> usize {
+    designs
+        .iter()
+        .filter(|&design| {
+            // how far we covered the design from left to right
+            let mut upto: std::collections::HashSet<usize> = Default::default();
+            // where to continue covering the design with a pattern
+            let mut worklist: BinaryHeap<usize> = Default::default();
+            worklist.push(0);
+            // peek the longest covered length in priority
+            while let Some(len) = worklist.pop() {
+                if len == design.len() {
+                    return true;
+                }
+                for pattern in patterns {
+                    let newlen = len + pattern.len();
+                    if upto.contains(&newlen) {
+                        // already covered the design up to that point
+                        continue;
+                    }
+                    if design[len..].starts_with(pattern) {
+                        assert!(upto.insert(newlen));
+                        // covered the design up to that point
+                        worklist.push(newlen);
+                    }
+                }
+            }
+            false
+        })
+        .count()
+}
+
+/// Count how many combinations of patterns cover each design, from left to right, memoizing
+/// the count for each covered length.
+fn solve_part2((patterns, designs): &(Patterns, Designs)) -> u64 {
+    designs
+        .iter()
+        .map(|design| {
+            count_memo(
+                0usize,
+                |&len| {
+                    patterns
+                        .iter()
+                        .filter(move |pattern| design[len..].starts_with(*pattern))
+                        .map(move |pattern| len + pattern.len())
+                },
+                |&len| len == design.len(),
+            )
+        })
+        .sum()
+}
+
+pub struct Day19;
+register_day!(19, Day19);
+
+impl Day for Day19 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |parsed| Ok(Solution::from(solve_part1(parsed))),
+            |parsed| Ok(Solution::from(solve_part2(parsed))),
+        )
+    }
+
+    /// Generate some towel patterns and `size` designs, most of them possible.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let colors = ['w', 'u', 'b', 'r', 'g'];
+        let mut patterns = (0..rng.between(8, 40))
+            .map(|_| {
+                (0..rng.between(1, 5))
+                    .map(|_| *rng.choose(&colors))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        patterns.sort();
+        patterns.dedup();
+
+        let mut input = patterns.join(", ");
+        input.push_str("\n\n");
+        for _ in 0..size {
+            let len = rng.between(10, 40) as usize;
+            let mut design = String::new();
+            while design.len() < len {
+                if rng.chance(0.95) {
+                    let pattern: &String = rng.choose(&patterns);
+                    design.push_str(pattern);
+                } else {
+                    design.push(*rng.choose(&colors));
+                }
+            }
+            input.push_str(&design);
+            input.push('\n');
+        }
+        input
+    }
+
+    /// Towel patterns, then designs.
+    fn looks_like(&self, input: &str) -> bool {
+        let is_word = |word: &str| shape::matches(word, "*");
+        let blocks = shape::blocks(input);
+        let [patterns, designs] = blocks.as_slice() else {
+            return false;
+        };
+        match patterns.as_slice() {
+            [patterns] => {
+                patterns.split(", ").all(is_word) && designs.iter().all(|design| is_word(design))
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(19, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 6);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), 16);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            prepare(""),
+            Err(DayError::Malformed("no towel patterns".to_owned()))
+        );
+        assert_eq!(
+            prepare("r, , b\n\nrb\n"),
+            Err(DayError::Malformed("empty towel pattern".to_owned()))
+        );
+    }
+}
@@ -0,0 +1,449 @@
+use crate::days::{Day, Parsed};
+use crate::etc::grid::{TAXICAB_DIRECTIONS, parse_maze};
+use crate::etc::render::{self, Color, Style};
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+use crate::etc::viz::{Frame, Visualize};
+use crate::{Grid, Point};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// A position of the racetrack.
+#[derive(Copy, PartialEq, Clone)]
+pub enum Cell {
+    Wall,
+    // track with distance from start
+    Track(Option<u64>),
+}
+
+impl std::fmt::Debug for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cell::Wall => f.write_str("#####"),
+            Cell::Track(None) => f.write_str("....."),
+            Cell::Track(Some(dist)) => f.write_fmt(format_args!(" {dist:03} ")),
+        }
+    }
+}
+
+pub type Map = Grid<Cell>;
+
+/// Read the racetrack and its start position.
+pub fn prepare(input: &str) -> Result<(Map, Point), DayError> {
+    let (walls, markers) = parse_maze(input, '#')?;
+    let start = *markers
+        .get(&'S')
+        .ok_or_else(|| DayError::Malformed("missing start position".to_owned()))?;
+    let map = walls.new_from(|&wall| if wall { Cell::Wall } else { Cell::Track(None) });
+    Ok((map, start))
+}
+
+/// Compute track distance from start, update the track distances accordingly.
+fn compute_distances(map: &mut Map, start: Point) {
+    let mut at = Some(start);
+    let mut dist = 0;
+    while let Some(pos) = at {
+        map.update(&pos, Cell::Track(Some(dist)));
+        dist += 1;
+        at = TAXICAB_DIRECTIONS
+            .iter()
+            .map(|dir| pos + *dir)
+            .find(|neigh| matches!(map.get(neigh), Some(Cell::Track(None))));
+    }
+}
+
+/// Length of the track, once distances are computed.
+fn track_length(map: &Map) -> u64 {
+    map.iter()
+        .filter_map(|cell| match cell {
+            Cell::Track(dist) => *dist,
+            Cell::Wall => None,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// How to draw a cell: tracks over the gradient of their distance from start.
+fn distance_style(cell: &Cell, length: u64) -> Style {
+    match cell {
+        Cell::Wall => Style::plain('#').fg(Color::White),
+        Cell::Track(None) => Style::plain('.'),
+        Cell::Track(Some(dist)) => Style::plain(' ').bg(Color::gradient(*dist, length)),
+    }
+}
+
+/// Show the track distances when debug dumps are enabled.
+fn debug_dump_distances(map: &Map) {
+    let length = track_length(map);
+    render::debug_dump("Track distances", map, |_, cell| {
+        distance_style(cell, length)
+    });
+}
+
+/// Compute the list of how much each distinct cheat saves.
+///
+/// For each position on the track, evaluate the possible cheats in the four directions.
+fn compute_cheats(map: &Map, save_min: u64, save_max: u64) -> Vec<u64> {
+    let mut cheats: Vec<u64> = Default::default();
+    map.for_each_with_position(|pos, cell| {
+        if let Cell::Track(Some(dist)) = cell {
+            for dir in [
+                Point::NORTH * 2,
+                Point::EAST * 2,
+                Point::SOUTH * 2,
+                Point::WEST * 2,
+            ] {
+                let at = pos + dir;
+                if let Some(Cell::Track(Some(other_dist))) = map.get(&at) {
+                    if *other_dist > *dist + 2 {
+                        let saves = other_dist - dist - 2;
+                        if saves >= save_min && saves <= save_max {
+                            cheats.push(saves);
+                        }
+                    }
+                }
+            }
+        }
+    });
+    cheats
+}
+
+fn solve_part1((map, start): &(Map, Point), save_min: u64, save_max: u64) -> u64 {
+    let mut map = map.clone();
+    compute_distances(&mut map, *start);
+    debug_dump_distances(&map);
+    let cheats = compute_cheats(&map, save_min, save_max);
+    cheats.len().try_into().unwrap()
+}
+
+/// The track points reachable from `pos` with a cheat of at most `max_len`, with their distance
+/// from the track start and the length of the cheat.
+///
+/// Walls and tracks alike can be crossed while cheating, so the shortest cheat to a point is its
+/// taxicab distance.
+fn cheats_from(
+    map: &Map,
+    pos: Point,
+    max_len: u64,
+) -> impl Iterator<Item = (Point, u64, u64)> + '_ {
+    let radius = max_len as i64;
+    (-radius..=radius)
+        .flat_map(move |line| (-radius..=radius).map(move |column| pos + Point(line, column)))
+        .filter_map(move |at| {
+            let cheat = at.taxicab_distance(&pos);
+            match map.get(&at) {
+                Some(Cell::Track(Some(at_dist))) if cheat <= max_len && at != pos => {
+                    Some((at, *at_dist, cheat))
+                }
+                _ => None,
+            }
+        })
+}
+
+/// Compute the list of how much each distinct cheat saves.
+/// Cheats can be up to `max_len` long.
+fn compute_cheats_upto(map: &Map, save_min: u64, save_max: u64, max_len: u64) -> Vec<u64> {
+    let track: Vec<(Point, u64)> = map
+        .iter_with_position()
+        .filter_map(|(pos, cell)| match cell {
+            Cell::Track(Some(dist)) => Some((pos, *dist)),
+            _ => None,
+        })
+        .collect();
+
+    let mut cheats: Vec<u64> = Default::default();
+    for &(pos, dist) in &track {
+        for (_, other_dist, cheat) in cheats_from(map, pos, max_len) {
+            if other_dist > dist {
+                let saves = other_dist - dist - cheat;
+                if saves >= save_min && saves <= save_max {
+                    cheats.push(saves);
+                }
+            }
+        }
+    }
+
+    cheats
+}
+
+/// A cheat through walls between two track positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cheat {
+    from: Point,
+    to: Point,
+    length: u64,
+    saves: u64,
+}
+
+/// The `count` cheats of up to `max_len` saving the most, at least `save_min`, best first.
+fn best_cheats(map: &Map, save_min: u64, max_len: u64, count: usize) -> Vec<Cheat> {
+    let mut cheats = vec![];
+    map.for_each_with_position(|from, cell| {
+        if let Cell::Track(Some(dist)) = cell {
+            for (to, to_dist, length) in cheats_from(map, from, max_len) {
+                if to_dist > dist + length && to_dist - dist - length >= save_min {
+                    let saves = to_dist - dist - length;
+                    cheats.push(Cheat {
+                        from,
+                        to,
+                        length,
+                        saves,
+                    });
+                }
+            }
+        }
+    });
+    cheats.sort_by_key(|cheat| (std::cmp::Reverse(cheat.saves), cheat.from, cheat.to));
+    cheats.truncate(count);
+    cheats
+}
+
+fn solve_part2((map, start): &(Map, Point), save_min: u64, save_max: u64, max_len: u64) -> u64 {
+    let mut map = map.clone();
+    compute_distances(&mut map, *start);
+    let cheats = compute_cheats_upto(&map, save_min, save_max, max_len);
+    cheats.len().try_into().unwrap()
+}
+
+/// The track distances as a heat map, with the best cheats of part 2 saving at least `save_min`:
+/// the `n`-th cheat goes from the `n`-th lowercase letter to the `n`-th uppercase letter.
+struct CheatViz {
+    save_min: u64,
+}
+
+/// Number of cheats shown by the heat map.
+const SHOWN_CHEATS: usize = 10;
+
+/// Lowercase letter of the `n`-th cheat.
+fn cheat_label(n: usize) -> char {
+    (b'a' + n as u8) as char
+}
+
+impl Visualize for CheatViz {
+    fn frames(&self, input: &str, _every: Option<usize>, sink: &mut dyn FnMut(Frame)) {
+        let Ok((mut map, start)) = prepare(input) else {
+            return;
+        };
+        compute_distances(&mut map, start);
+        let length = track_length(&map);
+        let cheats = best_cheats(&map, self.save_min, 20, SHOWN_CHEATS);
+        let mut out = render::render(&map, |pos, cell| {
+            let style = distance_style(cell, length);
+            // the best cheat wins on cells shared by several cheats
+            let label = cheats.iter().enumerate().find_map(|(n, cheat)| {
+                if cheat.from == pos {
+                    Some(cheat_label(n))
+                } else if cheat.to == pos {
+                    Some(cheat_label(n).to_ascii_uppercase())
+                } else {
+                    None
+                }
+            });
+            match label {
+                Some(label) => Style {
+                    glyph: label,
+                    ..style
+                }
+                .fg(Color::White)
+                .bold(),
+                None => style,
+            }
+        });
+        writeln!(out, "Track of {} picoseconds", length).unwrap();
+        for (n, cheat) in cheats.iter().enumerate() {
+            writeln!(
+                out,
+                "{}: {},{} to {},{} saves {} picoseconds in {}",
+                cheat_label(n),
+                cheat.from.0,
+                cheat.from.1,
+                cheat.to.0,
+                cheat.to.1,
+                cheat.saves,
+                cheat.length
+            )
+            .unwrap();
+        }
+        sink(Frame::Text(out));
+    }
+}
+
+pub struct Day20;
+register_day!(20, Day20);
+
+impl Day for Day20 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |parsed| Ok(solve_part1(parsed, 100, u64::max_value()).into()),
+            |parsed| Ok(solve_part2(parsed, 100, u64::max_value(), 20).into()),
+        )
+    }
+
+    /// Generate a winding single-path racetrack in a `size` x `size` map (at least 7).
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let size = size.max(7);
+        let mut map = vec!['#'; size * size];
+        let mut line = 1;
+        let mut left_to_right = true;
+        loop {
+            for c in 1..size - 1 {
+                map[line * size + c] = '.';
+            }
+            // go down on the right or on the left
+            let spacing = rng.between(2, 3) as usize;
+            if line + spacing >= size - 1 {
+                break;
+            }
+            let c = if left_to_right { size - 2 } else { 1 };
+            for l in line..=line + spacing {
+                map[l * size + c] = '.';
+            }
+            line += spacing;
+            left_to_right = !left_to_right;
+        }
+        let (start, end) = if left_to_right {
+            (size + 1, line * size + size - 2)
+        } else {
+            (size + 1, line * size + 1)
+        };
+        map[start] = 'S';
+        map[end] = 'E';
+        map.chunks(size)
+            .map(|line| line.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    /// A racetrack with a start and an end.
+    fn looks_like(&self, input: &str) -> bool {
+        matches!(shape::blocks(input).as_slice(), [grid] if shape::is_grid(grid, "#.SE"))
+    }
+
+    fn explainer(&self) -> Option<fn(&str) -> String> {
+        Some(explain)
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(&CheatViz { save_min: 100 })
+    }
+}
+
+/// Histogram of the time saved by cheats, in at most 20 buckets.
+fn histogram(out: &mut String, cheats: &[u64]) {
+    let Some(longest) = cheats.iter().max() else {
+        return;
+    };
+    let width = longest.div_ceil(20);
+    let mut buckets: BTreeMap<u64, usize> = Default::default();
+    for saves in cheats {
+        *buckets.entry((saves - 1) / width).or_default() += 1;
+    }
+    for (bucket, count) in buckets {
+        let (low, high) = (bucket * width + 1, (bucket + 1) * width);
+        if width == 1 {
+            writeln!(out, "   {count} cheats save {low} picoseconds").unwrap();
+        } else {
+            writeln!(out, "   {count} cheats save {low} to {high} picoseconds").unwrap();
+        }
+    }
+}
+
+/// The track length and the histogram of cheats of both parts.
+fn explain(input: &str) -> String {
+    let (mut map, start) = match prepare(input) {
+        Ok(parsed) => parsed,
+        Err(err) => return err.to_string(),
+    };
+    compute_distances(&mut map, start);
+    let mut out = format!("Track of {} picoseconds\n", track_length(&map));
+    for (part, cheats) in [
+        (1, compute_cheats(&map, 1, u64::MAX)),
+        (2, compute_cheats_upto(&map, 1, u64::MAX, 20)),
+    ] {
+        writeln!(out, "Part {part}: {} cheats", cheats.len()).unwrap();
+        histogram(&mut out, &cheats);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(20, 1);
+
+    #[test]
+    fn example_part1() {
+        let track = prepare(EXAMPLE_INPUT).unwrap();
+        assert_eq!(solve_part1(&track, 64, 64), 1);
+        assert_eq!(solve_part1(&track, 40, 40), 1);
+        assert_eq!(solve_part1(&track, 38, 38), 1);
+        assert_eq!(solve_part1(&track, 36, 36), 1);
+        assert_eq!(solve_part1(&track, 20, 20), 1);
+        assert_eq!(solve_part1(&track, 12, 12), 3);
+        assert_eq!(solve_part1(&track, 10, 10), 2);
+        assert_eq!(solve_part1(&track, 8, 8), 4);
+        assert_eq!(solve_part1(&track, 6, 6), 2);
+        assert_eq!(solve_part1(&track, 4, 4), 14);
+        assert_eq!(solve_part1(&track, 2, 2), 14);
+    }
+
+    #[test]
+    fn example_part2() {
+        let track = prepare(EXAMPLE_INPUT).unwrap();
+        // from part 1
+        assert_eq!(solve_part2(&track, 64, 64, 2), 1);
+        assert_eq!(solve_part2(&track, 40, 40, 2), 1);
+        assert_eq!(solve_part2(&track, 38, 38, 2), 1);
+        assert_eq!(solve_part2(&track, 36, 36, 2), 1);
+        assert_eq!(solve_part2(&track, 20, 20, 2), 1);
+        assert_eq!(solve_part2(&track, 12, 12, 2), 3);
+        assert_eq!(solve_part2(&track, 10, 10, 2), 2);
+        assert_eq!(solve_part2(&track, 8, 8, 2), 4);
+        assert_eq!(solve_part2(&track, 6, 6, 2), 2);
+        assert_eq!(solve_part2(&track, 4, 4, 2), 14);
+        assert_eq!(solve_part2(&track, 2, 2, 2), 14);
+
+        // with cheats up to 20 ps
+        assert_eq!(solve_part2(&track, 76, 76, 20), 3);
+        assert_eq!(solve_part2(&track, 74, 74, 20), 4);
+        assert_eq!(solve_part2(&track, 70, 70, 20), 12);
+        assert_eq!(solve_part2(&track, 72, 72, 20), 22);
+        assert_eq!(solve_part2(&track, 50, 50, 20), 32);
+    }
+
+    #[test]
+    fn heat_map() {
+        let (mut map, start) = prepare(EXAMPLE_INPUT).unwrap();
+        compute_distances(&mut map, start);
+        let cheats = best_cheats(&map, 74, 20, 10);
+        assert_eq!(cheats.len(), 7);
+        assert!(cheats.iter().all(|cheat| cheat.saves >= 74));
+        assert_eq!(cheats[0].saves, 76);
+        assert_eq!(cheats[0].from, start);
+
+        let mut frames = vec![];
+        CheatViz { save_min: 76 }.frames(EXAMPLE_INPUT, None, &mut |frame| frames.push(frame));
+        let [Frame::Text(text)] = frames.as_slice() else {
+            panic!("expected text");
+        };
+        assert!(text.contains("\x1b[1;37;48;2;0;64;255ma\x1b[0m"));
+        assert!(text.ends_with(
+            "Track of 84 picoseconds\n\
+             a: 3,1 to 7,3 saves 76 picoseconds in 6\n\
+             b: 3,1 to 7,4 saves 76 picoseconds in 7\n\
+             c: 3,1 to 7,5 saves 76 picoseconds in 8\n"
+        ));
+    }
+
+    #[test]
+    fn explain_example() {
+        let explanation = explain(EXAMPLE_INPUT);
+        assert!(explanation.starts_with(
+            "Track of 84 picoseconds\nPart 1: 44 cheats\n   28 cheats save 1 to 4 picoseconds\n   6 cheats save 5 to 8 picoseconds\n"
+        ));
+        assert!(explanation.contains("   1 cheats save 61 to 64 picoseconds\nPart 2:"));
+    }
+}
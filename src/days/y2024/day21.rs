@@ -1,23 +1,26 @@
-use crate::{Solution, SolutionPair};
-use petgraph::algo::dijkstra;
-use petgraph::graph::{Graph, NodeIndex};
-use petgraph::prelude::EdgeIndex;
-use std::collections::HashMap;
+use crate::Solution;
+use crate::days::{Day, Parsed};
+use crate::etc::pathfinding::dijkstra;
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
 
-type Code = [NumericalKey; 4];
+/// A door code, as the keys to press on the numeric keypad.
+pub type Code = [NumericalKey; 4];
 
-fn prepare(input: &str) -> Vec<Code> {
+/// Read the door codes, one per line.
+pub fn prepare(input: &str) -> Result<Vec<Code>, DayError> {
     input
-        .trim()
         .lines()
-        .map(|line| line.trim())
-        .map(|line| {
-            [
-                line.chars().nth(0).unwrap().into(),
-                line.chars().nth(1).unwrap().into(),
-                line.chars().nth(2).unwrap().into(),
-                line.chars().nth(3).unwrap().into(),
-            ]
+        .enumerate()
+        .map(|(i, line)| {
+            let keys = line
+                .chars()
+                .map(NumericalKey::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            keys.try_into().map_err(|_| {
+                DayError::Malformed(format!("line {}: {:?} is not a door code", i + 1, line))
+            })
         })
         .collect()
 }
@@ -32,28 +35,24 @@ enum DirectionalKey {
     Actionate,
 }
 
+/// A key of the numeric keypad.
 #[derive(Clone, Copy, PartialEq, Eq, Default, Hash, Debug)]
-enum NumericalKey {
+pub enum NumericalKey {
     Digit(u8),
     #[default]
     Actionate,
 }
 
-impl From<char> for NumericalKey {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for NumericalKey {
+    type Error = DayError;
+
+    fn try_from(value: char) -> Result<Self, DayError> {
         match value {
-            '0' => Digit(0),
-            '1' => Digit(1),
-            '2' => Digit(2),
-            '3' => Digit(3),
-            '4' => Digit(4),
-            '5' => Digit(5),
-            '6' => Digit(6),
-            '7' => Digit(7),
-            '8' => Digit(8),
-            '9' => Digit(9),
-            'A' => NumericalKey::Actionate,
-            _ => unreachable!("unexpected char in code: {value}"),
+            '0'..='9' => Ok(Digit(value as u8 - b'0')),
+            'A' => Ok(NumericalKey::Actionate),
+            _ => Err(DayError::Malformed(format!(
+                "unexpected char in code: {value:?}"
+            ))),
         }
     }
 }
@@ -209,88 +208,43 @@ fn transition(state: &State, action: DirectionalKey) -> Option<(State, Option<Nu
     }
 }
 
-type SystemGraph = Graph<State, (DirectionalKey, Option<NumericalKey>)>;
-
-fn build_system() -> SystemGraph {
-    // build whole system graph, each edge is a keystroke on the human-actionable directional
-    // keypad.
-    let mut g = SystemGraph::new();
-    let mut states: HashMap<State, NodeIndex> = Default::default();
-    let mut worklist: Vec<NodeIndex> = vec![];
-
-    let start = State::default();
-    let root = g.add_node(start);
-    states.insert(start, root);
-    worklist.push(root);
-
-    while let Some(from) = worklist.pop() {
-        let state = g[from];
-        for action in [Up, Down, Left, Right, Actionate] {
-            let res = transition(&state, action);
-            if let Some((next_state, maybe_output)) = res {
-                let to = *states.entry(next_state).or_insert_with(|| {
-                    let to = g.add_node(next_state);
-                    worklist.push(to);
-                    to
-                });
-                g.add_edge(from, to, (action, maybe_output));
-            }
-        }
-    }
-
-    g
-}
-
-/// Return the mapping from a numerical key to the edge in the system graph that
-/// would output this numerical key.
-///
-/// There is a single edge `X ---(Actionate, Some(K))---> X` that output `K` and leave the
-/// system state `X` unmodified.
-///
-fn action_to_edge(g: &SystemGraph) -> HashMap<NumericalKey, EdgeIndex> {
-    let mut action_edges: HashMap<NumericalKey, EdgeIndex> = Default::default();
-    for e in g.edge_indices() {
-        if let Some((action, Some(w))) = g.edge_weight(e) {
-            assert_eq!(*action, Actionate);
-            action_edges.insert(w.to_owned(), e);
-        }
-    }
-    action_edges
+/// States reached by one keystroke on the human-actionable directional keypad, without any
+/// output of the numerical keypad.
+fn successors(state: &State) -> impl Iterator<Item = (State, u64)> + use<> {
+    let state = *state;
+    [Up, Down, Left, Right, Actionate]
+        .into_iter()
+        .filter_map(move |action| match transition(&state, action) {
+            Some((next_state, None)) => Some((next_state, 1)),
+            _ => None,
+        })
 }
 
-/// Build the graph of the whole system state (`11*5*5` different configurations), with each edge
-/// being an action on the human-facing directional keypad and optionally an output of the
-/// numerical keypad.
+/// Search the whole system state (`11*5*5` different configurations), each move being an action
+/// on the human-facing directional keypad.
 ///
-/// Then accumulate the shortest path length from start configuration to first digit configuration
-/// and so on up to the activate key.
+/// Then accumulate the shortest path length from start configuration to a configuration
+/// outputting the first digit and so on up to the activate key.
 ///
-fn solve_part1(input: &str) -> u64 {
-    let codes = prepare(input);
-    let g = build_system();
-    let a2e = action_to_edge(&g);
-
+fn solve_part1(codes: &[Code]) -> u64 {
     let mut sum_of_complexities = 0u64;
-    for code in codes {
+    for &code in codes {
         let mut numeric_part = 0u64;
         let mut shortest_sequence_len = 0u64;
-        let mut start = petgraph::graph::node_index::<petgraph::graph::DefaultIx>(0);
+        let mut start = State::default();
         for key in code {
-
             match key {
                 Digit(i) => numeric_part = numeric_part * 10 + (i as u64),
-                _ => ()
+                _ => (),
             }
 
             // find length of the shortest path from current state to state that will output the key
-            let output_edge = a2e.get(&key).unwrap().to_owned();
-            let (from, end) = g.edge_endpoints(output_edge).unwrap();
-            assert_eq!(from, end);
-            let shortest_paths = dijkstra(&g, start, Some(end), |_| 1);
-            let len = shortest_paths.get(&end).unwrap().to_owned();
-            shortest_sequence_len += len;
+            let outputs_key =
+                |state: &State| transition(state, Actionate) == Some((*state, Some(key)));
+            let search = dijkstra(start, successors, outputs_key);
+            shortest_sequence_len += search.goal_distance().unwrap();
             shortest_sequence_len += 1; // for the Actionate
-            start = end;
+            start = search.goal.unwrap();
         }
         sum_of_complexities += shortest_sequence_len * numeric_part;
     }
@@ -298,41 +252,73 @@ fn solve_part1(input: &str) -> u64 {
     sum_of_complexities
 }
 
-fn solve_part2(input: &str) -> () {
-    () // TODO
+fn solve_part2(_codes: &[Code]) -> Solution {
+    Solution::pending("not solved yet")
 }
 
-pub fn solve(input: String) -> SolutionPair {
-    let sol1 = solve_part1(&input);
-    let sol2 = solve_part2(&input);
-    (Solution::from(sol1), Solution::from(sol2))
+pub struct Day21;
+register_day!(21, Day21);
+
+impl Day for Day21 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |codes| Ok(Solution::from(solve_part1(codes))),
+            |codes| Ok(solve_part2(codes)),
+        )
+    }
+
+    /// Generate `size` door codes.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| format!("{:03}A\n", rng.between(0, 999)))
+            .collect()
+    }
+
+    /// Door codes.
+    fn looks_like(&self, input: &str) -> bool {
+        shape::all_lines(input, |line| shape::matches(line, "#A"))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::etc::examples;
 
-    const EXAMPLE_INPUT: &str = "029A
-        980A
-        179A
-        456A
-        379A";
+    const EXAMPLE_INPUT: &str = examples::example(21, 1);
 
     #[test]
     fn test_graph() {
-        let g = build_system();
+        let search = dijkstra(State::default(), successors, |_| false);
         // system has 11*5*5 configurations
-        assert_eq!(g.node_count(), 275);
+        assert_eq!(search.distances.len(), 275);
     }
 
     #[test]
     fn example_part1() {
-        assert_eq!(solve_part1(EXAMPLE_INPUT), 126384);
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 126384);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            prepare("029A\n98A\n"),
+            Err(DayError::Malformed(
+                "line 2: \"98A\" is not a door code".to_owned()
+            ))
+        );
+        assert_eq!(
+            prepare("029B\n"),
+            Err(DayError::Malformed(
+                "unexpected char in code: 'B'".to_owned()
+            ))
+        );
     }
 
     #[test]
     fn example_part2() {
         unimplemented!()
-        //assert_eq!(solve_part2(EXAMPLE_INPUT), ());
+        //assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT)), ());
     }
 }
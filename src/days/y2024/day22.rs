@@ -0,0 +1,242 @@
+use crate::Solution;
+use crate::days::{Day, Parsed};
+use crate::etc::checkpoint::{self, Checkpoint, Resumable};
+use crate::etc::counter::Counter;
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+
+/// Read the initial secret number of every buyer.
+pub fn prepare(input: &str) -> Result<Vec<u32>, DayError> {
+    let secrets = input
+        .split_whitespace()
+        .map(|s| {
+            s.parse()
+                .map_err(|_| DayError::Malformed(format!("{:?} is not a secret number", s)))
+        })
+        .collect::<Result<Vec<u32>, _>>()?;
+    if secrets.is_empty() {
+        return Err(DayError::Malformed("no buyer".to_owned()));
+    }
+    Ok(secrets)
+}
+
+fn next_secret(secret: u32) -> u32 {
+    let secret_prime = ((secret << 6) ^ secret) & 0xffffff;
+    let secret_prime = ((secret_prime >> 5) ^ secret_prime) & 0xffffff;
+    ((secret_prime << 11) ^ secret_prime) & 0xffffff
+}
+
+fn solve_part1(secrets: &[u32]) -> u64 {
+    secrets
+        .iter()
+        .copied()
+        .map(|mut secret| -> u64 {
+            for _ in 0..2000 {
+                secret = next_secret(secret)
+            }
+            secret.into()
+        })
+        .sum()
+}
+
+/// A price change sequence.
+type Signal = [i32; 4];
+
+/// Accumulate, buyer by buyer, the bananas each signal would get.
+struct SignalScan {
+    secrets: Vec<u32>,
+    /// next buyer to process
+    next_buyer: usize,
+    signal_price_sum: Counter<Signal>,
+}
+
+impl SignalScan {
+    fn new(secrets: Vec<u32>) -> Self {
+        SignalScan {
+            secrets,
+            next_buyer: 0,
+            signal_price_sum: Default::default(),
+        }
+    }
+
+    /// The signal getting the most bananas, the smallest one on ties.
+    fn best_signal(&self) -> (Signal, u64) {
+        let (signal, sum) = self.signal_price_sum.most_common(1)[0];
+        (*signal, sum)
+    }
+}
+
+/// The price a buyer sells at when seeing a signal for the first time, if ever.
+fn signal_price(secret: u32, signal: &Signal) -> Option<u64> {
+    price_and_changes(secret)
+        .windows(4)
+        .find(|win| [win[0].1, win[1].1, win[2].1, win[3].1] == *signal)
+        .map(|win| win[3].0 as u64)
+}
+
+/// The price change sequence getting the most bananas.
+#[derive(Debug, PartialEq)]
+struct BestSequence {
+    changes: Signal,
+    bananas: u64,
+    /// price every buyer sells at, `None` for buyers never seeing the sequence
+    prices: Vec<Option<u64>>,
+}
+
+/// Compute the sequence of prices and price changes of a buyer.
+fn price_and_changes(mut secret: u32) -> Vec<(i32, i32)> {
+    let mut price_and_changes = Vec::<(i32, i32)>::new();
+    for _ in 0..2000 {
+        let price = (secret % 10) as i32;
+        let secret_prime = next_secret(secret);
+        let price_prime = (secret_prime % 10) as i32;
+        price_and_changes.push((price_prime, price_prime - price));
+        secret = secret_prime;
+    }
+    price_and_changes
+}
+
+impl Resumable for SignalScan {
+    fn advance(&mut self) -> bool {
+        let Some(&secret) = self.secrets.get(self.next_buyer) else {
+            return false;
+        };
+        let mut seen_signal: std::collections::HashSet<Signal> = Default::default();
+        for win in price_and_changes(secret).windows(4) {
+            let signal: Signal = [win[0].1, win[1].1, win[2].1, win[3].1];
+            if seen_signal.insert(signal) {
+                let price: u64 = win[3].0.try_into().unwrap();
+                self.signal_price_sum.add_n(signal, price);
+            }
+        }
+        self.next_buyer += 1;
+        true
+    }
+
+    /// The next buyer, then one `a,b,c,d=sum` line per signal.
+    fn progress(&self) -> Vec<String> {
+        std::iter::once(self.next_buyer.to_string())
+            .chain(
+                self.signal_price_sum
+                    .iter()
+                    .map(|([a, b, c, d], sum)| format!("{a},{b},{c},{d}={sum}")),
+            )
+            .collect()
+    }
+
+    fn restore(&mut self, progress: &[String]) -> Option<()> {
+        let (next_buyer, sums) = progress.split_first()?;
+        self.next_buyer = next_buyer.parse().ok()?;
+        self.signal_price_sum.clear();
+        for line in sums {
+            let (signal, sum) = line.split_once('=')?;
+            let signal = signal
+                .split(',')
+                .map(|change| change.parse().ok())
+                .collect::<Option<Vec<i32>>>()?;
+            self.signal_price_sum
+                .add_n(signal.try_into().ok()?, sum.parse().ok()?);
+        }
+        Some(())
+    }
+}
+
+fn solve_part2(secrets: &[u32], input_hash: u64) -> BestSequence {
+    let mut scan = SignalScan::new(secrets.to_vec());
+    Checkpoint::open("day22-part2", input_hash).run(&mut scan);
+    let (changes, bananas) = scan.best_signal();
+    let prices = scan
+        .secrets
+        .iter()
+        .map(|secret| signal_price(*secret, &changes))
+        .collect();
+    BestSequence {
+        changes,
+        bananas,
+        prices,
+    }
+}
+
+pub struct Day22;
+register_day!(22, Day22);
+
+impl Day for Day22 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input).map(|secrets| (secrets, checkpoint::input_hash(input))),
+            |(secrets, _)| Ok(Solution::from(solve_part1(secrets))),
+            |(secrets, input_hash)| Ok(Solution::from(solve_part2(secrets, *input_hash).bananas)),
+        )
+    }
+
+    /// Generate `size` buyers' initial secret numbers.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| format!("{}\n", rng.between(1, 0xffffff)))
+            .collect()
+    }
+
+    /// Initial secret numbers.
+    fn looks_like(&self, input: &str) -> bool {
+        shape::all_lines(input, |line| shape::matches(line, "#"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(22, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 37327623);
+    }
+
+    const EXAMPLE_INPUT_2: &str = examples::example(22, 2);
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            solve_part2(
+                &prepare(EXAMPLE_INPUT_2).unwrap(),
+                checkpoint::input_hash(EXAMPLE_INPUT_2)
+            ),
+            BestSequence {
+                changes: [-2, 1, -1, 3],
+                bananas: 23,
+                prices: vec![Some(7), Some(7), None, Some(9)],
+            }
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            prepare("1\n-10\n").err(),
+            Some(DayError::Malformed(
+                "\"-10\" is not a secret number".to_owned()
+            ))
+        );
+        assert_eq!(
+            prepare("\n").unwrap_err().to_string(),
+            "malformed input: no buyer"
+        );
+    }
+
+    #[test]
+    fn resume() {
+        let mut scan = SignalScan::new(prepare(EXAMPLE_INPUT_2).unwrap());
+        scan.advance();
+        scan.advance();
+        let progress = scan.progress();
+
+        let mut resumed = SignalScan::new(prepare(EXAMPLE_INPUT_2).unwrap());
+        resumed.restore(&progress).unwrap();
+        assert_eq!(resumed.next_buyer, 2);
+        while resumed.advance() {}
+        assert_eq!(resumed.best_signal(), ([-2, 1, -1, 3], 23));
+    }
+}
@@ -0,0 +1,235 @@
+use crate::Solution;
+use crate::days::{Day, Parsed};
+use crate::etc::graph::{AdjGraph, NodeId};
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+use crate::etc::viz::{Frame, Visualize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+/// Read the connections between computers, as pairs of names.
+pub fn prepare(input: &str) -> Result<Vec<(&str, &str)>, DayError> {
+    let edges = input
+        .split_whitespace()
+        .map(|s| {
+            s.split_once('-')
+                .ok_or_else(|| DayError::Malformed(format!("{:?} is not a connection", s)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if edges.is_empty() {
+        return Err(DayError::Malformed("no connection".to_owned()));
+    }
+    Ok(edges)
+}
+
+/// Find cliques of size 3 that contain at least one computer with a name starting with 't'.
+///
+/// For every edge `(a,b)` with computer `a`'s name starting with `'t'`:
+/// - find any edge `(a,c)` such that `(b,c)` is an existing edge.
+/// - then `{a,b,c}` is a clique of size 3.
+///
+fn solve_part1(edges: &[(&str, &str)]) -> usize {
+    let mut connected: HashSet<(&str, &str)> = Default::default();
+    for (a, b) in edges {
+        connected.insert((a, b));
+        connected.insert((b, a));
+    }
+
+    let mut triples: HashSet<[&str; 3]> = Default::default();
+    for (a, b) in &connected {
+        if a.starts_with('t') {
+            for (a_prime, c) in &connected {
+                if a_prime == a && connected.contains(&(b, c)) {
+                    let mut elems = [*a, *b, *c];
+                    elems.sort();
+                    triples.insert(elems);
+                }
+            }
+        }
+    }
+
+    triples.len()
+}
+
+/// The network graph, computers being named nodes, with an edge per connection.
+fn network<'a>(edges: &[(&'a str, &'a str)]) -> AdjGraph<&'a str> {
+    let mut g = AdjGraph::<&str>::new();
+    let mut computer_index: HashMap<&str, NodeId> = Default::default();
+    for (a, b) in edges {
+        let ka = *computer_index
+            .entry(a)
+            .or_insert_with_key(|name| g.add_node(name));
+        let kb = *computer_index
+            .entry(b)
+            .or_insert_with_key(|name| g.add_node(name));
+        g.add_edge(ka, kb, ());
+    }
+    g
+}
+
+/// Find the maximum clique in the network graph: the largest complete subgraph.
+///
+/// Use the graph's `maximal_cliques` algorithm.
+///
+fn maximum_clique(g: &AdjGraph<&str>) -> Vec<NodeId> {
+    g.maximal_cliques()
+        .into_iter()
+        .max_by(|c1, c2| c1.len().cmp(&c2.len()))
+        .unwrap()
+}
+
+fn solve_part2(edges: &[(&str, &str)]) -> String {
+    let g = network(edges);
+    let mut maximal_clique = maximum_clique(&g).iter().map(|k| g[*k]).collect::<Vec<_>>();
+    maximal_clique.sort();
+    maximal_clique.join(",")
+}
+
+/// The network in the DOT language, the computers and connections of the LAN party in gold, the
+/// names of computers starting with `t` in red.
+fn to_dot(g: &AdjGraph<&str>, party: &[NodeId]) -> String {
+    let mut out = "graph lan {\n  node [shape=ellipse];\n".to_owned();
+    for node in g.node_ids() {
+        let mut attributes = vec![];
+        if party.contains(&node) {
+            attributes.push("style=filled, fillcolor=gold");
+        }
+        if g[node].starts_with('t') {
+            attributes.push("fontcolor=red");
+        }
+        if attributes.is_empty() {
+            writeln!(out, "  \"{}\";", g[node]).unwrap();
+        } else {
+            writeln!(out, "  \"{}\" [{}];", g[node], attributes.join(", ")).unwrap();
+        }
+    }
+    for edge in g.edge_ids() {
+        let (a, b, _) = g.edge(edge);
+        let style = if party.contains(&a) && party.contains(&b) {
+            " [color=gold, penwidth=3]"
+        } else {
+            ""
+        };
+        writeln!(out, "  \"{}\" -- \"{}\"{};", g[a], g[b], style).unwrap();
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// The network as a graph, with the LAN party highlighted.
+struct NetworkViz;
+
+impl Visualize for NetworkViz {
+    fn frames(&self, input: &str, _every: Option<usize>, sink: &mut dyn FnMut(Frame)) {
+        let Ok(edges) = prepare(input) else {
+            return;
+        };
+        let g = network(&edges);
+        sink(Frame::Dot(to_dot(&g, &maximum_clique(&g))));
+    }
+}
+
+pub struct Day23;
+register_day!(23, Day23);
+
+impl Day for Day23 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input),
+            |edges| Ok(Solution::from(solve_part1(edges))),
+            |edges| Ok(Solution::from(solve_part2(edges))),
+        )
+    }
+
+    /// Generate a network of `size` computers (at most 676) with a planted LAN party.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut names = ('a'..='z')
+            .flat_map(|a| ('a'..='z').map(move |b| format!("{a}{b}")))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut names);
+        names.truncate(size.min(names.len()));
+
+        let mut edges: BTreeSet<(String, String)> = Default::default();
+        let mut connect = |a: &String, b: &String| {
+            if a != b && !edges.contains(&(b.to_owned(), a.to_owned())) {
+                edges.insert((a.to_owned(), b.to_owned()));
+            }
+        };
+        // the LAN party
+        let party = names.len().min(13);
+        for (i, a) in names[..party].iter().enumerate() {
+            for b in &names[i + 1..party] {
+                connect(a, b);
+            }
+        }
+        // random connections
+        for a in &names {
+            for _ in 0..3 {
+                connect(a, rng.choose(&names));
+            }
+        }
+
+        let mut edges = edges.into_iter().collect::<Vec<_>>();
+        rng.shuffle(&mut edges);
+        edges.iter().map(|(a, b)| format!("{a}-{b}\n")).collect()
+    }
+
+    /// Connections between computers.
+    fn looks_like(&self, input: &str) -> bool {
+        shape::all_lines(input, |line| shape::matches(line, "*-*"))
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(&NetworkViz)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(23, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), 7);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(&prepare(EXAMPLE_INPUT).unwrap()), "co,de,ka,ta");
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            prepare("kh-tc\nqp\n").err(),
+            Some(DayError::Malformed("\"qp\" is not a connection".to_owned()))
+        );
+        assert_eq!(
+            prepare(" \n").unwrap_err().to_string(),
+            "malformed input: no connection"
+        );
+    }
+
+    #[test]
+    fn dot_export() {
+        let g = network(&prepare(EXAMPLE_INPUT).unwrap());
+        assert_eq!((g.node_count(), g.edge_count()), (16, 32));
+        assert_eq!(maximum_clique(&g).len(), 4);
+
+        let mut frames = vec![];
+        NetworkViz.frames(EXAMPLE_INPUT, None, &mut |frame| frames.push(frame));
+        let [Frame::Dot(dot)] = frames.as_slice() else {
+            panic!("expected a graph");
+        };
+        assert!(dot.starts_with("graph lan {"));
+        assert!(dot.contains(r#""ta" [style=filled, fillcolor=gold, fontcolor=red];"#));
+        assert!(dot.contains(r#""tc" [fontcolor=red];"#));
+        assert!(dot.contains("  \"wq\";\n"));
+        // the 6 connections of the LAN party
+        assert_eq!(dot.matches("[color=gold, penwidth=3]").count(), 6);
+    }
+}
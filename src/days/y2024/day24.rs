@@ -0,0 +1,959 @@
+use crate::Solution;
+use crate::days::{Day, Parsed};
+use crate::etc::checkpoint::{self, Checkpoint, Resumable};
+use crate::etc::graph::topo_sort;
+use crate::etc::parse;
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::etc::solution::DayError;
+use crate::etc::viz::{Frame, Visualize};
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+
+/// A wire, other wires being identified by the bytes of their three-letter name.
+#[derive(Eq, PartialEq, Hash, Debug, Clone, Copy, Ord, PartialOrd)]
+pub enum Wire {
+    Other([u8; 3]),
+    X(u64),
+    Y(u64),
+    Z(u64),
+}
+
+impl ToString for Wire {
+    fn to_string(&self) -> String {
+        match self {
+            Self::X(i) => format!("x{i:02}"),
+            Self::Y(i) => format!("y{i:02}"),
+            Self::Z(i) => format!("z{i:02}"),
+            Self::Other(name) => String::from_utf8_lossy(name).into_owned(),
+        }
+    }
+}
+
+/// The operation of a gate.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+/// A logic gate, combining two input wires into its output wire.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Gate {
+    pub op: Op,
+    pub lhs: Wire,
+    pub rhs: Wire,
+    pub out: Wire,
+}
+
+/// The values of the wires.
+pub type WireValueMap = BTreeMap<Wire, bool>;
+pub type GateVec = VecDeque<Gate>;
+
+/// The wire with a three-letter name, the bits of `x`, `y` and `z` wires being below 64.
+pub fn make_wire(name: &str) -> Result<Wire, DayError> {
+    let malformed = || DayError::Malformed(format!("{:?} is not a wire name", name));
+    let bytes: [u8; 3] = name.as_bytes().try_into().map_err(|_| malformed())?;
+    let bit = || match name[1..].parse() {
+        Ok(bit) if bit < 64 => Ok(bit),
+        _ => Err(malformed()),
+    };
+    match bytes[0] {
+        b'x' => Ok(Wire::X(bit()?)),
+        b'y' => Ok(Wire::Y(bit()?)),
+        b'z' => Ok(Wire::Z(bit()?)),
+        _ => Ok(Wire::Other(bytes)),
+    }
+}
+
+/// Read the initial values of the input wires and the gates.
+pub fn prepare(input: &str) -> Result<(WireValueMap, GateVec), DayError> {
+    let mut gates: GateVec = Default::default();
+    let mut available: WireValueMap = Default::default();
+
+    let mut blocks = parse::blocks(input).into_iter();
+    let inputs = blocks.next().unwrap_or_default();
+    let connections = blocks.next().unwrap_or_default();
+    for line in inputs.lines() {
+        let (name, signal) = match line.split_once(": ") {
+            Some((name, "0")) => (name, false),
+            Some((name, "1")) => (name, true),
+            _ => {
+                return Err(DayError::Malformed(format!(
+                    "{:?} is not an input wire value",
+                    line
+                )));
+            }
+        };
+        available.insert(make_wire(name)?, signal);
+    }
+
+    for line in connections.lines() {
+        let [a, op, b, "->", out] = line.split(' ').collect::<Vec<_>>()[..] else {
+            return Err(DayError::Malformed(format!("{:?} is not a gate", line)));
+        };
+        let (a, b) = (make_wire(a)?, make_wire(b)?);
+        let (lhs, rhs) = if a < b { (a, b) } else { (b, a) };
+        let out = make_wire(out)?;
+        let op = match op {
+            "AND" => Op::And,
+            "OR" => Op::Or,
+            "XOR" => Op::Xor,
+            _ => {
+                return Err(DayError::Malformed(format!(
+                    "{:?} is not a gate operation",
+                    op
+                )));
+            }
+        };
+        gates.push_back(Gate { op, lhs, rhs, out });
+    }
+
+    Ok((available, gates))
+}
+
+/// Evaluate gates in topological order of the wires, every gate after the gates computing its
+/// input signals.
+///
+/// Return `None` if the circuit is not well-formed: it has a cycle, or an input signal of a gate
+/// is neither available nor computed by another gate.
+///
+fn evaluate_circuit(mut available: WireValueMap, gates: GateVec) -> Option<u64> {
+    let order = topo_sort(
+        gates.iter().map(|gate| gate.out),
+        gates
+            .iter()
+            .flat_map(|gate| [(gate.lhs, gate.out), (gate.rhs, gate.out)]),
+    )
+    .ok()?;
+    let computing: HashMap<Wire, &Gate> = gates.iter().map(|gate| (gate.out, gate)).collect();
+    for wire in order {
+        let Some(gate) = computing.get(&wire) else {
+            continue;
+        };
+        let (lhs, rhs) = (*available.get(&gate.lhs)?, *available.get(&gate.rhs)?);
+        let out = match gate.op {
+            Op::And => lhs & rhs,
+            Op::Or => lhs | rhs,
+            Op::Xor => lhs ^ rhs,
+        };
+        available.insert(gate.out, out);
+    }
+
+    Some(
+        available
+            .iter()
+            .filter_map(|(wire, signal)| match wire {
+                Wire::Z(bit) => Some(if *signal { 1 << bit } else { 0 }),
+                _ => None,
+            })
+            .sum(),
+    )
+}
+
+fn solve_part1((available, gates): &(WireValueMap, GateVec)) -> Result<u64, DayError> {
+    evaluate_circuit(available.clone(), gates.clone()).ok_or_else(|| {
+        DayError::Unsupported("the circuit has a cycle or an unconnected gate input".to_owned())
+    })
+}
+
+fn match_op(gate: &Gate, w1: &Wire, w2: &Wire, op: Op) -> bool {
+    gate.op == op && ((gate.lhs == *w1 && gate.rhs == *w2) || (gate.lhs == *w2 && gate.rhs == *w1))
+}
+
+fn match_xor(gate: &Gate, w1: &Wire, w2: &Wire) -> bool {
+    match_op(gate, w1, w2, Op::Xor)
+}
+
+fn match_or(gate: &Gate, w1: &Wire, w2: &Wire) -> bool {
+    match_op(gate, w1, w2, Op::Or)
+}
+
+fn match_and(gate: &Gate, w1: &Wire, w2: &Wire) -> bool {
+    match_op(gate, w1, w2, Op::And)
+}
+
+fn match_out(gate: &Gate, out: &Wire) -> bool {
+    gate.out == *out
+}
+
+/// A circuit ordered by levels, the gates of a level only depending on inputs and gates of the
+/// previous levels.
+struct Levelized {
+    input_len: u64,
+    /// index of every wire in the signals
+    index: HashMap<Wire, usize>,
+    /// gates as `(op, lhs, rhs, out)` wire indices, by level
+    levels: Vec<Vec<(Op, usize, usize, usize)>>,
+}
+
+impl Levelized {
+    /// Return `None` if the circuit is not well-formed.
+    fn new(gates: &GateVec, input_len: u64) -> Option<Self> {
+        let mut index: HashMap<Wire, usize> = Default::default();
+        let mut id = |wire: Wire| {
+            let next = index.len();
+            *index.entry(wire).or_insert(next)
+        };
+        let mut known: HashSet<Wire> = (0..input_len)
+            .flat_map(|i| [Wire::X(i), Wire::Y(i)])
+            .collect();
+        known.iter().for_each(|wire| {
+            id(*wire);
+        });
+        let mut remaining: Vec<Gate> = gates.iter().copied().collect();
+        let mut levels = vec![];
+        while !remaining.is_empty() {
+            let (ready, blocked): (Vec<Gate>, Vec<Gate>) = remaining
+                .into_iter()
+                .partition(|g| known.contains(&g.lhs) && known.contains(&g.rhs));
+            if ready.is_empty() {
+                return None;
+            }
+            known.extend(ready.iter().map(|g| g.out));
+            levels.push(
+                ready
+                    .iter()
+                    .map(|g| (g.op, id(g.lhs), id(g.rhs), id(g.out)))
+                    .collect(),
+            );
+            remaining = blocked;
+        }
+        Some(Levelized {
+            input_len,
+            index,
+            levels,
+        })
+    }
+
+    /// Evaluate 64 input couples at once, bit `k` of every signal being the value for the k-th
+    /// couple. Take and return signals by bit number.
+    fn evaluate_sliced(&self, x: &[u64], y: &[u64]) -> Vec<u64> {
+        let mut signals = vec![0u64; self.index.len()];
+        for i in 0..self.input_len {
+            signals[self.index[&Wire::X(i)]] = x[i as usize];
+            signals[self.index[&Wire::Y(i)]] = y[i as usize];
+        }
+        for level in &self.levels {
+            for &(op, lhs, rhs, out) in level {
+                signals[out] = match op {
+                    Op::And => signals[lhs] & signals[rhs],
+                    Op::Or => signals[lhs] | signals[rhs],
+                    Op::Xor => signals[lhs] ^ signals[rhs],
+                };
+            }
+        }
+        (0..=self.input_len)
+            .map(|i| self.index.get(&Wire::Z(i)).map_or(0, |z| signals[*z]))
+            .collect()
+    }
+
+    /// Output of the circuit for every `(x, y)` couple, evaluated in parallel by 64 couples.
+    fn evaluate_many(&self, inputs: &[(u64, u64)]) -> Vec<u64> {
+        let slice = |values: &mut dyn Iterator<Item = u64>, bits: u64| {
+            let mut slices = vec![0u64; bits as usize];
+            for (k, value) in values.enumerate() {
+                for (bit, slice) in slices.iter_mut().enumerate() {
+                    *slice |= (value >> bit & 1) << k;
+                }
+            }
+            slices
+        };
+        inputs
+            .par_chunks(64)
+            .flat_map_iter(|chunk| {
+                let x = slice(&mut chunk.iter().map(|(x, _)| *x), self.input_len);
+                let y = slice(&mut chunk.iter().map(|(_, y)| *y), self.input_len);
+                let z = self.evaluate_sliced(&x, &y);
+                (0..chunk.len()).map(move |k| {
+                    z.iter()
+                        .enumerate()
+                        .map(|(bit, slice)| (slice >> k & 1) << bit)
+                        .sum::<u64>()
+                })
+            })
+            .collect()
+    }
+}
+
+/// Check the circuit adds `samples` random couples of `input_len`-bit numbers correctly.
+fn adds_correctly(gates: &GateVec, input_len: u64, samples: usize, rng: &mut Rng) -> bool {
+    let Some(circuit) = Levelized::new(gates, input_len) else {
+        return false;
+    };
+    let mask = (1 << input_len) - 1;
+    let inputs: Vec<(u64, u64)> = (0..samples)
+        .map(|_| (rng.next_u64() & mask, rng.next_u64() & mask))
+        .collect();
+    circuit
+        .evaluate_many(&inputs)
+        .iter()
+        .zip(&inputs)
+        .all(|(z, (x, y))| *z == x + y)
+}
+
+/// Swap the output wires `a` and `b` in the circuit.
+fn swap_outputs(gates: &mut GateVec, a: &Wire, b: &Wire) {
+    gates.iter_mut().for_each(|g| {
+        if g.out == *a {
+            g.out = *b;
+        } else if g.out == *b {
+            g.out = *a;
+        }
+    });
+}
+
+/// Search for permutations that fix the adder circuit, one bit at a time.
+struct SwapSearch {
+    gates: GateVec,
+    input_len: u64,
+    /// next bit to check
+    bit: u64,
+    /// gate computing the carry out of the previous bit
+    carry_out: Gate,
+    /// permuted wires, by pairs
+    permuted: Vec<Wire>,
+    /// whether the gates of the bit could not be matched with the gates of a full adder
+    stuck: bool,
+}
+
+impl SwapSearch {
+    /// Return `None` if the adder is wider than 63 bits, or its first bit is not a half adder.
+    fn new(gates: GateVec, input_len: u64) -> Option<Self> {
+        if input_len >= 64 {
+            return None;
+        }
+        let _x0_xor_y0 = gates
+            .iter()
+            .find(|g| match_xor(g, &Wire::X(0), &Wire::Y(0)) && match_out(g, &Wire::Z(0)))?;
+
+        let carry_out = gates
+            .iter()
+            .find(|g| match_and(g, &Wire::X(0), &Wire::Y(0)))
+            .copied()?;
+
+        Some(SwapSearch {
+            gates,
+            input_len,
+            bit: 1,
+            carry_out,
+            permuted: Default::default(),
+            stuck: false,
+        })
+    }
+
+    fn swap(&mut self, a: Wire, b: Wire) {
+        swap_outputs(&mut self.gates, &a, &b);
+        self.permuted.push(a);
+        self.permuted.push(b);
+    }
+
+    /// The sorted, comma-separated names of permuted wires.
+    fn answer(&self) -> String {
+        let mut permuted = self.permuted.clone();
+        permuted.sort();
+        permuted
+            .iter()
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Check the full adder of the next bit, swapping outputs to fix it. Return `None` if its
+    /// gates cannot be found.
+    fn repair_bit(&mut self) -> Option<()> {
+        let i = self.bit;
+        let x = Wire::X(i);
+        let y = Wire::Y(i);
+        let z = Wire::Z(i);
+        let carry_in = self.carry_out.out;
+
+        let x_xor_y = self.gates.iter().find(|g| match_xor(g, &x, &y)).copied()?;
+
+        // expect: `(xi ^ yi) ^ carry -> zi`
+        let x_xor_y_xor_cin = self
+            .gates
+            .iter()
+            .find(|g| match_xor(g, &x_xor_y.out, &carry_in))
+            .copied();
+
+        if let Some(x_xor_y_xor_cin) = x_xor_y_xor_cin {
+            if x_xor_y_xor_cin.out != z {
+                // found `(xi ^ y1) ^ carry -> not zi`
+                self.swap(z, x_xor_y_xor_cin.out);
+            }
+        } else {
+            // cannot find `(xi^yi)^carry` at all.
+            //
+            // so... let's search `k^carry -> zi`
+            // and then permute output of `k` with output of `(xi^yi)`.
+            let k_and_carry = self
+                .gates
+                .iter()
+                .find(|g| g.op == Op::Xor && (g.lhs == carry_in || g.rhs == carry_in) && g.out == z)
+                .copied()?;
+
+            let k = if k_and_carry.lhs == carry_in {
+                k_and_carry.rhs
+            } else {
+                k_and_carry.lhs
+            };
+            self.swap(k, x_xor_y.out);
+        }
+        // reload `xi^yi` since may have permuted its output wire.
+        let gates = &self.gates;
+        let x_xor_y = gates.iter().find(|g| match_xor(g, &x, &y))?;
+
+        let x_and_y = gates.iter().find(|g| match_and(g, &x, &y))?;
+        let x_xor_y_and_carry = gates
+            .iter()
+            .find(|g| match_and(g, &x_xor_y.out, &carry_in))?;
+        let x_and_y_or_x_xor_y_and_carry = gates
+            .iter()
+            .find(|g| match_or(g, &x_and_y.out, &x_xor_y_and_carry.out))?;
+
+        // new carry out
+        self.carry_out = *x_and_y_or_x_xor_y_and_carry;
+        self.bit += 1;
+        Some(())
+    }
+}
+
+impl Resumable for SwapSearch {
+    fn advance(&mut self) -> bool {
+        if self.stuck || self.bit >= self.input_len {
+            return false;
+        }
+        self.stuck = self.repair_bit().is_none();
+        !self.stuck
+    }
+
+    /// The next bit, the carry out wire, then the permuted wires.
+    fn progress(&self) -> Vec<String> {
+        [self.bit.to_string(), self.carry_out.out.to_string()]
+            .into_iter()
+            .chain(self.permuted.iter().map(|w| w.to_string()))
+            .collect()
+    }
+
+    fn restore(&mut self, progress: &[String]) -> Option<()> {
+        let [bit, carry, permuted @ ..] = progress else {
+            return None;
+        };
+        for pair in permuted.chunks(2) {
+            let [a, b] = pair else {
+                return None;
+            };
+            self.swap(make_wire(a).ok()?, make_wire(b).ok()?);
+        }
+        let carry = make_wire(carry).ok()?;
+        self.carry_out = *self.gates.iter().find(|g| g.out == carry)?;
+        self.bit = bit.parse().ok()?;
+        Some(())
+    }
+}
+
+/// Find permutations that fix the adder circuit.
+///
+/// It's a semi-automatic solution. The circuit is a classical adder with carry.
+/// So we do concistency checks of every expected gates and discover permuted gate outputs.
+///
+/// Probably not fixing all possible permutations, but it's enough for my input of the problem.
+///
+fn solve_part2(parsed: &(WireValueMap, GateVec), input_hash: u64) -> Result<String, DayError> {
+    let (available, gates) = parsed;
+    // every input bit has a x wire and a y wire
+    let input_len = available.len() as u64 / 2;
+    let mut search = SwapSearch::new(gates.clone(), input_len).ok_or_else(|| {
+        DayError::Unsupported("the circuit is not an adder of at most 63 bits".to_owned())
+    })?;
+    Checkpoint::open("day24-part2", input_hash).run(&mut search);
+    if search.stuck {
+        return Err(DayError::Unsupported(format!(
+            "the gates of bit {} do not form a full adder",
+            search.bit
+        )));
+    }
+    if !adds_correctly(&search.gates, input_len, 1024, &mut Rng::new(24)) {
+        eprintln!("Warning: the fixed circuit is not an adder");
+    }
+    Ok(search.answer())
+}
+
+/// The bit of the adder of every wire: the highest bit of the input wires it depends on.
+fn wire_bits(gates: &GateVec) -> HashMap<Wire, u64> {
+    let mut bits: HashMap<Wire, u64> = gates
+        .iter()
+        .flat_map(|gate| [gate.lhs, gate.rhs])
+        .filter_map(|wire| match wire {
+            Wire::X(bit) | Wire::Y(bit) => Some((wire, bit)),
+            _ => None,
+        })
+        .collect();
+    // one more gate gets its bit every round, at least, unless there is a cycle
+    for _ in 0..gates.len() {
+        let mut changed = false;
+        for gate in gates {
+            let (Some(lhs), Some(rhs)) = (bits.get(&gate.lhs), bits.get(&gate.rhs)) else {
+                continue;
+            };
+            let bit = *lhs.max(rhs);
+            if bits.insert(gate.out, bit) != Some(bit) {
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    bits
+}
+
+/// The circuit in the DOT language, gates being named after their output wire and grouped by
+/// bit, the gates with a `swapped` output filled in red.
+fn to_dot(gates: &GateVec, swapped: &[Wire], title: &str) -> String {
+    let bits = wire_bits(gates);
+    let mut clusters: BTreeMap<Option<u64>, Vec<String>> = BTreeMap::new();
+    for gate in gates {
+        let op = match gate.op {
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Xor => "XOR",
+        };
+        let mut attributes = format!("label=\"{}\\n{}\"", op, gate.out.to_string());
+        if swapped.contains(&gate.out) {
+            attributes.push_str(", style=filled, fillcolor=red");
+        }
+        if let Wire::Z(_) = gate.out {
+            attributes.push_str(", peripheries=2");
+        }
+        let node = format!("\"{}\" [{}];", gate.out.to_string(), attributes);
+        clusters
+            .entry(bits.get(&gate.out).copied())
+            .or_default()
+            .push(node);
+        for input in [gate.lhs, gate.rhs] {
+            if let Wire::X(_) | Wire::Y(_) = input {
+                let node = format!("\"{}\" [shape=ellipse];", input.to_string());
+                let cluster = clusters.entry(bits.get(&input).copied()).or_default();
+                if !cluster.contains(&node) {
+                    cluster.push(node);
+                }
+            }
+        }
+    }
+
+    let mut out = format!(
+        "digraph circuit {{\n  label=\"{}\";\n  rankdir=LR;\n  node [shape=box];\n",
+        title
+    );
+    for (bit, nodes) in clusters {
+        match bit {
+            Some(bit) => writeln!(
+                out,
+                "  subgraph cluster_{bit:02} {{\n    label=\"bit {bit}\";"
+            )
+            .unwrap(),
+            // gates out of the adder, e.g. in a cycle
+            None => writeln!(out, "  {{").unwrap(),
+        }
+        for node in nodes {
+            writeln!(out, "    {}", node).unwrap();
+        }
+        writeln!(out, "  }}").unwrap();
+    }
+    for gate in gates {
+        for input in [gate.lhs, gate.rhs] {
+            writeln!(
+                out,
+                "  \"{}\" -> \"{}\";",
+                input.to_string(),
+                gate.out.to_string()
+            )
+            .unwrap();
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// The circuit before and after the repair, as graphs with the swapped outputs highlighted.
+struct CircuitViz;
+
+impl Visualize for CircuitViz {
+    fn frames(&self, input: &str, _every: Option<usize>, sink: &mut dyn FnMut(Frame)) {
+        let Ok((available, gates)) = prepare(input) else {
+            return;
+        };
+        let input_len = available.len() as u64 / 2;
+        let Some(mut search) = SwapSearch::new(gates.clone(), input_len) else {
+            return;
+        };
+        while search.advance() {}
+        sink(Frame::Dot(to_dot(
+            &gates,
+            &search.permuted,
+            "before repair",
+        )));
+        sink(Frame::Dot(to_dot(
+            &search.gates,
+            &search.permuted,
+            "after repair",
+        )));
+    }
+}
+
+pub struct Day24;
+register_day!(24, Day24);
+
+impl Day for Day24 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::try_new(
+            prepare(input).map(|parsed| (parsed, checkpoint::input_hash(input))),
+            |(parsed, _)| solve_part1(parsed).map(Solution::from),
+            |(parsed, input_hash)| solve_part2(parsed, *input_hash).map(Solution::from),
+        )
+    }
+
+    /// Generate a `size`-bit adder (6 to 63 bits), with outputs of four pairs of gates swapped
+    /// in the ways `solve_part2` is able to detect.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let bits = size.clamp(6, 63);
+        let mut used: std::collections::HashSet<String> = Default::default();
+        let mut fresh = |rng: &mut Rng| loop {
+            let name = (0..3)
+                .map(|_| (b'a' + rng.below(23) as u8) as char)
+                .collect::<String>();
+            if used.insert(name.clone()) {
+                break name;
+            }
+        };
+
+        let mut input = String::new();
+        for v in ["x", "y"] {
+            for i in 0..bits {
+                input.push_str(&format!("{v}{i:02}: {}\n", rng.below(2)));
+            }
+        }
+        input.push('\n');
+
+        // gates as `[lhs, op, rhs, out]`, with the per bit indices of the sum, and-ed inputs,
+        // and-ed carry and carry out gates.
+        let mut gates: Vec<[String; 4]> = vec![];
+        let mut per_bit: Vec<[usize; 5]> = vec![];
+        let gate = |gates: &mut Vec<[String; 4]>, lhs: &str, op: &str, rhs: &str, out: &str| {
+            gates.push([lhs, op, rhs, out].map(str::to_owned));
+            gates.len() - 1
+        };
+        gate(&mut gates, "x00", "XOR", "y00", "z00");
+        let mut carry = fresh(rng);
+        gate(&mut gates, "x00", "AND", "y00", &carry);
+        for i in 1..bits {
+            let (x, y, z) = (format!("x{i:02}"), format!("y{i:02}"), format!("z{i:02}"));
+            let s = fresh(rng);
+            let a = fresh(rng);
+            let b = fresh(rng);
+            let carry_out = if i == bits - 1 {
+                format!("z{bits:02}")
+            } else {
+                fresh(rng)
+            };
+            per_bit.push([
+                gate(&mut gates, &x, "XOR", &y, &s),
+                gate(&mut gates, &s, "XOR", &carry, &z),
+                gate(&mut gates, &x, "AND", &y, &a),
+                gate(&mut gates, &s, "AND", &carry, &b),
+                gate(&mut gates, &a, "OR", &b, &carry_out),
+            ]);
+            carry = carry_out;
+        }
+
+        // swap outputs at four distinct bits, neither the first nor the last
+        let mut swapped = (0..per_bit.len() - 1).collect::<Vec<_>>();
+        rng.shuffle(&mut swapped);
+        for bit in &swapped[..4] {
+            let [xor_in, xor_out, and_in, and_carry, or_carry] = per_bit[*bit];
+            let (g1, g2) = match rng.below(4) {
+                0 => (xor_out, and_in),
+                1 => (xor_out, and_carry),
+                2 => (xor_out, or_carry),
+                _ => (xor_in, and_in),
+            };
+            let out = gates[g1][3].clone();
+            gates[g1][3] = std::mem::replace(&mut gates[g2][3], out);
+        }
+
+        rng.shuffle(&mut gates);
+        for [lhs, op, rhs, out] in gates {
+            input.push_str(&format!("{lhs} {op} {rhs} -> {out}\n"));
+        }
+        input
+    }
+
+    /// Initial wire values, then gates.
+    fn looks_like(&self, input: &str) -> bool {
+        match shape::blocks(input).as_slice() {
+            [wires, gates] => {
+                wires.iter().all(|line| shape::matches(line, "*: #"))
+                    && gates.iter().all(|line| shape::matches(line, "* * * -> *"))
+            }
+            _ => false,
+        }
+    }
+
+    fn explainer(&self) -> Option<fn(&str) -> String> {
+        Some(explain)
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(&CircuitViz)
+    }
+}
+
+/// The swaps found in the adder, and whether the fixed circuit adds correctly.
+fn explain(input: &str) -> String {
+    let (available, gates) = match prepare(input) {
+        Ok(parsed) => parsed,
+        Err(err) => return err.to_string(),
+    };
+    let input_len = available.len() as u64 / 2;
+    let mut out = format!("{}-bit adder of {} gates\n", input_len, gates.len());
+    let Some(mut search) = SwapSearch::new(gates, input_len) else {
+        out.push_str("Not an adder of at most 63 bits\n");
+        return out;
+    };
+    while search.advance() {}
+    for pair in search.permuted.chunks(2) {
+        writeln!(
+            out,
+            "Swapped outputs {} and {}",
+            pair[0].to_string(),
+            pair[1].to_string()
+        )
+        .unwrap();
+    }
+    if search.stuck {
+        writeln!(
+            out,
+            "The gates of bit {} do not form a full adder",
+            search.bit
+        )
+        .unwrap();
+    }
+    let adds = adds_correctly(&search.gates, input_len, 1024, &mut Rng::new(24));
+    writeln!(
+        out,
+        "Fixed circuit {} 1024 random couples",
+        if adds { "adds" } else { "does not add" }
+    )
+    .unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(24, 1);
+
+    #[test]
+    fn test_make_wire() {
+        assert_eq!(make_wire("z00"), Ok(Wire::Z(0)));
+        assert_eq!(make_wire("z01"), Ok(Wire::Z(1)));
+        assert_eq!(make_wire("z24"), Ok(Wire::Z(24)));
+        assert_eq!(make_wire("a01"), Ok(Wire::Other(*b"a01")));
+        assert_eq!(make_wire("a01").unwrap().to_string(), "a01");
+        assert_eq!(
+            make_wire("zab"),
+            Err(DayError::Malformed("\"zab\" is not a wire name".to_owned()))
+        );
+        assert!(make_wire("x64").is_err());
+        assert!(make_wire("ab").is_err());
+    }
+
+    #[test]
+    fn test_prepare() {
+        let (available, gates) = prepare(EXAMPLE_INPUT).unwrap();
+        let x00 = Wire::X(0);
+        let x01 = Wire::X(1);
+        let x02 = Wire::X(2);
+        let y00 = Wire::Y(0);
+        let y01 = Wire::Y(1);
+        let y02 = Wire::Y(2);
+        let z00 = Wire::Z(0);
+        let z01 = Wire::Z(1);
+        let z02 = Wire::Z(2);
+        assert_eq!(
+            available,
+            WireValueMap::from([
+                (x00, true),
+                (x01, true),
+                (x02, true),
+                (y00, false),
+                (y01, true),
+                (y02, false),
+            ])
+        );
+        assert_eq!(
+            gates,
+            vec![
+                Gate {
+                    op: Op::And,
+                    lhs: x00,
+                    rhs: y00,
+                    out: z00
+                },
+                Gate {
+                    op: Op::Xor,
+                    lhs: x01,
+                    rhs: y01,
+                    out: z01
+                },
+                Gate {
+                    op: Op::Or,
+                    lhs: x02,
+                    rhs: y02,
+                    out: z02
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            prepare("x00: 2\n\nx00 AND y00 -> z00\n").err(),
+            Some(DayError::Malformed(
+                "\"x00: 2\" is not an input wire value".to_owned()
+            ))
+        );
+        assert_eq!(
+            prepare("x00: 1\n\nx00 AND y00 z00\n")
+                .unwrap_err()
+                .to_string(),
+            "malformed input: \"x00 AND y00 z00\" is not a gate"
+        );
+        assert!(prepare("x00: 1\n\nx00 NAND y00 -> z00\n").is_err());
+
+        // a gate input neither given nor computed
+        let parsed = prepare("x00: 1\ny00: 0\n\nx00 AND ghi -> z00\n").unwrap();
+        assert!(matches!(
+            solve_part1(&parsed),
+            Err(DayError::Unsupported(_))
+        ));
+        // no gate computing the carry of bit 1
+        let input = SWAPPED_ADDER.replace("x01 AND y01 -> z01\n", "");
+        let parsed = prepare(&input).unwrap();
+        assert_eq!(
+            solve_part2(&parsed, checkpoint::input_hash(&input)),
+            Err(DayError::Unsupported(
+                "the gates of bit 1 do not form a full adder".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT).unwrap()), Ok(4));
+    }
+
+    #[test]
+    fn levelized() {
+        let (_, gates) = prepare(EXAMPLE_INPUT).unwrap();
+        let circuit = Levelized::new(&gates, 3).unwrap();
+        assert_eq!(circuit.levels.len(), 1);
+        assert_eq!(circuit.evaluate_many(&[(0b111, 0b010)]), vec![4]);
+
+        // more couples than a single slice, against the worklist evaluation
+        let mut rng = Rng::new(24);
+        let input = Day24.generate(20, &mut rng);
+        let (_, gates) = prepare(&input).unwrap();
+        let circuit = Levelized::new(&gates, 20).unwrap();
+        let inputs: Vec<(u64, u64)> = (0..100)
+            .map(|_| (rng.next_u64() & 0xfffff, rng.next_u64() & 0xfffff))
+            .collect();
+        let outputs = circuit.evaluate_many(&inputs);
+        for ((x, y), z) in inputs.iter().zip(outputs) {
+            let available = (0..20)
+                .flat_map(|i| [(Wire::X(i), x >> i & 1 == 1), (Wire::Y(i), y >> i & 1 == 1)])
+                .collect();
+            assert_eq!(evaluate_circuit(available, gates.clone()), Some(z));
+        }
+
+        // a loop
+        let (_, mut gates) = prepare(SWAPPED_ADDER).unwrap();
+        swap_outputs(&mut gates, &Wire::Other(*b"s01"), &Wire::Other(*b"b01"));
+        assert!(Levelized::new(&gates, 2).is_none());
+    }
+
+    /// Two bits adder with outputs of `z01` and `a01` swapped.
+    const SWAPPED_ADDER: &str = "x00: 1
+x01: 1
+y00: 1
+y01: 0
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> s01
+s01 XOR c00 -> a01
+x01 AND y01 -> z01
+s01 AND c00 -> b01
+a01 OR b01 -> z02
+";
+
+    #[test]
+    fn swap_search() {
+        assert_eq!(
+            solve_part2(
+                &prepare(SWAPPED_ADDER).unwrap(),
+                checkpoint::input_hash(SWAPPED_ADDER)
+            ),
+            Ok("a01,z01".to_owned())
+        );
+
+        let (_, gates) = prepare(SWAPPED_ADDER).unwrap();
+        let mut rng = Rng::new(24);
+        assert!(!adds_correctly(&gates, 2, 64, &mut rng));
+        let mut fixed = gates.clone();
+        swap_outputs(&mut fixed, &Wire::Z(1), &Wire::Other(*b"a01"));
+        assert!(adds_correctly(&fixed, 2, 64, &mut rng));
+
+        let mut search = SwapSearch::new(gates.clone(), 2).unwrap();
+        assert!(search.advance());
+        assert!(!search.advance());
+        let progress = search.progress();
+        assert_eq!(progress, vec!["2", "z02", "z01", "a01"]);
+
+        let mut resumed = SwapSearch::new(gates, 2).unwrap();
+        resumed.restore(&progress).unwrap();
+        assert!(!resumed.advance());
+        assert_eq!(resumed.answer(), "a01,z01");
+
+        assert_eq!(
+            explain(SWAPPED_ADDER),
+            "2-bit adder of 7 gates\nSwapped outputs z01 and a01\nFixed circuit adds 1024 random couples\n"
+        );
+    }
+
+    #[test]
+    fn dot_export() {
+        let (_, gates) = prepare(SWAPPED_ADDER).unwrap();
+        assert_eq!(wire_bits(&gates)[&Wire::Other(*b"c00")], 0);
+        assert_eq!(wire_bits(&gates)[&Wire::Z(2)], 1);
+
+        let mut frames = vec![];
+        CircuitViz.frames(SWAPPED_ADDER, None, &mut |frame| frames.push(frame));
+        let [Frame::Dot(before), Frame::Dot(after)] = frames.as_slice() else {
+            panic!("expected two graphs");
+        };
+        assert!(before.contains("label=\"before repair\";"));
+        assert!(before.contains("subgraph cluster_01 {"));
+        assert!(before.contains(r#""a01" [label="XOR\na01", style=filled, fillcolor=red];"#));
+        assert!(after.contains(r#""a01" [label="AND\na01", style=filled, fillcolor=red];"#));
+        assert!(after.contains(r#""s01" -> "z01";"#));
+        assert_eq!(after.matches(" -> ").count(), 14);
+    }
+}
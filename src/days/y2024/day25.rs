@@ -0,0 +1,106 @@
+use crate::days::{Day, Parsed};
+use crate::etc::parse;
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+use crate::{Grid, Solution};
+
+/// The heights of the pins of a lock, or of the cuts of a key.
+pub type Heights = [i32; 5];
+pub type Locks = Vec<Heights>;
+pub type Keys = Vec<Heights>;
+
+/// Read the schematics, sorted into locks and keys.
+pub fn prepare(input: &str) -> (Locks, Keys) {
+    let mut locks = Locks::default();
+    let mut keys = Locks::default();
+
+    for schematic in parse::blocks(input) {
+        let grid = Grid::new(schematic);
+        // the full top or bottom line is not part of the heights
+        let heights: Heights = std::array::from_fn(|column| {
+            grid.iter_col(column).filter(|(_, c)| **c == '#').count() as i32 - 1
+        });
+        if grid.iter_row(0).all(|(_, c)| *c == '#') {
+            locks.push(heights);
+        } else {
+            keys.push(heights);
+        }
+    }
+
+    (locks, keys)
+}
+
+fn solve_part1((locks, keys): &(Locks, Keys)) -> u64 {
+    let mut fits = 0;
+    for lock in locks {
+        for key in keys {
+            if lock.iter().zip(key).all(|(l, k)| l + k < 6) {
+                fits += 1;
+            }
+        }
+    }
+    fits
+}
+
+fn solve_part2(_parsed: &(Locks, Keys)) -> Solution {
+    Solution::pending("the last day has no part 2")
+}
+
+pub struct Day25;
+register_day!(25, Day25);
+
+impl Day for Day25 {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::new(
+            prepare(input),
+            |parsed| Solution::from(solve_part1(parsed)),
+            solve_part2,
+        )
+    }
+
+    /// Generate `size` lock and key schematics.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut schematics = vec![];
+        for _ in 0..size {
+            let is_lock = rng.chance(0.5);
+            let heights = (0..5).map(|_| rng.between(0, 5)).collect::<Vec<_>>();
+            let mut schematic = String::new();
+            for row in 0..7 {
+                for h in &heights {
+                    let filled = if is_lock { row <= *h } else { 6 - row <= *h };
+                    schematic.push(if filled { '#' } else { '.' });
+                }
+                schematic.push('\n');
+            }
+            schematics.push(schematic);
+        }
+        schematics.join("\n")
+    }
+
+    /// Schematics of locks and keys.
+    fn looks_like(&self, input: &str) -> bool {
+        let blocks = shape::blocks(input);
+        !blocks.is_empty() && blocks.iter().all(|block| shape::is_grid(block, "#."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::examples;
+
+    const EXAMPLE_INPUT: &str = examples::example(25, 1);
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(&prepare(EXAMPLE_INPUT)), 3);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            solve_part2(&prepare(EXAMPLE_INPUT)),
+            Solution::pending("the last day has no part 2")
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Expected answers of puzzles, read from `answers.toml`, to check solvers against.
+#![allow(dead_code)]
+use crate::etc::config::key_value;
+use std::collections::BTreeMap;
+
+/// Expected answers file, in the working directory.
+pub const PATH: &str = "answers.toml";
+
+/// Expected answers of both parts of a day, if known.
+pub type Expected = [Option<String>; 2];
+
+/// Read expected answers from a TOML document with one `[dayNN]` table per day, holding `part1`
+/// and `part2` keys with string or integer values. The answers for the inputs of a profile are in
+/// `[<profile>.dayNN]` tables, only the ones of the given profile are kept.
+pub fn parse(toml: &str, profile: Option<&str>) -> Result<BTreeMap<u8, Expected>, String> {
+    let mut answers: BTreeMap<u8, Expected> = BTreeMap::new();
+    // the day of the current table, `None` before the first one, `Some(None)` in other profiles
+    let mut day = None;
+    for (number, line) in toml.lines().enumerate() {
+        let error = |msg: String| format!("{}:{}: {}", PATH, number + 1, msg);
+        if let Some(table) = line.trim().strip_prefix('[') {
+            let name = table.strip_suffix(']').unwrap_or(table);
+            let (owner, day_name) = match name.split_once('.') {
+                Some((owner, day_name)) => (Some(owner), day_name),
+                None => (None, name),
+            };
+            let number = day_name
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| error(format!("expected [dayNN], not [{}]", name)))?;
+            day = Some((owner == profile).then_some(number));
+            continue;
+        }
+        let Some((key, value)) = key_value(line).map_err(error)? else {
+            continue;
+        };
+        let day = day.ok_or_else(|| error("answer outside of a [dayNN] table".to_owned()))?;
+        let part = match key {
+            "part1" => 0,
+            "part2" => 1,
+            _ => return Err(error(format!("unknown key {}", key))),
+        };
+        if let Some(day) = day {
+            answers.entry(day).or_default()[part] = Some(value.to_owned());
+        }
+    }
+    Ok(answers)
+}
+
+/// Expected answers of `answers.toml` for the inputs of a profile, or for mine, none if it does
+/// not exist.
+pub fn load(profile: Option<&str>) -> Result<BTreeMap<u8, Expected>, String> {
+    match std::fs::read_to_string(PATH) {
+        Ok(toml) => parse(&toml, profile),
+        Err(_) => Ok(BTreeMap::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn tables() {
+        let answers = parse(
+            r#"
+            [day01]
+            part1 = 765748
+            part2 = 27732508
+
+            # no part 2 yet
+            [day17]
+            part1 = "6,0,6,3,0,2,3,1,6"
+
+            [alice.day01]
+            part1 = 1189304
+            "#,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            answers[&1],
+            [Some("765748".to_owned()), Some("27732508".to_owned())]
+        );
+        assert_eq!(answers[&17], [Some("6,0,6,3,0,2,3,1,6".to_owned()), None]);
+        assert_eq!(answers.len(), 2);
+
+        let alice = parse(
+            "[day01]\npart1 = 1\n[alice.day01]\npart1 = 2",
+            Some("alice"),
+        )
+        .unwrap();
+        assert_eq!(alice[&1], [Some("2".to_owned()), None]);
+        assert_eq!(alice.len(), 1);
+
+        assert_eq!(
+            parse("part1 = 1", None),
+            Err("answers.toml:1: answer outside of a [dayNN] table".to_owned())
+        );
+        assert!(parse("[day1]\npart3 = 1", None).is_err());
+        assert!(parse("[first]", None).is_err());
+        assert!(parse("[alice.first]", None).is_err());
+    }
+}
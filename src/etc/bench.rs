@@ -0,0 +1,106 @@
+//! Repeated timing of solvers, with statistics robust to noisy runs.
+#![allow(dead_code)]
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+/// Runs before the measured ones, to warm up caches and the allocator.
+pub const WARMUP: usize = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// Number of samples discarded before computing the statistics.
+    pub outliers: usize,
+}
+
+impl Stats {
+    /// Statistics of the samples, discarding the ones beyond 1.5 interquartile range of the
+    /// quartiles, `None` without samples.
+    pub fn new(samples: &[Duration]) -> Option<Stats> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let (q1, q3) = (*sorted.get(sorted.len() / 4)?, sorted[sorted.len() * 3 / 4]);
+        let fence = (q3 - q1) * 3 / 2;
+        let kept: Vec<Duration> = sorted
+            .iter()
+            .copied()
+            .filter(|d| *d + fence >= q1 && *d <= q3 + fence)
+            .collect();
+        Some(Stats {
+            min: kept[0],
+            median: kept[kept.len() / 2],
+            mean: kept.iter().sum::<Duration>() / kept.len() as u32,
+            outliers: samples.len() - kept.len(),
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:?}, median {:?}, mean {:?}",
+            self.min, self.median, self.mean
+        )?;
+        if self.outliers > 0 {
+            write!(f, " ({} outliers discarded)", self.outliers)?;
+        }
+        Ok(())
+    }
+}
+
+/// Run a function [`WARMUP`] times, then `runs` times more (at least once) while timing each
+/// run. Return the result of the last run with the durations of the timed ones.
+pub fn measure<T, F>(runs: usize, mut f: F) -> (T, Vec<Duration>)
+where
+    F: FnMut() -> T,
+{
+    for _ in 0..WARMUP {
+        std::hint::black_box(f());
+    }
+    let mut samples = Vec::with_capacity(runs);
+    loop {
+        let start = Instant::now();
+        let result = f();
+        samples.push(start.elapsed());
+        if samples.len() >= runs {
+            return (result, samples);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Stats, WARMUP, measure};
+    use std::time::Duration;
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        let samples: Vec<Duration> = [12, 10, 11, 10, 95, 13, 11, 12].map(ms).to_vec();
+        let stats = Stats::new(&samples).unwrap();
+        assert_eq!(stats.min, ms(10));
+        assert_eq!(stats.median, ms(11));
+        assert_eq!(stats.mean, ms(79) / 7);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(
+            stats.to_string(),
+            "min 10ms, median 11ms, mean 11.285714ms (1 outliers discarded)"
+        );
+        assert_eq!(Stats::new(&[ms(5)]).unwrap().mean, ms(5));
+        assert_eq!(Stats::new(&[]), None);
+    }
+
+    #[test]
+    fn runs() {
+        let mut calls = 0;
+        let (last, samples) = measure(3, || {
+            calls += 1;
+            calls
+        });
+        assert_eq!(last, WARMUP + 3);
+        assert_eq!(samples.len(), 3);
+    }
+}
@@ -0,0 +1,247 @@
+//! Checkpointing the progress of long-running searches to disk.
+#![allow(dead_code)]
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Directory where checkpoints are stored.
+pub const DIRECTORY: &str = ".checkpoints";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// No checkpointing.
+    #[default]
+    Disabled,
+    /// Save progress periodically.
+    Save,
+    /// Resume from saved progress if any, and save progress periodically.
+    Resume,
+}
+
+static MODE: OnceLock<Mode> = OnceLock::new();
+
+/// Set the checkpointing mode of the whole process, return `false` if it was already set.
+pub fn set_mode(mode: Mode) -> bool {
+    MODE.set(mode).is_ok()
+}
+
+/// The checkpointing mode of the whole process.
+pub fn mode() -> Mode {
+    MODE.get().copied().unwrap_or_default()
+}
+
+/// A stable hash of the puzzle input (64-bit FNV-1a).
+pub fn input_hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// A search whose progress can be saved and restored.
+pub trait Resumable {
+    /// Process the next item of the search, return `false` when the search is over.
+    fn advance(&mut self) -> bool;
+
+    /// Current progress of the search, one item per line.
+    fn progress(&self) -> Vec<String>;
+
+    /// Restore the progress of the search, return `None` on malformed progress.
+    fn restore(&mut self, progress: &[String]) -> Option<()>;
+}
+
+/// Progress file of a search on a given puzzle input.
+pub struct Checkpoint {
+    /// `None` when checkpointing is disabled.
+    path: Option<PathBuf>,
+    resume: bool,
+    interval: Duration,
+    last_save: Instant,
+}
+
+impl Checkpoint {
+    /// Open the checkpoint `name` for the puzzle input of the given [`input_hash`], following
+    /// the process mode.
+    pub fn open(name: &str, input_hash: u64) -> Self {
+        Self::with_mode(mode(), DIRECTORY, name, input_hash)
+    }
+
+    pub fn with_mode(
+        mode: Mode,
+        directory: impl Into<PathBuf>,
+        name: &str,
+        input_hash: u64,
+    ) -> Self {
+        let path = (mode != Mode::Disabled).then(|| {
+            directory
+                .into()
+                .join(format!("{name}-{input_hash:016x}.txt"))
+        });
+        Checkpoint {
+            path,
+            resume: mode == Mode::Resume,
+            interval: Duration::from_secs(1),
+            last_save: Instant::now(),
+        }
+    }
+
+    /// Minimum duration between two periodic saves.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Saved progress, only when resuming.
+    pub fn load(&self) -> Option<Vec<String>> {
+        let path = self.path.as_ref().filter(|_| self.resume)?;
+        let content = std::fs::read_to_string(path).ok()?;
+        Some(content.lines().map(str::to_owned).collect())
+    }
+
+    /// Save the progress.
+    pub fn save(&mut self, progress: &[String]) {
+        if let Some(path) = &self.path {
+            let tmp = path.with_extension("tmp");
+            let res = path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::write(&tmp, progress.join("\n")))
+                .and_then(|_| std::fs::rename(&tmp, path));
+            if let Err(e) = res {
+                eprintln!("warning: cannot save checkpoint {}: {e}", path.display());
+            }
+            self.last_save = Instant::now();
+        }
+    }
+
+    /// Save the progress if the interval elapsed since the last save.
+    pub fn tick<F>(&mut self, progress: F)
+    where
+        F: FnOnce() -> Vec<String>,
+    {
+        if self.path.is_some() && self.last_save.elapsed() >= self.interval {
+            self.save(&progress());
+        }
+    }
+
+    /// Drive the search to completion, restoring saved progress first and saving progress
+    /// periodically. The checkpoint is removed once the search is over. A malformed checkpoint
+    /// is removed, and the search starts over.
+    pub fn run<R: Resumable>(mut self, search: &mut R) {
+        if let Some(progress) = self.load() {
+            let initial = search.progress();
+            if search.restore(&progress).is_none() {
+                let path = self.path.as_ref().unwrap();
+                eprintln!(
+                    "warning: malformed checkpoint {}, starting over",
+                    path.display()
+                );
+                let _ = std::fs::remove_file(path);
+                search.restore(&initial);
+            }
+        }
+        while search.advance() {
+            self.tick(|| search.progress());
+        }
+        if let Some(path) = &self.path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Checkpoint, Mode, Resumable, input_hash};
+    use std::time::Duration;
+
+    /// Sum numbers up to a limit, giving up after `budget` steps.
+    struct Sum {
+        next: u64,
+        limit: u64,
+        sum: u64,
+        budget: usize,
+    }
+
+    impl Resumable for Sum {
+        fn advance(&mut self) -> bool {
+            if self.next > self.limit || self.budget == 0 {
+                return false;
+            }
+            self.sum += self.next;
+            self.next += 1;
+            self.budget -= 1;
+            true
+        }
+
+        fn progress(&self) -> Vec<String> {
+            vec![self.next.to_string(), self.sum.to_string()]
+        }
+
+        fn restore(&mut self, progress: &[String]) -> Option<()> {
+            self.next = progress.first()?.parse().ok()?;
+            self.sum = progress.get(1)?.parse().ok()?;
+            Some(())
+        }
+    }
+
+    #[test]
+    fn hash() {
+        assert_eq!(input_hash(""), 0xcbf29ce484222325);
+        assert_ne!(input_hash("1 2"), input_hash("2 1"));
+    }
+
+    #[test]
+    fn resume() {
+        let dir = std::env::temp_dir().join(format!("aoc-checkpoint-{}", std::process::id()));
+        let open = |mode| {
+            Checkpoint::with_mode(mode, &dir, "sum", input_hash("input"))
+                .with_interval(Duration::ZERO)
+        };
+
+        // interrupted search
+        let mut search = Sum {
+            next: 1,
+            limit: 100,
+            sum: 0,
+            budget: 10,
+        };
+        let mut checkpoint = open(Mode::Save);
+        while search.advance() {
+            checkpoint.tick(|| search.progress());
+        }
+        assert_eq!(open(Mode::Save).load(), None);
+        assert_eq!(
+            open(Mode::Resume).load(),
+            Some(vec!["11".to_owned(), "55".to_owned()])
+        );
+
+        // resumed search
+        let mut search = Sum {
+            next: 1,
+            limit: 100,
+            sum: 0,
+            budget: usize::MAX,
+        };
+        open(Mode::Resume).run(&mut search);
+        assert_eq!(search.sum, 5050);
+        assert_eq!(open(Mode::Resume).load(), None);
+
+        // malformed checkpoint, partially restored
+        open(Mode::Save).save(&["42".to_owned(), "not a sum".to_owned()]);
+        let mut search = Sum {
+            next: 1,
+            limit: 100,
+            sum: 0,
+            budget: usize::MAX,
+        };
+        open(Mode::Resume).run(&mut search);
+        assert_eq!(search.sum, 5050);
+        assert_eq!(open(Mode::Resume).load(), None);
+
+        // disabled
+        let mut checkpoint = open(Mode::Disabled);
+        checkpoint.save(&["1".to_owned()]);
+        assert_eq!(open(Mode::Resume).load(), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
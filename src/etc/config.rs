@@ -0,0 +1,241 @@
+//! Persistent settings, read from `aoc.toml` and overridden by environment variables.
+#![allow(dead_code)]
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Configuration file, in the working directory.
+pub const PATH: &str = "aoc.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Directory of the `dayNN.txt` puzzle inputs.
+    pub input_dir: String,
+    /// Templates of input paths tried before the input directory, see [`expand`], separated by `;`
+    /// in settings.
+    pub input_paths: Vec<String>,
+    /// File holding the session cookie of the Advent of Code website.
+    pub session_path: Option<String>,
+    /// Default output format.
+    pub format: String,
+    /// Number of threads of parallel solvers, all cores by default.
+    pub threads: Option<usize>,
+    /// Warn about days taking longer than this to solve.
+    pub time_budget: Option<Duration>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            input_dir: "./input".to_owned(),
+            input_paths: vec![],
+            session_path: None,
+            format: "text".to_owned(),
+            threads: None,
+            time_budget: None,
+        }
+    }
+}
+
+/// Output formats: one block per day, a table of all days, or one JSON object per line and day.
+pub const FORMATS: [&str; 3] = ["text", "table", "json"];
+
+/// Settings as `(key in aoc.toml, environment variable)`.
+const KEYS: [(&str, &str); 6] = [
+    ("input_dir", "AOC_INPUT_DIR"),
+    ("input_path", "AOC_INPUT_PATH"),
+    ("session_path", "AOC_SESSION_PATH"),
+    ("format", "AOC_FORMAT"),
+    ("threads", "AOC_THREADS"),
+    ("time_budget_ms", "AOC_TIME_BUDGET_MS"),
+];
+
+impl Config {
+    /// Set a value given as text, e.g. from an environment variable.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let number = || {
+            value
+                .parse::<u64>()
+                .map_err(|_| format!("{} must be a number, not {:?}", key, value))
+        };
+        match key {
+            "input_dir" => self.input_dir = value.to_owned(),
+            "input_path" => {
+                // `:` is used by the placeholders, unlike `;`
+                self.input_paths = value.split(';').map(str::to_owned).collect();
+                for template in &self.input_paths {
+                    expand(template, 2024, 1, Some(""))?;
+                }
+            }
+            "session_path" => self.session_path = Some(value.to_owned()),
+            "format" if FORMATS.contains(&value) => self.format = value.to_owned(),
+            "format" => return Err(format!("unknown format {:?}", value)),
+            "threads" => self.threads = Some(number()? as usize),
+            "time_budget_ms" => self.time_budget = Some(Duration::from_millis(number()?)),
+            _ => return Err(format!("unknown setting {}", key)),
+        }
+        Ok(())
+    }
+
+    /// Read settings from a TOML document, limited to `key = value` lines with string and
+    /// integer values, and comments.
+    pub fn parse(toml: &str) -> Result<Self, String> {
+        let mut config = Config::default();
+        for (number, line) in toml.lines().enumerate() {
+            let error = |msg: String| format!("{}:{}: {}", PATH, number + 1, msg);
+            if let Some((key, value)) = key_value(line).map_err(error)? {
+                config.set(key, value).map_err(error)?;
+            }
+        }
+        Ok(config)
+    }
+
+    /// Override settings with the environment variables that are set.
+    pub fn override_from_env(&mut self) -> Result<(), String> {
+        for (key, var) in KEYS {
+            if let Ok(value) = std::env::var(var) {
+                self.set(key, &value)
+                    .map_err(|msg| format!("{}: {}", var, msg))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Settings of `aoc.toml` if it exists, then of the environment.
+    pub fn load() -> Result<Self, String> {
+        let mut config = match std::fs::read_to_string(PATH) {
+            Ok(toml) => Self::parse(&toml)?,
+            Err(_) => Config::default(),
+        };
+        config.override_from_env()?;
+        Ok(config)
+    }
+}
+
+/// The key and value of a `key = value` line of a TOML document, with the quotes of string
+/// values removed. `None` for blank and comment lines.
+pub fn key_value(line: &str) -> Result<Option<(&str, &str)>, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let (key, value) = line
+        .split_once('=')
+        .ok_or_else(|| "expected key = value".to_owned())?;
+    let value = value.trim();
+    let value = match value.strip_prefix('"') {
+        Some(quoted) => quoted
+            .split_once('"')
+            .filter(|(_, rest)| rest.trim().is_empty() || rest.trim().starts_with('#'))
+            .map(|(string, _)| string)
+            .ok_or_else(|| "unterminated string".to_owned())?,
+        None => value.split('#').next().unwrap().trim(),
+    };
+    Ok(Some((key.trim(), value)))
+}
+
+/// Path of the input of a day from a template such as `input/{year}/day{day:02}.txt`, with
+/// `{year}`, `{day}`, `{day:02}` for the day padded to two digits, and `{profile}`. Templates with
+/// a profile are only used for profiles, the others only without, `None` otherwise.
+pub fn expand(
+    template: &str,
+    year: u16,
+    day: u8,
+    profile: Option<&str>,
+) -> Result<Option<String>, String> {
+    let mut path = String::new();
+    let mut rest = template;
+    let mut uses_profile = false;
+    while let Some((before, after)) = rest.split_once('{') {
+        let (name, after) = after
+            .split_once('}')
+            .ok_or_else(|| format!("unterminated placeholder in {}", template))?;
+        path.push_str(before);
+        match name {
+            "year" => path.push_str(&year.to_string()),
+            "day" => path.push_str(&day.to_string()),
+            "day:02" => path.push_str(&format!("{:02}", day)),
+            "profile" => {
+                uses_profile = true;
+                path.push_str(profile.unwrap_or_default());
+            }
+            _ => return Err(format!("unknown placeholder {{{}}} in {}", name, template)),
+        }
+        rest = after;
+    }
+    path.push_str(rest);
+    if !template.contains("{day") {
+        return Err(format!("{} does not depend on the day", template));
+    }
+    Ok((uses_profile == profile.is_some()).then_some(path))
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Set the configuration of the whole process, return `false` if it was already set.
+pub fn set(config: Config) -> bool {
+    CONFIG.set(config).is_ok()
+}
+
+/// The configuration of the whole process, the default one if none was set.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, expand};
+    use std::time::Duration;
+
+    #[test]
+    fn parse() {
+        let config = Config::parse(
+            r#"
+            # my settings
+            input_dir = "inputs/2024" # mine
+            threads = 4
+            time_budget_ms=1000
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.input_dir, "inputs/2024");
+        assert_eq!(config.threads, Some(4));
+        assert_eq!(config.time_budget, Some(Duration::from_secs(1)));
+        assert_eq!(config.format, "text");
+        assert_eq!(config.session_path, None);
+
+        assert_eq!(Config::parse(""), Ok(Config::default()));
+        assert_eq!(
+            Config::parse("\nthreads = many"),
+            Err("aoc.toml:2: threads must be a number, not \"many\"".to_owned())
+        );
+        assert!(Config::parse("colour = \"red\"").is_err());
+        assert!(Config::parse("format = \"html\"").is_err());
+        assert_eq!(Config::parse("format = \"table\"").unwrap().format, "table");
+        assert!(Config::parse("input_dir = \"in").is_err());
+        assert!(Config::parse("input_dir").is_err());
+
+        let config = Config::parse(r#"input_path = "../inputs/{year}/{day}.in;in/day{day:02}""#);
+        assert_eq!(
+            config.unwrap().input_paths,
+            vec!["../inputs/{year}/{day}.in", "in/day{day:02}"]
+        );
+        assert!(Config::parse(r#"input_path = "in/{month}/{day}""#).is_err());
+        assert!(Config::parse(r#"input_path = "in/{year}.txt""#).is_err());
+    }
+
+    #[test]
+    fn templates() {
+        let template = "input/{year}/day{day:02}.txt";
+        assert_eq!(
+            expand(template, 2024, 7, None),
+            Ok(Some("input/2024/day07.txt".to_owned()))
+        );
+        assert_eq!(expand(template, 2024, 7, Some("alice")), Ok(None));
+        assert_eq!(
+            expand("friends/{profile}/{day}", 2024, 17, Some("alice")),
+            Ok(Some("friends/alice/17".to_owned()))
+        );
+        assert_eq!(expand("friends/{profile}/{day}", 2024, 17, None), Ok(None));
+        assert!(expand("input/{day", 2024, 1, None).is_err());
+    }
+}
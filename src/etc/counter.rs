@@ -0,0 +1,159 @@
+//! Multisets: how many times every item was seen.
+#![allow(dead_code)]
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Sub};
+
+/// A multiset, counting items. Items counted 0 times are not stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Eq + Hash> {
+    counts: HashMap<T, u64>,
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Counter {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count an item once more.
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    /// Count an item `n` times more.
+    pub fn add_n(&mut self, item: T, n: u64) {
+        if n > 0 {
+            *self.counts.entry(item).or_default() += n;
+        }
+    }
+
+    /// Number of times an item was counted, 0 if never.
+    pub fn count(&self, item: &T) -> u64 {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// Number of distinct items.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Sum of the counts of all items.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+
+    /// The distinct items with their count, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, u64)> {
+        self.counts.iter().map(|(item, count)| (item, *count))
+    }
+}
+
+impl<T: Eq + Hash + Ord> Counter<T> {
+    /// The `n` most counted items with their count, most counted first, the smallest items first
+    /// on ties.
+    pub fn most_common(&self, n: usize) -> Vec<(&T, u64)> {
+        let mut items: Vec<(&T, u64)> = self.iter().collect();
+        items.sort_unstable_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        items.truncate(n);
+        items
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Eq + Hash> AddAssign for Counter<T> {
+    fn add_assign(&mut self, other: Self) {
+        for (item, count) in other.counts {
+            self.add_n(item, count);
+        }
+    }
+}
+
+/// Counts of both counters summed.
+impl<T: Eq + Hash> Add for Counter<T> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+/// Counts of the other counter taken away, items counted less often than in the other dropped.
+impl<T: Eq + Hash> Sub for Counter<T> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        for (item, count) in other.counts {
+            if let Some(mine) = self.counts.get_mut(&item) {
+                if *mine > count {
+                    *mine -= count;
+                } else {
+                    self.counts.remove(&item);
+                }
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Counter;
+
+    #[test]
+    fn counts() {
+        let mut counter: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(counter.count(&'a'), 5);
+        assert_eq!(counter.count(&'z'), 0);
+        assert_eq!((counter.len(), counter.total()), (5, 11));
+        assert_eq!(
+            counter.most_common(3),
+            vec![(&'a', 5), (&'b', 2), (&'r', 2)]
+        );
+        counter.add_n('z', 0);
+        assert_eq!(counter.len(), 5);
+        counter.add('z');
+        assert_eq!(counter.count(&'z'), 1);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a: Counter<u8> = [1, 1, 2].into_iter().collect();
+        let b: Counter<u8> = [1, 2, 2, 3].into_iter().collect();
+        let sum = a.clone() + b.clone();
+        assert_eq!(sum, [1, 1, 1, 2, 2, 2, 3].into_iter().collect());
+        assert_eq!(a.clone() - b.clone(), [1].into_iter().collect());
+        assert_eq!(b - a, [2, 3].into_iter().collect());
+    }
+}
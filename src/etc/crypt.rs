@@ -0,0 +1,83 @@
+//! Puzzle inputs stored encrypted with `openssl`, so that they can be published with the code.
+#![allow(dead_code)]
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Environment variable holding the key of the encrypted inputs.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+/// Extension added to the path of an input to get the path of its encrypted version.
+pub const EXTENSION: &str = ".enc";
+
+/// AES-256 with a key derived from the passphrase, read from the environment of `openssl` to keep
+/// it out of the process list.
+const CIPHER: [&str; 5] = [
+    "enc",
+    "-aes-256-cbc",
+    "-pbkdf2",
+    "-pass",
+    "env:AOC_INPUT_KEY",
+];
+
+/// Path of the encrypted version of an input.
+pub fn encrypted_path(path: &str) -> String {
+    format!("{}{}", path, EXTENSION)
+}
+
+/// The key of the encrypted inputs.
+pub fn key() -> Result<String, String> {
+    std::env::var(KEY_VAR).map_err(|_| format!("set {} to the key of the inputs", KEY_VAR))
+}
+
+/// Run `openssl` on some data, with the given key.
+fn openssl(args: &[&str], key: &str, data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut child = Command::new("openssl")
+        .args(CIPHER)
+        .args(args)
+        .env(KEY_VAR, key)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("cannot run openssl: {}", err))?;
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(data).map_err(|err| err.to_string())?;
+    drop(stdin);
+    let output = child.wait_with_output().map_err(|err| err.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or_default().to_owned());
+    }
+    Ok(output.stdout)
+}
+
+/// Encrypt an input into the file at `path`.
+pub fn encrypt(input: &str, path: &str, key: &str) -> Result<(), String> {
+    let encrypted = openssl(&["-salt"], key, input.as_bytes())?;
+    std::fs::write(path, encrypted).map_err(|err| err.to_string())
+}
+
+/// Decrypt the input in the file at `path`.
+pub fn decrypt(path: &str, key: &str) -> Result<String, String> {
+    let encrypted = std::fs::read(path).map_err(|err| err.to_string())?;
+    let input = openssl(&["-d"], key, &encrypted)
+        .map_err(|err| format!("wrong key or corrupted file: {}", err))?;
+    String::from_utf8(input).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decrypt, encrypt, encrypted_path};
+
+    #[test]
+    fn roundtrip() {
+        let path = std::env::temp_dir().join("aoc24-rust-day01.txt");
+        let path = encrypted_path(path.to_str().unwrap());
+        let input = "3   4\n4   3\n";
+        encrypt(input, &path, "secret").unwrap();
+        assert_ne!(std::fs::read(&path).unwrap(), input.as_bytes());
+        assert_eq!(decrypt(&path, "secret").as_deref(), Ok(input));
+        assert!(decrypt(&path, "guess").is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,107 @@
+//! Disjoint sets of elements identified by their index, merged with a union-find.
+#![allow(dead_code)]
+
+/// Disjoint sets of the elements `0..len`, merged by rank, with path compression.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    /// upper bound of the height of the tree of each root
+    rank: Vec<u8>,
+    /// number of disjoint sets
+    sets: usize,
+}
+
+impl UnionFind {
+    /// Every element in its own set.
+    pub fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            sets: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of disjoint sets.
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// The representative of the set of an element.
+    pub fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // point every element of the path to the root
+        let mut i = i;
+        while self.parent[i] != root {
+            let next = self.parent[i];
+            self.parent[i] = root;
+            i = next;
+        }
+        root
+    }
+
+    /// Merge the sets of two elements, return `false` if they were already in the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        // the shortest tree goes under the root of the other one
+        let (child, root) = if self.rank[a] < self.rank[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[child] = root;
+        if self.rank[child] == self.rank[root] {
+            self.rank[root] += 1;
+        }
+        self.sets -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UnionFind;
+
+    #[test]
+    fn sets() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!((sets.len(), sets.set_count()), (6, 6));
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+        assert!(sets.same_set(0, 3));
+        assert!(!sets.same_set(0, 4));
+        assert_eq!(sets.set_count(), 3);
+        assert_eq!(sets.find(1), sets.find(2));
+    }
+
+    #[test]
+    fn long_chain() {
+        let mut sets = UnionFind::new(10_000);
+        for i in 1..sets.len() {
+            sets.union(i - 1, i);
+        }
+        assert_eq!(sets.set_count(), 1);
+        assert!(sets.same_set(0, 9_999));
+        // union by rank keeps the trees flat
+        assert!(sets.rank.iter().all(|&rank| rank <= 14));
+    }
+}
@@ -0,0 +1,30 @@
+//! Puzzle inputs baked into the binary by the build script, with the `embed-inputs` feature.
+#![allow(dead_code)]
+use crate::days::DEFAULT_YEAR;
+
+include!(concat!(env!("OUT_DIR"), "/inputs.rs"));
+
+/// The embedded input of a day, from `<input directory>/<year>/dayNN.txt`, or from
+/// `<input directory>/dayNN.txt` for the default year.
+pub fn get(year: u16, day: u8) -> Option<&'static str> {
+    let find = |wanted: Option<u16>| {
+        INPUTS
+            .iter()
+            .find(|(other, other_day, _)| (*other, *other_day) == (wanted, day))
+            .map(|(_, _, input)| *input)
+    };
+    find(Some(year)).or_else(|| find(None).filter(|_| year == DEFAULT_YEAR))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{INPUTS, get};
+
+    #[test]
+    fn lookup() {
+        assert_eq!(get(2024, 26), None);
+        for (year, day, input) in INPUTS {
+            assert_eq!(get(year.unwrap_or(2024), *day), Some(*input));
+        }
+    }
+}
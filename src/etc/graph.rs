@@ -0,0 +1,342 @@
+//! Directed graphs stored as adjacency lists.
+#![allow(dead_code)]
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, VecDeque};
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+
+pub type NodeId = usize;
+pub type EdgeId = usize;
+
+/// Directed graph with weights `N` on nodes and `E` on edges.
+///
+/// Nodes and edges are identified by their insertion order.
+#[derive(Debug, Clone)]
+pub struct AdjGraph<N, E = ()> {
+    nodes: Vec<N>,
+    /// `(from, to, weight)`
+    edges: Vec<(NodeId, NodeId, E)>,
+    /// outgoing edges of each node
+    adjacency: Vec<Vec<EdgeId>>,
+}
+
+impl<N, E> Default for AdjGraph<N, E> {
+    fn default() -> Self {
+        AdjGraph {
+            nodes: vec![],
+            edges: vec![],
+            adjacency: vec![],
+        }
+    }
+}
+
+impl<N, E> AdjGraph<N, E> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn add_node(&mut self, weight: N) -> NodeId {
+        self.nodes.push(weight);
+        self.adjacency.push(vec![]);
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: E) -> EdgeId {
+        self.edges.push((from, to, weight));
+        self.adjacency[from].push(self.edges.len() - 1);
+        self.edges.len() - 1
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn node_ids(&self) -> std::ops::Range<NodeId> {
+        0..self.nodes.len()
+    }
+
+    pub fn edge_ids(&self) -> std::ops::Range<EdgeId> {
+        0..self.edges.len()
+    }
+
+    /// Return `(from, to, weight)` of an edge.
+    pub fn edge(&self, id: EdgeId) -> (NodeId, NodeId, &E) {
+        let (from, to, weight) = &self.edges[id];
+        (*from, *to, weight)
+    }
+
+    /// Outgoing edges of a node, as `(to, weight)`.
+    pub fn neighbours(&self, from: NodeId) -> impl Iterator<Item = (NodeId, &E)> {
+        self.adjacency[from].iter().map(|id| {
+            let (_, to, weight) = &self.edges[*id];
+            (*to, weight)
+        })
+    }
+
+    /// Number of edges from `start` to every node, `None` for unreachable nodes.
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<u64>> {
+        let mut distances = vec![None; self.nodes.len()];
+        let mut worklist: VecDeque<NodeId> = Default::default();
+        distances[start] = Some(0);
+        worklist.push_back(start);
+        while let Some(from) = worklist.pop_front() {
+            let dist = distances[from].unwrap();
+            for (to, _) in self.neighbours(from) {
+                if distances[to].is_none() {
+                    distances[to] = Some(dist + 1);
+                    worklist.push_back(to);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Shortest distances from `start` with the given edge costs, `None` for unreachable nodes.
+    ///
+    /// When a `goal` is given, the search stops once its distance is known, other distances may
+    /// then be missing.
+    pub fn dijkstra<C>(&self, start: NodeId, goal: Option<NodeId>, cost: C) -> Vec<Option<u64>>
+    where
+        C: Fn(&E) -> u64,
+    {
+        let mut distances = vec![None; self.nodes.len()];
+        let mut done = vec![false; self.nodes.len()];
+        let mut worklist = BinaryHeap::new();
+        distances[start] = Some(0);
+        worklist.push(Reverse((0, start)));
+        while let Some(Reverse((dist, from))) = worklist.pop() {
+            if done[from] {
+                continue;
+            }
+            done[from] = true;
+            if goal == Some(from) {
+                break;
+            }
+            for (to, weight) in self.neighbours(from) {
+                let to_dist = dist + cost(weight);
+                if distances[to].is_none_or(|d| to_dist < d) {
+                    distances[to] = Some(to_dist);
+                    worklist.push(Reverse((to_dist, to)));
+                }
+            }
+        }
+        distances
+    }
+
+    /// All maximal cliques, considering edges as undirected (Bron–Kerbosch with pivoting).
+    pub fn maximal_cliques(&self) -> Vec<Vec<NodeId>> {
+        let mut neighbours: Vec<BTreeSet<NodeId>> = vec![Default::default(); self.nodes.len()];
+        for (from, to, _) in &self.edges {
+            if from != to {
+                neighbours[*from].insert(*to);
+                neighbours[*to].insert(*from);
+            }
+        }
+        let mut cliques = vec![];
+        bron_kerbosch(
+            &neighbours,
+            &mut vec![],
+            self.node_ids().collect(),
+            Default::default(),
+            &mut cliques,
+        );
+        cliques
+    }
+}
+
+fn bron_kerbosch(
+    neighbours: &[BTreeSet<NodeId>],
+    clique: &mut Vec<NodeId>,
+    mut candidates: BTreeSet<NodeId>,
+    mut excluded: BTreeSet<NodeId>,
+    cliques: &mut Vec<Vec<NodeId>>,
+) {
+    let Some(pivot) = candidates
+        .union(&excluded)
+        .max_by_key(|n| neighbours[**n].len())
+    else {
+        cliques.push(clique.clone());
+        return;
+    };
+    let others: Vec<NodeId> = candidates
+        .difference(&neighbours[*pivot])
+        .copied()
+        .collect();
+    for node in others {
+        clique.push(node);
+        bron_kerbosch(
+            neighbours,
+            clique,
+            candidates
+                .intersection(&neighbours[node])
+                .copied()
+                .collect(),
+            excluded.intersection(&neighbours[node]).copied().collect(),
+            cliques,
+        );
+        clique.pop();
+        candidates.remove(&node);
+        excluded.insert(node);
+    }
+}
+
+impl<N, E> std::ops::Index<NodeId> for AdjGraph<N, E> {
+    type Output = N;
+
+    fn index(&self, id: NodeId) -> &N {
+        &self.nodes[id]
+    }
+}
+
+/// Error of a topological sort of a graph with a cycle, see [`topo_sort`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N> {
+    /// the nodes that could not be sorted, on a cycle or after one
+    pub unsorted: Vec<N>,
+}
+
+impl<N: Debug> Display for CycleError<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cycle among {:?}", self.unsorted)
+    }
+}
+
+impl<N: Debug> std::error::Error for CycleError<N> {}
+
+/// Sort nodes so that every edge `(from, to)` has `from` before `to`.
+///
+/// Nodes only appearing in edges are sorted too. Among the nodes whose predecessors are all
+/// sorted, the first one to appear, in `nodes` then in `edges`, comes first.
+pub fn topo_sort<N, I, J>(nodes: I, edges: J) -> Result<Vec<N>, CycleError<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    J: IntoIterator<Item = (N, N)>,
+{
+    let mut ids: HashMap<N, usize> = HashMap::new();
+    let mut order: Vec<N> = vec![];
+    let mut id = |node: N, order: &mut Vec<N>| {
+        *ids.entry(node.clone()).or_insert_with(|| {
+            order.push(node);
+            order.len() - 1
+        })
+    };
+    for node in nodes {
+        id(node, &mut order);
+    }
+    let mut successors: Vec<Vec<usize>> = vec![];
+    let mut incoming: Vec<usize> = vec![];
+    for (from, to) in edges {
+        let (from, to) = (id(from, &mut order), id(to, &mut order));
+        successors.resize(order.len(), vec![]);
+        incoming.resize(order.len(), 0);
+        successors[from].push(to);
+        incoming[to] += 1;
+    }
+    successors.resize(order.len(), vec![]);
+    incoming.resize(order.len(), 0);
+
+    // Kahn's algorithm, taking ready nodes in order
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..order.len())
+        .filter(|&node| incoming[node] == 0)
+        .map(Reverse)
+        .collect();
+    let mut sorted = Vec::with_capacity(order.len());
+    while let Some(Reverse(node)) = ready.pop() {
+        sorted.push(node);
+        for &next in &successors[node] {
+            incoming[next] -= 1;
+            if incoming[next] == 0 {
+                ready.push(Reverse(next));
+            }
+        }
+    }
+    if sorted.len() < order.len() {
+        let unsorted = (0..order.len())
+            .filter(|&node| incoming[node] > 0)
+            .map(|node| order[node].clone())
+            .collect();
+        return Err(CycleError { unsorted });
+    }
+    Ok(sorted.into_iter().map(|node| order[node].clone()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AdjGraph, CycleError, topo_sort};
+
+    /// ```text
+    /// a --1--> b --1--> c
+    ///  \               ^
+    ///   -------5-------/
+    /// ```
+    fn weighted() -> AdjGraph<char, u64> {
+        let mut g = AdjGraph::new();
+        let a = g.add_node('a');
+        let b = g.add_node('b');
+        let c = g.add_node('c');
+        g.add_node('d');
+        g.add_edge(a, b, 1);
+        g.add_edge(b, c, 1);
+        g.add_edge(a, c, 5);
+        g
+    }
+
+    #[test]
+    fn structure() {
+        let g = weighted();
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g[2], 'c');
+        assert_eq!(g.edge(2), (0, 2, &5));
+        assert_eq!(g.neighbours(0).collect::<Vec<_>>(), vec![(1, &1), (2, &5)]);
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let g = weighted();
+        assert_eq!(g.bfs(0), vec![Some(0), Some(1), Some(1), None]);
+        assert_eq!(g.bfs(1), vec![None, Some(0), Some(1), None]);
+        assert_eq!(
+            g.dijkstra(0, None, |w| *w),
+            vec![Some(0), Some(1), Some(2), None]
+        );
+        assert_eq!(g.dijkstra(0, Some(1), |w| *w)[1], Some(1));
+    }
+
+    #[test]
+    fn cliques() {
+        // two triangles sharing the edge 1-2, and a lone node
+        let mut g = AdjGraph::<(), ()>::new();
+        for _ in 0..5 {
+            g.add_node(());
+        }
+        for (a, b) in [(0, 1), (0, 2), (1, 2), (3, 1), (3, 2)] {
+            g.add_edge(a, b, ());
+        }
+        let mut cliques = g.maximal_cliques();
+        cliques.iter_mut().for_each(|c| c.sort());
+        cliques.sort();
+        assert_eq!(cliques, vec![vec![0, 1, 2], vec![1, 2, 3], vec![4]]);
+    }
+
+    #[test]
+    fn topological_order() {
+        let edges = [('a', 'c'), ('b', 'c'), ('c', 'd'), ('e', 'b')];
+        assert_eq!(
+            topo_sort(['d', 'c', 'b', 'a'], edges),
+            Ok(vec!['a', 'e', 'b', 'c', 'd'])
+        );
+        assert_eq!(topo_sort(1..4, []), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            topo_sort([0], [(1, 2), (2, 3), (3, 1), (3, 4)]),
+            Err(CycleError {
+                unsorted: vec![1, 2, 3, 4]
+            })
+        );
+    }
+}
@@ -1,12 +1,16 @@
 //! 2D grid stuff.
 #![allow(dead_code)]
-use std::ops::Add;
+use crate::etc::math;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Index, IndexMut};
+use std::str::FromStr;
 
 /// A 2D grid, where coordinates are expressed as a couple `(line, column)`.
 ///
 /// The origin `(0,0)` is the top-left-most item.
 /// The bottom-right-most item is at coordinates (height-1, width-1).
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct Grid<T = char> {
     pub lines: usize,
     pub columns: usize,
@@ -42,10 +46,46 @@ impl Point {
         Self(-self.0, -self.1)
     }
 
+    /// Euclidean remainder of each coordinate, always in `0..limit`.
+    pub fn rem_euclid(&self, limit: Point) -> Self {
+        Self(self.0.rem_euclid(limit.0), self.1.rem_euclid(limit.1))
+    }
+
     /// Return the taxicab distance to the other point.
     pub fn taxicab_distance(&self, other: &Self) -> u64 {
         self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
     }
+
+    /// Return the Chebyshev distance to the other point, the number of king moves to reach it.
+    pub fn chebyshev_distance(&self, other: &Self) -> u64 {
+        self.0.abs_diff(other.0).max(self.1.abs_diff(other.1))
+    }
+
+    /// Sign of each coordinate, e.g. the direction of an axis-aligned or diagonal vector.
+    pub fn signum(&self) -> Self {
+        Self(self.0.signum(), self.1.signum())
+    }
+
+    /// The shortest vector with integer coordinates in the same direction, the identity staying
+    /// the identity.
+    pub fn reduced(&self) -> Self {
+        let gcd = math::gcd(self.0, self.1).max(1);
+        Self(self.0 / gcd, self.1 / gcd)
+    }
+
+    /// The points with integer coordinates on the segment to the other point, both included.
+    ///
+    /// These are all the cells of axis-aligned and diagonal segments.
+    pub fn line_to(&self, other: &Self) -> impl Iterator<Item = Point> + use<> {
+        let (start, delta) = (*self, *other - *self);
+        let step = delta.reduced();
+        let count = if step.is_identity() {
+            0
+        } else {
+            delta.chebyshev_distance(&Point(0, 0)) / step.chebyshev_distance(&Point(0, 0))
+        };
+        (0..=count as i64).map(move |i| start + step * i)
+    }
 }
 
 impl<T: std::fmt::Debug> std::fmt::Debug for Grid<T> {
@@ -60,6 +100,35 @@ impl<T: std::fmt::Debug> std::fmt::Debug for Grid<T> {
     }
 }
 
+/// One line of text per grid line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for line in self.items.chunks(self.columns.max(1)) {
+            for cell in line {
+                cell.fmt(f)?;
+            }
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// The cell at a position, panics if the position is out of the grid.
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        &self.items[self.strict_index(&pos)]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut T {
+        let index = self.strict_index(&pos);
+        &mut self.items[index]
+    }
+}
+
 impl From<(i32, i32)> for Point {
     fn from(value: (i32, i32)) -> Self {
         Self(value.0.into(), value.1.into())
@@ -139,25 +208,270 @@ pub const ALL_DIRECTIONS: [Point; 8] = [
     Point(-1, 1),
 ];
 
+/// Which cells are the neighbours of a cell, see [`Grid::neighbours`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topology {
+    /// The four cells sharing a side with the cell.
+    Orthogonal,
+    /// The four cells sharing only a corner with the cell.
+    Diagonal,
+    /// The eight surrounding cells.
+    All,
+}
+
+impl Topology {
+    /// The direction vectors to the neighbours, clockwise.
+    pub fn directions(&self) -> &'static [Point] {
+        match self {
+            Topology::Orthogonal => &[Point::NORTH, Point::EAST, Point::SOUTH, Point::WEST],
+            Topology::Diagonal => &[
+                Point::NORTH_EAST,
+                Point::SOUTH_EAST,
+                Point::SOUTH_WEST,
+                Point::NORTH_WEST,
+            ],
+            Topology::All => &[
+                Point::NORTH,
+                Point::NORTH_EAST,
+                Point::EAST,
+                Point::SOUTH_EAST,
+                Point::SOUTH,
+                Point::SOUTH_WEST,
+                Point::WEST,
+                Point::NORTH_WEST,
+            ],
+        }
+    }
+}
+
+/// One of the four cardinal directions, for states that need a small copyable direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// The four directions clockwise from north, in the order of their [`Direction::index`].
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The unit vector of the direction.
+    pub fn to_point(self) -> Point {
+        match self {
+            Direction::North => Point::NORTH,
+            Direction::East => Point::EAST,
+            Direction::South => Point::SOUTH,
+            Direction::West => Point::WEST,
+        }
+    }
+
+    pub fn rotate_cw(self) -> Self {
+        Self::ALL[(self.index() + 1) % 4]
+    }
+
+    pub fn rotate_ccw(self) -> Self {
+        Self::ALL[(self.index() + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self.index() + 2) % 4]
+    }
+
+    /// The direction of an arrow among `^`, `>`, `v` and `<`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::North),
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// The arrow of the direction, see [`Direction::from_char`].
+    pub fn to_char(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+
+    /// Index of the direction in `0..4`, e.g. to store per direction states in an array.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(direction: Direction) -> Self {
+        direction.to_point()
+    }
+}
+
+/// Error of a grid read from text, see [`Grid::try_new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridParseError {
+    /// No line at all.
+    Empty,
+    /// A line whose length differs from the length of the first line.
+    Ragged {
+        line: usize,
+        length: usize,
+        expected: usize,
+    },
+}
+
+impl Display for GridParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::Empty => write!(f, "empty grid"),
+            GridParseError::Ragged {
+                line,
+                length,
+                expected,
+            } => write!(
+                f,
+                "line {} of the grid has {} cells instead of {}",
+                line + 1,
+                length,
+                expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
 impl Grid<char> {
     /// Read a grid from the given string, lines are separated by ascii whitespace.
+    ///
+    /// Panics on an empty or ragged grid, see [`Grid::try_new`].
     pub fn new(input: &str) -> Self {
-        let lines = input.split_ascii_whitespace().collect::<Vec<_>>();
-        let height = lines.len();
-        let width = lines.first().unwrap().len();
-        let items = lines
-            .iter()
-            .flat_map(|&line| line.chars().collect::<Vec<_>>())
-            .collect();
-        Grid {
-            lines: height,
-            columns: width,
-            items,
-        }
+        Self::try_new(input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Read a grid from the given string, lines are separated by ascii whitespace, checking that
+    /// there is at least one line and that all lines have the same length.
+    pub fn try_new(input: &str) -> Result<Self, GridParseError> {
+        Self::from_rows(input.split_ascii_whitespace().map(str::chars))
+    }
+
+    /// Whether `word` is read from `origin` by applying `step` between its letters.
+    pub fn has_word(&self, origin: &Point, step: &Point, word: &str) -> bool {
+        word.chars()
+            .enumerate()
+            .all(|(i, c)| self.get(&(*origin + *step * i as i64)) == Some(&c))
+    }
+
+    /// Number of times `word` is read in the grid, starting anywhere and following any of the
+    /// `directions`.
+    pub fn count_word(&self, word: &str, directions: &[Point]) -> usize {
+        self.positions()
+            .map(|origin| {
+                directions
+                    .iter()
+                    .filter(|step| self.has_word(&origin, step, word))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Number of positions where `word`, of odd length, is read along both diagonals centred on
+    /// the position, in either direction along each diagonal.
+    pub fn count_cross(&self, word: &str) -> usize {
+        let len = word.chars().count();
+        assert!(!len.is_multiple_of(2), "crossed words have a middle letter");
+        let half = (len / 2) as i64;
+        let reads = |center: Point, step: Point| {
+            self.has_word(&(center - step * half), &step, word)
+                || self.has_word(&(center + step * half), &(step * -1), word)
+        };
+        self.positions()
+            .filter(|&center| reads(center, Point::SOUTH_EAST) && reads(center, Point::SOUTH_WEST))
+            .count()
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridParseError;
+
+    fn from_str(input: &str) -> Result<Self, GridParseError> {
+        Self::try_new(input)
     }
 }
 
+/// Read a maze, whose cells are walls (`true`) or free (`false`).
+///
+/// Characters other than `wall` and `.` are markers on free cells, returned with their position.
+pub fn parse_maze(
+    input: &str,
+    wall: char,
+) -> Result<(Grid<bool>, HashMap<char, Point>), GridParseError> {
+    let grid = Grid::try_new(input)?;
+    let markers = grid
+        .iter_with_position()
+        .filter(|(_, c)| **c != wall && **c != '.')
+        .map(|(pos, &c)| (c, pos))
+        .collect();
+    Ok((grid.new_from(|&c| c == wall), markers))
+}
+
 impl<T> Grid<T> {
+    /// Build a grid from its lines, checking that there is at least one line and that all lines
+    /// have the same length.
+    pub fn from_rows<R, I>(rows: R) -> Result<Self, GridParseError>
+    where
+        R: IntoIterator<Item = I>,
+        I: IntoIterator<Item = T>,
+    {
+        let mut items = Vec::new();
+        let mut lines = 0;
+        let mut columns = None;
+        for row in rows {
+            let before = items.len();
+            items.extend(row);
+            let length = items.len() - before;
+            let expected = *columns.get_or_insert(length);
+            if length != expected {
+                return Err(GridParseError::Ragged {
+                    line: lines,
+                    length,
+                    expected,
+                });
+            }
+            lines += 1;
+        }
+        Ok(Grid {
+            lines,
+            columns: columns.ok_or(GridParseError::Empty)?,
+            items,
+        })
+    }
+
+    /// Build a grid of the given size, the cell at each position being given by `f`, line by
+    /// line.
+    pub fn from_fn<F>(lines: usize, columns: usize, f: F) -> Self
+    where
+        F: FnMut(Point) -> T,
+    {
+        let mut grid = Grid {
+            lines,
+            columns,
+            items: Vec::new(),
+        };
+        grid.items = grid.positions().map(f).collect();
+        grid
+    }
+
     pub fn iter(&self) -> std::slice::Iter<'_, T> {
         self.items.iter()
     }
@@ -166,10 +480,6 @@ impl<T> Grid<T> {
         pos.0 >= 0 && (pos.0 as usize) < self.lines && pos.1 >= 0 && (pos.1 as usize) < self.columns
     }
 
-    pub fn valid_coordinates(&self, line: usize, column: usize) -> bool {
-        line < self.lines && column < self.columns
-    }
-
     pub fn valid_index(&self, index: usize) -> bool {
         index < self.items.len()
     }
@@ -220,16 +530,6 @@ impl<T> Grid<T> {
         }
     }
 
-    /// Retrieve value at given line and column coordinates.
-    pub fn at(&self, line: usize, column: usize) -> Option<&T> {
-        if self.valid_coordinates(line, column) {
-            let index = line * self.columns + column;
-            self.items.get(index)
-        } else {
-            None
-        }
-    }
-
     /// Retrieve value at given point.
     pub fn get(&self, pos: &Point) -> Option<&T> {
         self.checked_index(pos)
@@ -273,14 +573,23 @@ impl<T> Grid<T> {
         self.items.iter().find(|&x| predicate(x))
     }
 
+    /// Iterate over the positions of the grid, line by line.
+    pub fn positions(&self) -> impl Iterator<Item = Point> + use<T> {
+        let columns = self.columns;
+        (0..self.size()).map(move |index| Point((index / columns) as i64, (index % columns) as i64))
+    }
+
+    /// Iterate over the cells of the grid with their position, line by line.
+    pub fn iter_with_position(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(self.items.iter())
+    }
+
     pub fn for_each_with_position<F>(&self, mut f: F)
     where
         F: FnMut(Point, &T),
     {
-        self.items
-            .iter()
-            .enumerate()
-            .for_each(|(index, item)| f(self.unchecked_position(index), item));
+        self.iter_with_position()
+            .for_each(|(pos, item)| f(pos, item));
     }
 
     pub fn for_each_with_index<F>(&self, mut f: F)
@@ -293,23 +602,168 @@ impl<T> Grid<T> {
             .for_each(|(index, item)| f(index, item));
     }
 
+    /// Iterate over the cells of a line, from left to right, with their position.
+    ///
+    /// Panics if the line is out of the grid.
+    pub fn iter_row(&self, line: usize) -> impl Iterator<Item = (Point, &T)> {
+        assert!(line < self.lines, "invalid line");
+        self.items[line * self.columns..(line + 1) * self.columns]
+            .iter()
+            .enumerate()
+            .map(move |(column, item)| (Point(line as i64, column as i64), item))
+    }
+
+    /// Iterate over the cells of a column, from top to bottom, with their position.
+    ///
+    /// Panics if the column is out of the grid.
+    pub fn iter_col(&self, column: usize) -> impl Iterator<Item = (Point, &T)> {
+        assert!(column < self.columns, "invalid column");
+        self.items[column..]
+            .iter()
+            .step_by(self.columns)
+            .enumerate()
+            .map(move |(line, item)| (Point(line as i64, column as i64), item))
+    }
+
+    /// Iterate over the lines of the grid, from top to bottom, see [`Grid::iter_row`].
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = (Point, &T)>> {
+        (0..self.lines).map(|line| self.iter_row(line))
+    }
+
+    /// Iterate over the columns of the grid, from left to right, see [`Grid::iter_col`].
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = (Point, &T)>> {
+        (0..self.columns).map(|column| self.iter_col(column))
+    }
+
+    /// Draw the grid as text, one line per grid line, with the character of each cell.
+    pub fn render_with<F>(&self, glyph: F) -> String
+    where
+        F: Fn(&T) -> char,
+    {
+        let mut out = String::with_capacity(self.size() + self.lines);
+        for line in self.items.chunks(self.columns.max(1)) {
+            out.extend(line.iter().map(&glyph));
+            out.push('\n');
+        }
+        out
+    }
+
     pub fn step(&self, origin: &Point, delta: &Point) -> Option<Point> {
         let point = origin.add(*delta);
         self.valid_position(&point).then_some(point)
     }
 
+    /// The position inside the grid of any point, the grid wrapping around its edges like a
+    /// torus.
+    pub fn wrap(&self, pos: &Point) -> Point {
+        pos.rem_euclid(Point(self.lines as i64, self.columns as i64))
+    }
+
+    /// Step from the origin, wrapping around the edges of the grid, see [`Grid::wrap`].
+    pub fn step_wrapping(&self, origin: &Point, delta: &Point) -> Point {
+        self.wrap(&origin.add(*delta))
+    }
+
+    /// Retrieve the value at any point, wrapping around the edges of the grid.
+    pub fn get_wrapping(&self, pos: &Point) -> &T {
+        self.unchecked_get(&self.wrap(pos))
+    }
+
+    /// Iterate over the positions whose cells differ between the two grids, line by line,
+    /// positions inside only one of the grids included.
+    pub fn diff<'a>(&'a self, other: &'a Grid<T>) -> impl Iterator<Item = Point> + 'a
+    where
+        T: PartialEq,
+    {
+        let columns = self.columns.max(other.columns);
+        (0..self.lines.max(other.lines) * columns)
+            .map(move |index| Point((index / columns) as i64, (index % columns) as i64))
+            .filter(|pos| self.get(pos) != other.get(pos))
+    }
+
+    /// Iterate over the neighbours of a position inside the grid, with their position.
+    pub fn neighbours<'a>(
+        &'a self,
+        origin: &Point,
+        topology: Topology,
+    ) -> impl Iterator<Item = (Point, &'a T)> + use<'a, T> {
+        let origin = *origin;
+        topology
+            .directions()
+            .iter()
+            .filter_map(move |delta| self.step(&origin, delta))
+            .map(|pos| (pos, self.unchecked_get(&pos)))
+    }
+
     pub fn for_each_neighbour<F>(&self, origin: &Point, mut f: F)
     where
         F: FnMut(Point, &T),
     {
-        for delta in &[Point::NORTH, Point::EAST, Point::SOUTH, Point::WEST] {
-            if let Some(pos) = self.step(origin, delta) {
-                f(pos, self.unchecked_get(&pos));
+        self.neighbours(origin, Topology::Orthogonal)
+            .for_each(|(pos, item)| f(pos, item));
+    }
+
+    /// The positions of the region of `start`, in breadth-first order: the positions reached
+    /// through orthogonal neighbours in the same region, `same_region` telling whether two
+    /// neighbouring items are.
+    pub fn flood_fill<F>(&self, start: &Point, same_region: F) -> Vec<Point>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let mut seen = vec![false; self.size()];
+        self.fill(start, &same_region, &mut seen)
+    }
+
+    /// Label every position with the region it belongs to, see [`Grid::flood_fill`], regions
+    /// being numbered from 0 in the order of their first position, line by line.
+    pub fn label_regions<F>(&self, same_region: F) -> Grid<RegionId>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let mut labels = Grid {
+            lines: self.lines,
+            columns: self.columns,
+            items: vec![0; self.size()],
+        };
+        let mut seen = vec![false; self.size()];
+        let mut regions = 0;
+        for index in 0..self.size() {
+            if !seen[index] {
+                let start = self.unchecked_position(index);
+                for pos in self.fill(&start, &same_region, &mut seen) {
+                    labels[pos] = regions;
+                }
+                regions += 1;
             }
         }
+        labels
+    }
+
+    /// Breadth-first flood fill from `start`, skipping the positions already `seen`.
+    fn fill<F>(&self, start: &Point, same_region: &F, seen: &mut [bool]) -> Vec<Point>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let mut region = vec![];
+        let mut queue = VecDeque::from([*start]);
+        seen[self.unchecked_index(start)] = true;
+        while let Some(pos) = queue.pop_front() {
+            region.push(pos);
+            for (next, item) in self.neighbours(&pos, Topology::Orthogonal) {
+                let index = self.unchecked_index(&next);
+                if !seen[index] && same_region(&self[pos], item) {
+                    seen[index] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        region
     }
 }
 
+/// Identifier of a region of a grid, see [`Grid::label_regions`].
+pub type RegionId = usize;
+
 impl<T> Grid<T>
 where
     T: Default + Clone,
@@ -401,9 +855,154 @@ where
     }
 }
 
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// A new grid of the given size, whose cell `(line, column)` is the cell of this grid at the
+    /// coordinates returned by `source`.
+    fn remap<F>(&self, lines: usize, columns: usize, source: F) -> Self
+    where
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let mut items = Vec::with_capacity(self.size());
+        for line in 0..lines {
+            for column in 0..columns {
+                let (l, c) = source(line, column);
+                items.push(self.items[l * self.columns + c].clone());
+            }
+        }
+        Grid {
+            lines,
+            columns,
+            items,
+        }
+    }
+
+    /// Swap lines and columns, mirroring the grid along its top-left to bottom-right diagonal.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.columns, self.lines, |line, column| (column, line))
+    }
+
+    /// Widen the grid `N` times, each cell being replaced by the `N` cells returned by `f`.
+    pub fn scale_horizontal<const N: usize, F>(&self, f: F) -> Self
+    where
+        F: Fn(&T) -> [T; N],
+    {
+        Grid {
+            lines: self.lines,
+            columns: self.columns * N,
+            items: self.items.iter().flat_map(f).collect(),
+        }
+    }
+
+    /// Rotate the grid a quarter turn clockwise, the first column becomes the first line.
+    pub fn rotate_cw(&self) -> Self {
+        let lines = self.lines;
+        self.remap(self.columns, self.lines, |line, column| {
+            (lines - 1 - column, line)
+        })
+    }
+
+    /// Rotate the grid a quarter turn counterclockwise, the first line becomes the first column.
+    pub fn rotate_ccw(&self) -> Self {
+        let columns = self.columns;
+        self.remap(self.columns, self.lines, |line, column| {
+            (column, columns - 1 - line)
+        })
+    }
+
+    /// Mirror the grid left to right, the first column becomes the last one.
+    pub fn flip_horizontal(&self) -> Self {
+        let columns = self.columns;
+        self.remap(self.lines, self.columns, |line, column| {
+            (line, columns - 1 - column)
+        })
+    }
+
+    /// Mirror the grid top to bottom, the first line becomes the last one.
+    pub fn flip_vertical(&self) -> Self {
+        let lines = self.lines;
+        self.remap(self.lines, self.columns, |line, column| {
+            (lines - 1 - line, column)
+        })
+    }
+}
+
+/// A grid of booleans packed in bits, with `planes` independent booleans per cell, e.g. one per
+/// direction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitGrid {
+    pub lines: usize,
+    pub columns: usize,
+    pub planes: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// A grid of the given size, with every bit cleared.
+    pub fn new(lines: usize, columns: usize, planes: usize) -> Self {
+        BitGrid {
+            lines,
+            columns,
+            planes,
+            words: vec![0; (lines * columns * planes).div_ceil(64)],
+        }
+    }
+
+    /// Word and mask of the bit of a plane of a cell, the planes of a cell being contiguous.
+    fn bit(&self, pos: &Point, plane: usize) -> (usize, u64) {
+        debug_assert!(plane < self.planes, "invalid plane");
+        debug_assert!(
+            pos.0 >= 0
+                && (pos.0 as usize) < self.lines
+                && pos.1 >= 0
+                && (pos.1 as usize) < self.columns,
+            "invalid position"
+        );
+        let bit = ((pos.0 as usize) * self.columns + pos.1 as usize) * self.planes + plane;
+        (bit / 64, 1 << (bit % 64))
+    }
+
+    pub fn test(&self, pos: &Point, plane: usize) -> bool {
+        let (word, mask) = self.bit(pos, plane);
+        self.words[word] & mask != 0
+    }
+
+    /// Set a bit, returning whether it was already set.
+    pub fn set(&mut self, pos: &Point, plane: usize) -> bool {
+        let (word, mask) = self.bit(pos, plane);
+        let was_set = self.words[word] & mask != 0;
+        self.words[word] |= mask;
+        was_set
+    }
+
+    pub fn clear(&mut self, pos: &Point, plane: usize) {
+        let (word, mask) = self.bit(pos, plane);
+        self.words[word] &= !mask;
+    }
+
+    /// Whether any plane of a cell is set.
+    pub fn any(&self, pos: &Point) -> bool {
+        (0..self.planes).any(|plane| self.test(pos, plane))
+    }
+
+    /// Clear every bit, keeping the allocation to reuse the grid.
+    pub fn clear_all(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Iterate over the positions of the cells with any plane set, line by line.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        (0..self.lines * self.columns)
+            .map(|index| Point((index / self.columns) as i64, (index % self.columns) as i64))
+            .filter(|pos| self.any(pos))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Grid, Point};
+    use super::{BitGrid, Direction, Grid, GridParseError, Point, Topology, parse_maze};
     #[test]
     fn rotate_90_clockwise() {
         assert_eq!(Point::NORTH.rotate_90_clockwise(), Point::EAST);
@@ -428,6 +1027,52 @@ mod tests {
         assert_eq!(Point::WEST.rotate_180(), Point::EAST);
     }
 
+    #[test]
+    fn directions() {
+        for (index, direction) in Direction::ALL.into_iter().enumerate() {
+            assert_eq!(direction.index(), index);
+            assert_eq!(
+                direction.rotate_cw().to_point(),
+                direction.to_point().rotate_90_clockwise()
+            );
+            assert_eq!(direction.rotate_ccw().rotate_cw(), direction);
+            assert_eq!(Direction::from_char(direction.to_char()), Some(direction));
+            assert_eq!(
+                direction.opposite().to_point(),
+                direction.to_point().rotate_180()
+            );
+        }
+        assert_eq!(Direction::from_char('v'), Some(Direction::South));
+        assert_eq!(Direction::from_char('x'), None);
+        assert_eq!(Point::from(Direction::West), Point::WEST);
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point(1, -2), Point(-3, 4));
+        assert_eq!(a.taxicab_distance(&b), 10);
+        assert_eq!(a.chebyshev_distance(&b), 6);
+        assert_eq!((b - a).signum(), Point::NORTH_EAST);
+        assert_eq!(Point(6, -4).reduced(), Point(3, -2));
+        assert_eq!(Point(0, -5).reduced(), Point::WEST);
+        assert_eq!(Point(0, 0).reduced(), Point(0, 0));
+    }
+
+    #[test]
+    fn line_to() {
+        let line = |a: Point, b: Point| a.line_to(&b).collect::<Vec<_>>();
+        assert_eq!(line(Point(2, 2), Point(2, 2)), vec![Point(2, 2)]);
+        assert_eq!(
+            line(Point(0, 3), Point(0, 0)),
+            vec![Point(0, 3), Point(0, 2), Point(0, 1), Point(0, 0)]
+        );
+        assert_eq!(line(Point(0, 0), Point(2, 2)).len(), 3);
+        assert_eq!(
+            line(Point(0, 0), Point(4, 6)),
+            vec![Point(0, 0), Point(2, 3), Point(4, 6)]
+        );
+    }
+
     #[test]
     fn is_identity() {
         assert!(Point(0, 0).is_identity());
@@ -494,4 +1139,259 @@ mod tests {
         assert_eq!(None, g.position(|v| *v == '0'));
         assert_eq!(Some(Point(1, 3)), g.position(|v| *v == '8'));
     }
+
+    #[test]
+    fn parse() {
+        let g: Grid = "12\n34\n".parse().unwrap();
+        assert_eq!((g.lines, g.columns), (2, 2));
+        assert_eq!(g.items, vec!['1', '2', '3', '4']);
+        assert_eq!(Grid::try_new(" \n\n").err(), Some(GridParseError::Empty));
+        let ragged = Grid::try_new("123\n45\n678\n").unwrap_err();
+        assert_eq!(
+            ragged,
+            GridParseError::Ragged {
+                line: 1,
+                length: 2,
+                expected: 3
+            }
+        );
+        assert_eq!(
+            ragged.to_string(),
+            "line 2 of the grid has 2 cells instead of 3"
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let g = Grid::new("123\n456\n");
+        let row: Vec<_> = g.iter_row(1).collect();
+        assert_eq!(
+            row,
+            vec![
+                (Point(1, 0), &'4'),
+                (Point(1, 1), &'5'),
+                (Point(1, 2), &'6')
+            ]
+        );
+        let col: Vec<_> = g.iter_col(2).map(|(_, c)| *c).collect();
+        assert_eq!(col, vec!['3', '6']);
+        let rows: Vec<String> = g.rows().map(|row| row.map(|(_, c)| c).collect()).collect();
+        assert_eq!(rows, vec!["123", "456"]);
+        let columns: Vec<String> = g
+            .columns()
+            .map(|col| col.map(|(_, c)| c).collect())
+            .collect();
+        assert_eq!(columns, vec!["14", "25", "36"]);
+        assert_eq!(g.iter_col(0).last(), Some((Point(1, 0), &'4')));
+    }
+
+    #[test]
+    fn transformations() {
+        let g = Grid::new("123\n456\n");
+        assert_eq!(g.transpose().to_string(), "14\n25\n36\n");
+        assert_eq!(g.rotate_cw().to_string(), "41\n52\n63\n");
+        assert_eq!(g.rotate_ccw().to_string(), "36\n25\n14\n");
+        assert_eq!(g.flip_horizontal().to_string(), "321\n654\n");
+        assert_eq!(g.flip_vertical().to_string(), "456\n123\n");
+        let back = g.rotate_cw().rotate_cw().rotate_cw().rotate_cw();
+        assert_eq!((back.lines, back.columns), (2, 3));
+        assert_eq!(back.items, g.items);
+        assert_eq!(
+            g.rotate_cw().rotate_cw().items,
+            g.flip_horizontal().flip_vertical().items
+        );
+    }
+
+    #[test]
+    fn index() {
+        let mut g = Grid::new("123\n456\n");
+        assert_eq!(g[Point(1, 2)], '6');
+        g[Point(0, 1)] = 'x';
+        assert_eq!(g.to_string(), "1x3\n456\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid position")]
+    fn index_out_of_bounds() {
+        let g = Grid::new("123\n456\n");
+        let _ = g[Point(2, 0)];
+    }
+
+    #[test]
+    fn positions() {
+        let g = Grid::new("12\n34\n56\n");
+        let positions: Vec<_> = g.positions().collect();
+        assert_eq!(
+            positions,
+            vec![
+                Point(0, 0),
+                Point(0, 1),
+                Point(1, 0),
+                Point(1, 1),
+                Point(2, 0),
+                Point(2, 1)
+            ]
+        );
+        assert_eq!(
+            g.iter_with_position().find(|(_, c)| **c == '4'),
+            Some((Point(1, 1), &'4'))
+        );
+        let odd: String = g
+            .iter_with_position()
+            .filter(|(pos, _)| pos.1 == 0)
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(odd, "135");
+    }
+
+    #[test]
+    fn neighbours() {
+        let g = Grid::new("123\n456\n789\n");
+        let around =
+            |pos, topology| -> String { g.neighbours(&pos, topology).map(|(_, c)| c).collect() };
+        assert_eq!(around(Point(1, 1), Topology::Orthogonal), "2684");
+        assert_eq!(around(Point(1, 1), Topology::Diagonal), "3971");
+        assert_eq!(around(Point(1, 1), Topology::All), "23698741");
+        assert_eq!(around(Point(0, 0), Topology::All), "254");
+        assert_eq!(
+            g.neighbours(&Point(2, 2), Topology::Orthogonal).next(),
+            Some((Point(1, 2), &'6'))
+        );
+    }
+
+    #[test]
+    fn constructors() {
+        let g = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!((g.lines, g.columns), (2, 3));
+        assert_eq!(g[Point(1, 0)], 4);
+        let ragged = Grid::from_rows([vec![1, 2], vec![3]]).err();
+        assert_eq!(
+            ragged,
+            Some(GridParseError::Ragged {
+                line: 1,
+                length: 1,
+                expected: 2
+            })
+        );
+        assert_eq!(
+            Grid::<u8>::from_rows(Vec::<Vec<u8>>::new()).err(),
+            Some(GridParseError::Empty)
+        );
+        let g = Grid::from_fn(2, 3, |pos| pos.0 * 10 + pos.1);
+        assert_eq!(g.items, vec![0, 1, 2, 10, 11, 12]);
+    }
+
+    #[test]
+    fn scale_horizontal() {
+        let g = Grid::new("#.\n.O\n");
+        let wide = g.scale_horizontal(|&c| match c {
+            'O' => ['[', ']'],
+            c => [c, c],
+        });
+        assert_eq!(wide.to_string(), "##..\n..[]\n");
+        assert_eq!(g.scale_horizontal(|&c| [c]).items, g.items);
+    }
+
+    #[test]
+    fn wrapping() {
+        assert_eq!(Point(-1, 7).rem_euclid(Point(3, 5)), Point(2, 2));
+        let g = Grid::new("123\n456\n");
+        assert_eq!(g.wrap(&Point(-1, -1)), Point(1, 2));
+        assert_eq!(
+            g.step_wrapping(&Point(1, 2), &(Point::EAST * 4)),
+            Point(1, 0)
+        );
+        assert_eq!(g.get_wrapping(&Point(2, 3)), &'1');
+        assert_eq!(g.get_wrapping(&Point(-3, -4)), &'6');
+    }
+
+    #[test]
+    fn bit_grid() {
+        let mut bits = BitGrid::new(3, 7, 4);
+        assert!(!bits.set(&Point(2, 6), 3));
+        assert!(bits.set(&Point(2, 6), 3));
+        assert!(!bits.set(&Point(0, 0), 0));
+        assert!(bits.test(&Point(2, 6), 3) && !bits.test(&Point(2, 6), 2));
+        assert!(bits.any(&Point(0, 0)) && !bits.any(&Point(1, 1)));
+        assert_eq!(
+            bits.positions().collect::<Vec<_>>(),
+            vec![Point(0, 0), Point(2, 6)]
+        );
+        bits.clear(&Point(0, 0), 0);
+        assert_eq!(bits.positions().collect::<Vec<_>>(), vec![Point(2, 6)]);
+        bits.clear_all();
+        assert_eq!(bits, BitGrid::new(3, 7, 4));
+    }
+
+    #[test]
+    fn diff() {
+        let g = Grid::new("123\n456\n");
+        assert!(g == g.clone());
+        assert_eq!(g.diff(&g.clone()).count(), 0);
+        let other = Grid::new("1x3\n45y\n");
+        assert!(g != other);
+        assert_eq!(
+            g.diff(&other).collect::<Vec<_>>(),
+            vec![Point(0, 1), Point(1, 2)]
+        );
+        let narrow = Grid::new("12\n45\n78\n");
+        assert_eq!(
+            g.diff(&narrow).collect::<Vec<_>>(),
+            vec![Point(0, 2), Point(1, 2), Point(2, 0), Point(2, 1)]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let g = Grid::new("1234\n5678\n");
+        assert_eq!(g.to_string(), "1234\n5678\n");
+        let digits = g.new_from(|c| c.to_digit(10).unwrap() * 2);
+        assert_eq!(digits.to_string(), "2468\n10121416\n");
+        let even = digits.render_with(|d| if d % 4 == 0 { '#' } else { '.' });
+        assert_eq!(even, ".#.#\n.#.#\n");
+    }
+
+    #[test]
+    fn regions() {
+        let g = Grid::new("AAB\nCAB\nCCA\n");
+        let same = |a: &char, b: &char| a == b;
+        assert_eq!(
+            g.flood_fill(&Point(0, 0), same),
+            vec![Point(0, 0), Point(0, 1), Point(1, 1)]
+        );
+        // diagonals do not connect
+        assert_eq!(g.flood_fill(&Point(2, 2), same), vec![Point(2, 2)]);
+        let labels = g.label_regions(same);
+        assert_eq!(labels.to_string(), "001\n201\n223\n");
+        let one = g.label_regions(|_, _| true);
+        assert!(one.iter().all(|&region| region == 0));
+    }
+
+    #[test]
+    fn words() {
+        let g = Grid::new("ABCA\nBBAB\nCACC\n");
+        assert!(g.has_word(&Point(0, 0), &Point::EAST, "ABC"));
+        assert!(!g.has_word(&Point(0, 2), &Point::EAST, "CAB"));
+        assert_eq!(g.count_word("ABC", &[Point::EAST, Point::SOUTH]), 3);
+        assert_eq!(g.count_word("ABC", &super::ALL_DIRECTIONS), 4);
+        assert_eq!(g.count_word("B", &[Point::EAST, Point::SOUTH]), 8);
+        assert_eq!(g.count_cross("ABC"), 0);
+        let x = Grid::new("A.C\n.B.\nA.C\n");
+        assert_eq!(x.count_cross("ABC"), 1);
+        assert_eq!(x.count_cross("CBA"), 1);
+        assert_eq!(x.count_cross("B"), 1);
+        assert_eq!(Grid::new("A.A\n.B.\nC.C\n").count_cross("ABC"), 1);
+        assert_eq!(Grid::new("A.A\n.B.\nA.C\n").count_cross("ABC"), 0);
+    }
+
+    #[test]
+    fn maze() {
+        let (walls, markers) = parse_maze("#####\n#S.E#\n#####\n", '#').unwrap();
+        assert_eq!((walls.lines, walls.columns), (3, 5));
+        assert_eq!(walls.iter().filter(|wall| !**wall).count(), 3);
+        assert_eq!(walls.get(&Point(1, 2)), Some(&false));
+        assert_eq!(markers.len(), 2);
+        assert_eq!(markers[&'S'], Point(1, 1));
+        assert_eq!(markers[&'E'], Point(1, 3));
+    }
 }
@@ -0,0 +1,225 @@
+//! 3D grid stuff, the counterpart of [`crate::etc::grid`] with a third coordinate.
+#![allow(dead_code)]
+use crate::etc::grid::Topology;
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+/// A point in space, as `(layer, line, column)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3(pub i64, pub i64, pub i64);
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3(0, 0, 0);
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::ORIGIN
+    }
+
+    /// Return the taxicab distance to the other point.
+    pub fn taxicab_distance(&self, other: &Self) -> u64 {
+        self.0.abs_diff(other.0) + self.1.abs_diff(other.1) + self.2.abs_diff(other.2)
+    }
+
+    /// The direction vectors to the neighbours of a point, see [`Topology`]: the 6 faces, the
+    /// 20 edges and corners, or all 26 of them.
+    pub fn directions(topology: Topology) -> impl Iterator<Item = Point3> {
+        (-1..=1)
+            .flat_map(|l| (-1..=1).flat_map(move |i| (-1..=1).map(move |c| Point3(l, i, c))))
+            .filter(move |delta| match delta.taxicab_distance(&Point3::ORIGIN) {
+                0 => false,
+                1 => topology != Topology::Diagonal,
+                _ => topology != Topology::Orthogonal,
+            })
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point3(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point3(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Point3(self.0 * rhs, self.1 * rhs, self.2 * rhs)
+    }
+}
+
+/// A 3D grid, a stack of `layers` 2D grids of `lines` x `columns` cells.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid3<T> {
+    pub layers: usize,
+    pub lines: usize,
+    pub columns: usize,
+    pub items: Vec<T>,
+}
+
+impl<T> Grid3<T> {
+    /// Build a grid of the given size, the cell at each position being given by `f`, layer by
+    /// layer and line by line.
+    pub fn from_fn<F>(layers: usize, lines: usize, columns: usize, f: F) -> Self
+    where
+        F: FnMut(Point3) -> T,
+    {
+        let mut grid = Grid3 {
+            layers,
+            lines,
+            columns,
+            items: Vec::new(),
+        };
+        grid.items = grid.positions().map(f).collect();
+        grid
+    }
+
+    /// Return the number of cells.
+    pub fn size(&self) -> usize {
+        self.layers * self.lines * self.columns
+    }
+
+    pub fn valid_position(&self, pos: &Point3) -> bool {
+        (0..self.layers as i64).contains(&pos.0)
+            && (0..self.lines as i64).contains(&pos.1)
+            && (0..self.columns as i64).contains(&pos.2)
+    }
+
+    /// Unchecked conversion from cell index to point.
+    pub fn unchecked_position(&self, index: usize) -> Point3 {
+        let layer = self.lines * self.columns;
+        Point3(
+            (index / layer) as i64,
+            (index % layer / self.columns) as i64,
+            (index % self.columns) as i64,
+        )
+    }
+
+    pub fn unchecked_index(&self, pos: &Point3) -> usize {
+        ((pos.0 as usize) * self.lines + pos.1 as usize) * self.columns + pos.2 as usize
+    }
+
+    pub fn checked_index(&self, pos: &Point3) -> Option<usize> {
+        self.valid_position(pos).then(|| self.unchecked_index(pos))
+    }
+
+    /// Retrieve value at given point.
+    pub fn get(&self, pos: &Point3) -> Option<&T> {
+        self.checked_index(pos).map(|index| &self.items[index])
+    }
+
+    pub fn get_mut(&mut self, pos: &Point3) -> Option<&mut T> {
+        self.checked_index(pos).map(|index| &mut self.items[index])
+    }
+
+    pub fn step(&self, origin: &Point3, delta: &Point3) -> Option<Point3> {
+        let point = *origin + *delta;
+        self.valid_position(&point).then_some(point)
+    }
+
+    /// Iterate over the positions of the grid, layer by layer and line by line.
+    pub fn positions(&self) -> impl Iterator<Item = Point3> + use<T> {
+        let (lines, columns) = (self.lines, self.columns);
+        (0..self.size()).map(move |index| {
+            let layer = lines * columns;
+            Point3(
+                (index / layer) as i64,
+                (index % layer / columns) as i64,
+                (index % columns) as i64,
+            )
+        })
+    }
+
+    /// Iterate over the cells of the grid with their position, layer by layer and line by line.
+    pub fn iter_with_position(&self) -> impl Iterator<Item = (Point3, &T)> {
+        self.positions().zip(self.items.iter())
+    }
+
+    /// Iterate over the neighbours of a position inside the grid, with their position.
+    pub fn neighbours<'a>(
+        &'a self,
+        origin: &Point3,
+        topology: Topology,
+    ) -> impl Iterator<Item = (Point3, &'a T)> + use<'a, T> {
+        let origin = *origin;
+        Point3::directions(topology)
+            .filter_map(move |delta| self.step(&origin, &delta))
+            .map(|pos| (pos, &self[pos]))
+    }
+}
+
+impl<T> Grid3<T>
+where
+    T: Default + Clone,
+{
+    pub fn default(layers: usize, lines: usize, columns: usize) -> Self {
+        Grid3 {
+            layers,
+            lines,
+            columns,
+            items: vec![T::default(); layers * lines * columns],
+        }
+    }
+}
+
+/// The cell at a position, panics if the position is out of the grid.
+impl<T> Index<Point3> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point3) -> &T {
+        let index = self.checked_index(&pos).expect("invalid position");
+        &self.items[index]
+    }
+}
+
+impl<T> IndexMut<Point3> for Grid3<T> {
+    fn index_mut(&mut self, pos: Point3) -> &mut T {
+        let index = self.checked_index(&pos).expect("invalid position");
+        &mut self.items[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid3, Point3};
+    use crate::etc::grid::Topology;
+
+    #[test]
+    fn points() {
+        assert_eq!(Point3(1, 2, 3) + Point3(-1, 0, 1) * 2, Point3(-1, 2, 5));
+        assert_eq!(Point3(1, 2, 3) - Point3(1, 2, 3), Point3::ORIGIN);
+        assert_eq!(Point3(1, -2, 3).taxicab_distance(&Point3::ORIGIN), 6);
+        assert_eq!(Point3::directions(Topology::Orthogonal).count(), 6);
+        assert_eq!(Point3::directions(Topology::Diagonal).count(), 20);
+        assert_eq!(Point3::directions(Topology::All).count(), 26);
+    }
+
+    #[test]
+    fn grid() {
+        let mut grid = Grid3::from_fn(2, 3, 4, |p| p.0 * 100 + p.1 * 10 + p.2);
+        assert_eq!(grid.size(), 24);
+        assert_eq!(grid[Point3(1, 2, 3)], 123);
+        assert_eq!(grid.get(&Point3(2, 0, 0)), None);
+        grid[Point3(0, 1, 1)] = -1;
+        assert_eq!(grid.items[5], -1);
+        for (index, (pos, _)) in grid.iter_with_position().enumerate() {
+            assert_eq!(grid.unchecked_position(index), pos);
+            assert_eq!(grid.unchecked_index(&pos), index);
+        }
+        let faces: Vec<i64> = grid
+            .neighbours(&Point3(0, 0, 0), Topology::Orthogonal)
+            .map(|(_, v)| *v)
+            .collect();
+        assert_eq!(faces, vec![1, 10, 100]);
+        assert_eq!(grid.neighbours(&Point3(1, 1, 1), Topology::All).count(), 17);
+        assert_eq!(Grid3::<bool>::default(2, 2, 2).items, vec![false; 8]);
+    }
+}
@@ -0,0 +1,144 @@
+//! Sets of integers stored as disjoint ranges, e.g. seed ranges or sensor coverage.
+#![allow(dead_code)]
+use std::ops::Range;
+
+/// A set of `i64`, as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Intervals {
+    ranges: Vec<Range<i64>>,
+}
+
+impl Intervals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the values of a range, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        // ranges before `first` end before the new one, ranges from `last` start after it
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if first < last {
+            range.start.min(self.ranges[first].start)..range.end.max(self.ranges[last - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Add the values of the other set.
+    pub fn merge(&mut self, other: &Intervals) {
+        for range in &other.ranges {
+            self.insert(range.clone());
+        }
+    }
+
+    /// Remove the values of a range, splitting the ranges it falls within.
+    pub fn subtract(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first == last {
+            return;
+        }
+        let mut remaining = Vec::with_capacity(2);
+        if self.ranges[first].start < range.start {
+            remaining.push(self.ranges[first].start..range.start);
+        }
+        if range.end < self.ranges[last - 1].end {
+            remaining.push(range.end..self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, remaining);
+    }
+
+    /// Remove the values of the other set.
+    pub fn subtract_all(&mut self, other: &Intervals) {
+        for range in &other.ranges {
+            self.subtract(range.clone());
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(index).is_some_and(|r| r.contains(&value))
+    }
+
+    /// Number of values in the set.
+    pub fn total_len(&self) -> u64 {
+        self.ranges.iter().map(|r| r.start.abs_diff(r.end)).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The disjoint ranges of the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Range<i64>> {
+        self.ranges.iter()
+    }
+}
+
+impl FromIterator<Range<i64>> for Intervals {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        let mut intervals = Intervals::new();
+        for range in iter {
+            intervals.insert(range);
+        }
+        intervals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Intervals;
+
+    #[test]
+    fn insert() {
+        let mut set: Intervals = [10..20, 30..40, 0..0].into_iter().collect();
+        assert_eq!(set.total_len(), 20);
+        // touching ranges are merged
+        set.insert(20..25);
+        assert_eq!(
+            set.iter().cloned().collect::<Vec<_>>(),
+            vec![10..25, 30..40]
+        );
+        set.insert(-5..0);
+        set.insert(22..35);
+        assert_eq!(set.iter().cloned().collect::<Vec<_>>(), vec![-5..0, 10..40]);
+        assert!(set.contains(-5) && set.contains(39));
+        assert!(!set.contains(0) && !set.contains(40));
+
+        let mut other: Intervals = [0..10, 50..60].into_iter().collect();
+        other.merge(&set);
+        assert_eq!(
+            other.iter().cloned().collect::<Vec<_>>(),
+            vec![-5..40, 50..60]
+        );
+        assert_eq!(other.total_len(), 55);
+    }
+
+    #[test]
+    fn subtract() {
+        let mut set: Intervals = [0..10, 20..30].into_iter().collect();
+        set.subtract(3..5);
+        assert_eq!(
+            set.iter().cloned().collect::<Vec<_>>(),
+            vec![0..3, 5..10, 20..30]
+        );
+        set.subtract(8..25);
+        assert_eq!(
+            set.iter().cloned().collect::<Vec<_>>(),
+            vec![0..3, 5..8, 25..30]
+        );
+        set.subtract(10..20);
+        assert_eq!(set.total_len(), 11);
+        set.subtract_all(&[-10..4, 4..100].into_iter().collect());
+        assert!(set.is_empty());
+        assert_eq!(set, Intervals::new());
+    }
+}
@@ -0,0 +1,121 @@
+//! Number theory toolbox: gcd, lcm, modular arithmetic and the chinese remainder theorem.
+#![allow(dead_code)]
+
+/// Greatest common divisor, always non-negative, `gcd(0, 0)` being 0.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    egcd(a, b).0
+}
+
+/// Extended Euclidean algorithm: `(g, x, y)` such that `a * x + b * y == g`, `g` being the
+/// non-negative gcd of `a` and `b`.
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// Least common multiple, always non-negative, 0 if either is 0.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// `base` to the power `exp`, modulo `modulus`.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// The inverse of `a` modulo `modulus`, in `0..modulus`, if `a` and `modulus` are coprime.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = egcd(a, modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solve the system of congruences `x = residue (mod modulus)`, given as `(residue, modulus)`.
+///
+/// Return the least non-negative solution and the modulus of all solutions, the lcm of the
+/// moduli, or `None` if the congruences are incompatible. Moduli need not be coprime.
+pub fn chinese_remainder(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, m), &(residue, modulus)| {
+            // x + m * k = residue (mod modulus)
+            let (g, inverse, _) = egcd(m, modulus);
+            let diff = residue - x;
+            if diff % g != 0 {
+                return None;
+            }
+            let lcm = m / g * modulus;
+            let k = (diff / g) as i128 * inverse as i128 % (modulus / g) as i128;
+            let x = (x as i128 + m as i128 * k).rem_euclid(lcm as i128);
+            Some((x as i64, lcm))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chinese_remainder, egcd, gcd, lcm, mod_inverse, mod_pow};
+
+    #[test]
+    fn divisors() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(gcd(0, 0), 0);
+        let (g, x, y) = egcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(101, 103), 10403);
+        assert_eq!(lcm(0, 3), 0);
+    }
+
+    #[test]
+    fn modular() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(3, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 6), None);
+    }
+
+    #[test]
+    fn remainders() {
+        assert_eq!(
+            chinese_remainder(&[(2, 3), (3, 5), (2, 7)]),
+            Some((23, 105))
+        );
+        assert_eq!(chinese_remainder(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
+        assert_eq!(chinese_remainder(&[]), Some((0, 1)));
+        // day 14 robots clustering at step 12 along x (period 101) and 76 along y (period 103)
+        assert_eq!(
+            chinese_remainder(&[(12, 101), (76, 103)]),
+            Some((7183, 10403))
+        );
+    }
+}
@@ -1,2 +1,29 @@
 pub mod solution;
 pub mod grid;
+pub mod grid3;
+pub mod math;
+pub mod intervals;
+pub mod counter;
+pub mod simulation;
+pub mod checkpoint;
+pub mod config;
+pub mod rng;
+pub mod search;
+pub mod pathfinding;
+pub mod graph;
+pub mod dsu;
+pub mod parse;
+pub mod shape;
+pub mod results;
+pub mod render;
+pub mod viz;
+pub mod bench;
+pub mod answers;
+pub mod scaffold;
+pub mod watch;
+pub mod report;
+pub mod website;
+pub mod examples;
+pub mod crypt;
+#[cfg(feature = "embed-inputs")]
+pub mod embedded;
@@ -0,0 +1,273 @@
+//! Lightweight parsing helpers.
+#![allow(dead_code)]
+use crate::etc::grid::{Grid, GridParseError};
+use std::str::FromStr;
+
+/// Puzzle input in the form the days parse: without carriage returns, leading and trailing blank
+/// lines, nor the indentation common to the lines after the first one, as examples embedded in
+/// the source have, and ending with a newline.
+pub fn normalize(input: &str) -> String {
+    let lines: Vec<&str> = input
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .collect();
+    let indent = lines
+        .iter()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let lines: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| match line.get(indent..) {
+            Some(dedented) if i > 0 => dedented,
+            _ if i > 0 => line.trim_start(),
+            _ => line,
+        })
+        .skip_while(|line| line.trim().is_empty())
+        .collect();
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |last| last + 1);
+    lines[..end]
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// Cursor over a text, consuming literals and integers.
+///
+/// Methods returning `None` do not consume anything.
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Scanner { input, pos: 0 }
+    }
+
+    /// The text remaining to scan.
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    /// Skip the next character, return `false` at the end of the text.
+    pub fn skip(&mut self) -> bool {
+        match self.rest().chars().next() {
+            Some(c) => {
+                self.pos += c.len_utf8();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Consume the given literal.
+    pub fn literal(&mut self, literal: &str) -> Option<()> {
+        self.rest().starts_with(literal).then(|| {
+            self.pos += literal.len();
+        })
+    }
+
+    /// Skip up to and including the next occurrence of the given literal.
+    pub fn skip_past(&mut self, literal: &str) -> Option<()> {
+        let at = self.rest().find(literal)?;
+        self.pos += at + literal.len();
+        Some(())
+    }
+
+    /// Consume an unsigned integer. Digits that do not fit in `T` are consumed too.
+    pub fn unsigned<T: FromStr>(&mut self) -> Option<T> {
+        self.integer(false)?.ok()
+    }
+
+    /// Consume an integer, with an optional minus sign. Digits that do not fit in `T` are
+    /// consumed too.
+    pub fn signed<T: FromStr>(&mut self) -> Option<T> {
+        self.integer(true)?.ok()
+    }
+
+    /// Consume the digits of an integer, `None` without any digit, or the error of digits that
+    /// do not fit in `T`.
+    fn integer<T: FromStr>(&mut self, signed: bool) -> Option<Result<T, T::Err>> {
+        let sign = usize::from(signed && self.rest().starts_with('-'));
+        let len = self.rest()[sign..]
+            .bytes()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if len == 0 {
+            return None;
+        }
+        let value = self.rest()[..sign + len].parse();
+        self.pos += sign + len;
+        Some(value)
+    }
+
+    /// Run a sequence of scans, rolling back if any of them fails.
+    pub fn attempt<T, F>(&mut self, f: F) -> Option<T>
+    where
+        F: FnOnce(&mut Self) -> Option<T>,
+    {
+        let pos = self.pos;
+        let res = f(self);
+        if res.is_none() {
+            self.pos = pos;
+        }
+        res
+    }
+}
+
+/// All the unsigned integers of a text, ignoring everything else, or the error of those that do
+/// not fit in `T`.
+pub fn unsigned_ints<T: FromStr>(input: &str) -> impl Iterator<Item = Result<T, T::Err>> {
+    let mut scanner = Scanner::new(input);
+    std::iter::from_fn(move || {
+        loop {
+            if let Some(value) = scanner.integer(false) {
+                return Some(value);
+            }
+            if !scanner.skip() {
+                return None;
+            }
+        }
+    })
+}
+
+/// All the integers of a text, a minus sign right before digits making them negative, or the
+/// error of those that do not fit in `T`.
+pub fn signed_ints<T: FromStr>(input: &str) -> impl Iterator<Item = Result<T, T::Err>> {
+    let mut scanner = Scanner::new(input);
+    std::iter::from_fn(move || {
+        loop {
+            if let Some(value) = scanner.integer(true) {
+                return Some(value);
+            }
+            if !scanner.skip() {
+                return None;
+            }
+        }
+    })
+}
+
+/// All the integers of a text, see [`signed_ints`].
+pub fn ints<T: FromStr>(input: &str) -> Result<Vec<T>, T::Err> {
+    signed_ints(input).collect()
+}
+
+/// The blocks of a text separated by blank lines, without their surrounding newlines.
+pub fn blocks(input: &str) -> Vec<&str> {
+    input
+        .split("\n\n")
+        .map(|block| block.trim_matches('\n'))
+        .filter(|block| !block.is_empty())
+        .collect()
+}
+
+/// A grid in the first block of a text, and the text after the blank line following it, empty
+/// if there is none.
+pub fn grid_and_rest(input: &str) -> Result<(Grid<char>, &str), GridParseError> {
+    let input = input.trim_start_matches('\n');
+    let (grid, rest) = input.split_once("\n\n").unwrap_or((input, ""));
+    Ok((Grid::try_new(grid)?, rest.trim_start_matches('\n')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Scanner, blocks, grid_and_rest, normalize, signed_ints, unsigned_ints};
+    use crate::etc::grid::GridParseError;
+
+    #[test]
+    fn normalization() {
+        assert_eq!(
+            normalize("3   4\n        4   3\n        2   5"),
+            "3   4\n4   3\n2   5\n"
+        );
+        assert_eq!(
+            normalize("\n    Register A: 729\n\n      Program: 0,1\n  \n"),
+            "Register A: 729\n\n  Program: 0,1\n"
+        );
+        assert_eq!(normalize("#.\r\n.#\r\n\r\n"), "#.\n.#\n");
+        let input = "p=0,4 v=3,-3\np=6,3 v=-1,-3\n";
+        assert_eq!(normalize(input), input);
+        assert_eq!(normalize(" \n\n"), "");
+    }
+
+    #[test]
+    fn scanner() {
+        let mut s = Scanner::new("mul(12,-3)x");
+        assert_eq!(s.literal("mul["), None);
+        assert_eq!(s.literal("mul("), Some(()));
+        assert_eq!(s.signed::<i32>(), Some(12));
+        assert_eq!(s.unsigned::<u32>(), None);
+        assert_eq!(s.literal(","), Some(()));
+        assert_eq!(s.unsigned::<u32>(), None);
+        assert_eq!(s.signed::<i32>(), Some(-3));
+        assert_eq!(s.rest(), ")x");
+        assert_eq!(s.skip_past("x"), Some(()));
+        assert!(s.is_done());
+        assert!(!s.skip());
+    }
+
+    #[test]
+    fn attempt() {
+        let mut s = Scanner::new("a=1,b");
+        let pair = |s: &mut Scanner| {
+            s.attempt(|s| {
+                s.skip();
+                s.literal("=")?;
+                s.unsigned::<u8>()
+            })
+        };
+        assert_eq!(pair(&mut s), Some(1));
+        assert_eq!(s.literal(","), Some(()));
+        assert_eq!(pair(&mut s), None);
+        assert_eq!(s.rest(), "b");
+    }
+
+    #[test]
+    fn ints() {
+        let text = "p=0,4 v=3,-3\nx-y 12-5 -";
+        assert_eq!(
+            unsigned_ints::<u32>(text).collect::<Result<Vec<_>, _>>(),
+            Ok(vec![0, 4, 3, 3, 12, 5])
+        );
+        assert_eq!(
+            signed_ints::<i32>(text).collect::<Result<Vec<_>, _>>(),
+            Ok(vec![0, 4, 3, -3, 12, -5])
+        );
+        assert_eq!(unsigned_ints::<u8>("").count(), 0);
+        assert_eq!(super::ints::<i64>("-1 2\n-3"), Ok(vec![-1, 2, -3]));
+
+        // too large numbers are errors, not the numbers of their last digits
+        let overflows = unsigned_ints::<u8>("1,300,2").collect::<Vec<_>>();
+        assert_eq!(overflows.len(), 3);
+        assert!(overflows[1].is_err());
+        assert_eq!((&overflows[0], &overflows[2]), (&Ok(1), &Ok(2)));
+        assert!(super::ints::<i8>("-129").is_err());
+        let mut s = Scanner::new("300,1");
+        assert_eq!(s.unsigned::<u8>(), None);
+        assert_eq!(s.rest(), ",1");
+    }
+
+    #[test]
+    fn blocks_and_grids() {
+        let input = "#.\n.#\n\n<>\n^v\n\n\nend\n";
+        assert_eq!(blocks(input), vec!["#.\n.#", "<>\n^v", "end"]);
+        assert!(blocks("\n").is_empty());
+        let (grid, rest) = grid_and_rest(input).unwrap();
+        assert_eq!(grid.items, vec!['#', '.', '.', '#']);
+        assert_eq!(rest, "<>\n^v\n\n\nend\n");
+        assert_eq!(grid_and_rest("ab\ncd\n").unwrap().1, "");
+        assert_eq!(grid_and_rest("\n\n").unwrap_err(), GridParseError::Empty);
+    }
+}
@@ -0,0 +1,274 @@
+//! Path finding over any state type.
+#![allow(dead_code)]
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Result of a breadth-first search: the distance of every reached state from the start, and
+/// the goal if one was reached.
+#[derive(Debug, Clone)]
+pub struct Bfs<S> {
+    pub distances: HashMap<S, u64>,
+    /// predecessor of every reached state but the start, on one of its shortest paths
+    parents: HashMap<S, S>,
+    pub goal: Option<S>,
+}
+
+impl<S> Bfs<S>
+where
+    S: Clone + Eq + Hash,
+{
+    /// Distance from the start to the goal, if reached.
+    pub fn goal_distance(&self) -> Option<u64> {
+        self.goal.as_ref().map(|goal| self.distances[goal])
+    }
+
+    /// A shortest path from the start to a reached state, both included.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return None;
+        }
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// A shortest path from the start to the goal, if reached.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+}
+
+/// Breadth-first search from `start`, stopping at the first state satisfying `goal`.
+///
+/// Every reachable state is explored when there is no goal, e.g. with `|_| false`.
+pub fn bfs<S, I, F, G>(start: S, mut successors: F, mut goal: G) -> Bfs<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    let mut result = Bfs {
+        distances: HashMap::from([(start.clone(), 0)]),
+        parents: HashMap::new(),
+        goal: None,
+    };
+    let mut worklist = VecDeque::from([(start, 0)]);
+    while let Some((state, dist)) = worklist.pop_front() {
+        if goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+        for next in successors(&state) {
+            if !result.distances.contains_key(&next) {
+                result.distances.insert(next.clone(), dist + 1);
+                result.parents.insert(next.clone(), state.clone());
+                worklist.push_back((next, dist + 1));
+            }
+        }
+    }
+    result
+}
+
+/// Result of a Dijkstra or A* search: the least cost of every reached state from the start, and the
+/// goal if one was reached.
+#[derive(Debug, Clone)]
+pub struct Dijkstra<S> {
+    pub distances: HashMap<S, u64>,
+    /// predecessors of every reached state but the start, on all of its least cost paths
+    predecessors: HashMap<S, Vec<S>>,
+    pub goal: Option<S>,
+}
+
+impl<S> Dijkstra<S>
+where
+    S: Clone + Eq + Hash,
+{
+    /// Least cost from the start to the goal, if reached.
+    pub fn goal_distance(&self) -> Option<u64> {
+        self.goal.as_ref().map(|goal| self.distances[goal])
+    }
+
+    /// The predecessors of a state on its least cost paths from the start.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// A least cost path from the start to a reached state, both included.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return None;
+        }
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.predecessors(path.last().unwrap()).first() {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// A least cost path from the start to the goal, if reached.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    /// Every state on a least cost path from the start to one of the targets, targets included.
+    pub fn on_best_paths(&self, targets: &[S]) -> HashSet<S> {
+        let mut on_paths = HashSet::new();
+        let mut worklist: Vec<S> = targets
+            .iter()
+            .filter(|target| self.distances.contains_key(target))
+            .cloned()
+            .collect();
+        while let Some(state) = worklist.pop() {
+            if on_paths.insert(state.clone()) {
+                worklist.extend(self.predecessors(&state).iter().cloned());
+            }
+        }
+        on_paths
+    }
+}
+
+/// Dijkstra search from `start`, successors being given with the cost of moving to them,
+/// stopping at the first state satisfying `goal`.
+///
+/// Every reachable state is explored when there is no goal, e.g. with `|_| false`.
+pub fn dijkstra<S, I, F, G>(start: S, successors: F, goal: G) -> Dijkstra<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    astar(start, successors, |_| 0, goal)
+}
+
+/// A* search from `start` to the first state satisfying `goal`, guided by a `heuristic`
+/// estimate of the cost from a state to the goal.
+///
+/// The heuristic must never overestimate the cost for the goal distance to be the least one.
+/// Only the states explored on the way to the goal are in the result.
+pub fn astar<S, I, F, H, G>(
+    start: S,
+    mut successors: F,
+    mut heuristic: H,
+    mut goal: G,
+) -> Dijkstra<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+    F: FnMut(&S) -> I,
+    H: FnMut(&S) -> u64,
+    G: FnMut(&S) -> bool,
+{
+    let mut result = Dijkstra {
+        distances: HashMap::from([(start.clone(), 0)]),
+        predecessors: HashMap::new(),
+        goal: None,
+    };
+    // states are queued by index in `queued`, to not require them to be ordered
+    let mut worklist = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut queued = vec![start];
+    while let Some(Reverse((_, dist, index))) = worklist.pop() {
+        let state = queued[index].clone();
+        if result.distances[&state] < dist {
+            // already reached at a lower cost
+            continue;
+        }
+        if goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+        for (next, cost) in successors(&state) {
+            let next_dist = dist + cost;
+            match result.distances.get(&next) {
+                Some(&known) if known < next_dist => {}
+                Some(&known) if known == next_dist => {
+                    result
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                }
+                _ => {
+                    result.distances.insert(next.clone(), next_dist);
+                    result
+                        .predecessors
+                        .insert(next.clone(), vec![state.clone()]);
+                    let estimate = next_dist + heuristic(&next);
+                    worklist.push(Reverse((estimate, next_dist, queued.len())));
+                    queued.push(next);
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{astar, bfs, dijkstra};
+
+    #[test]
+    fn numbers() {
+        // reach 10 from 1, by doubling or adding one
+        let search = bfs(1u32, |&n| [n * 2, n + 1], |&n| n == 10);
+        assert_eq!(search.goal, Some(10));
+        assert_eq!(search.goal_distance(), Some(4));
+        assert_eq!(search.path(), Some(vec![1, 2, 4, 5, 10]));
+        assert_eq!(search.path_to(&1), Some(vec![1]));
+        assert_eq!(search.path_to(&1000), None);
+
+        // every state is explored without a goal
+        let search = bfs(0u32, |&n| (n < 5).then_some(n + 1), |_| false);
+        assert_eq!(search.goal, None);
+        assert_eq!(search.distances.len(), 6);
+        assert_eq!(search.path_to(&5), Some(vec![0, 1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn weighted() {
+        // a diamond with two best paths from 0 to 3, and a costly shortcut
+        let edges = |n: &u32| -> Vec<(u32, u64)> {
+            match n {
+                0 => vec![(1, 1), (2, 2), (3, 10)],
+                1 => vec![(3, 3)],
+                2 => vec![(3, 2)],
+                _ => vec![],
+            }
+        };
+        let search = dijkstra(0, edges, |&n| n == 3);
+        assert_eq!(search.goal_distance(), Some(4));
+        assert_eq!(search.predecessors(&3).len(), 2);
+        assert_eq!(search.path().unwrap().len(), 3);
+        let mut on_paths: Vec<u32> = search.on_best_paths(&[3]).into_iter().collect();
+        on_paths.sort();
+        assert_eq!(on_paths, vec![0, 1, 2, 3]);
+
+        let search = dijkstra(1, edges, |_| false);
+        assert_eq!(search.goal, None);
+        assert_eq!(search.distances, [(1, 0), (3, 3)].into());
+        assert_eq!(search.path_to(&0), None);
+    }
+
+    #[test]
+    fn guided() {
+        // walk on a line from 0 to 50, by steps of 1 or 5 costing 1 and 3
+        let steps = |n: &i64| [(n + 1, 1), (n - 1, 1), (n + 5, 3), (n - 5, 3)];
+        let guided = astar(
+            0i64,
+            steps,
+            |n| (50 - n).unsigned_abs() * 3 / 5,
+            |&n| n == 50,
+        );
+        let blind = dijkstra(0i64, steps, |&n| n == 50);
+        assert_eq!(guided.goal_distance(), Some(30));
+        assert_eq!(blind.goal_distance(), Some(30));
+        assert!(guided.distances.len() < blind.distances.len());
+        assert_eq!(guided.path().unwrap().len(), 11);
+    }
+}
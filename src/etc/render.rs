@@ -0,0 +1,187 @@
+//! Rendering grids in the terminal, with colours from ANSI escape codes.
+#![allow(dead_code)]
+use crate::{Grid, Point};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Escape code parameters, as foreground or background colour.
+    fn code(&self, background: bool) -> String {
+        let base = if background { 40 } else { 30 };
+        match self {
+            Color::Black => base.to_string(),
+            Color::Red => (base + 1).to_string(),
+            Color::Green => (base + 2).to_string(),
+            Color::Yellow => (base + 3).to_string(),
+            Color::Blue => (base + 4).to_string(),
+            Color::Magenta => (base + 5).to_string(),
+            Color::Cyan => (base + 6).to_string(),
+            Color::White => (base + 7).to_string(),
+            Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+        }
+    }
+
+    /// The colour in CSS syntax, e.g. for SVG drawings.
+    pub fn css(&self) -> String {
+        match self {
+            Color::Black => "black".to_owned(),
+            Color::Red => "red".to_owned(),
+            Color::Green => "green".to_owned(),
+            Color::Yellow => "gold".to_owned(),
+            Color::Blue => "blue".to_owned(),
+            Color::Magenta => "magenta".to_owned(),
+            Color::Cyan => "darkcyan".to_owned(),
+            Color::White => "white".to_owned(),
+            Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        }
+    }
+
+    /// Colour of a value on a scale from blue (0) to red (`max`).
+    pub fn gradient(value: u64, max: u64) -> Self {
+        let ratio = if max == 0 {
+            0.0
+        } else {
+            value.min(max) as f64 / max as f64
+        };
+        Color::Rgb((255.0 * ratio) as u8, 64, (255.0 * (1.0 - ratio)) as u8)
+    }
+}
+
+/// How to draw a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub glyph: char,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+}
+
+impl Style {
+    pub fn plain(glyph: char) -> Self {
+        Style {
+            glyph,
+            fg: None,
+            bg: None,
+            bold: false,
+        }
+    }
+
+    pub fn fg(self, color: Color) -> Self {
+        Style {
+            fg: Some(color),
+            ..self
+        }
+    }
+
+    pub fn bg(self, color: Color) -> Self {
+        Style {
+            bg: Some(color),
+            ..self
+        }
+    }
+
+    pub fn bold(self) -> Self {
+        Style { bold: true, ..self }
+    }
+
+    /// The glyph surrounded with the escape codes of its style.
+    fn paint(&self, out: &mut String) {
+        let mut codes = vec![];
+        if self.bold {
+            codes.push("1".to_owned());
+        }
+        codes.extend(self.fg.map(|c| c.code(false)));
+        codes.extend(self.bg.map(|c| c.code(true)));
+        if codes.is_empty() {
+            out.push(self.glyph);
+        } else {
+            out.push_str(&format!("\x1b[{}m{}\x1b[0m", codes.join(";"), self.glyph));
+        }
+    }
+}
+
+/// Text drawn in a foreground colour.
+pub fn colored(text: &str, color: Color) -> String {
+    format!("\x1b[{}m{}\x1b[0m", color.code(false), text)
+}
+
+/// Draw a grid, one line of text per grid line, styling each cell with the given callback.
+pub fn render<T, F>(grid: &Grid<T>, style: F) -> String
+where
+    F: Fn(Point, &T) -> Style,
+{
+    let mut out = String::new();
+    grid.for_each_with_position(|pos, cell| {
+        style(pos, cell).paint(&mut out);
+        if pos.1 as usize == grid.columns - 1 {
+            out.push('\n');
+        }
+    });
+    out
+}
+
+static DEBUG: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable debug dumps for the whole process.
+pub fn set_debug(enabled: bool) {
+    DEBUG.store(enabled, Ordering::Relaxed);
+}
+
+pub fn debug() -> bool {
+    DEBUG.load(Ordering::Relaxed)
+}
+
+/// Draw a grid on the standard error when debug dumps are enabled, see [`render`].
+pub fn debug_dump<T, F>(title: &str, grid: &Grid<T>, style: F)
+where
+    F: Fn(Point, &T) -> Style,
+{
+    if debug() {
+        eprintln!("{}:\n{}", title, render(grid, style));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Color, Style, render};
+    use crate::{Grid, Point};
+
+    #[test]
+    fn styles() {
+        let grid = Grid::new("#.\n.#\n");
+        let plain = render(&grid, |_, c| Style::plain(*c));
+        assert_eq!(plain, "#.\n.#\n");
+
+        let styled = render(&grid, |pos, c| match c {
+            '#' => Style::plain('█').fg(Color::Blue).bold(),
+            _ if pos == Point(0, 1) => Style::plain(' ').bg(Color::Rgb(1, 2, 3)),
+            _ => Style::plain(' '),
+        });
+        assert_eq!(
+            styled,
+            "\x1b[1;34m█\x1b[0m\x1b[48;2;1;2;3m \x1b[0m\n \x1b[1;34m█\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn gradient() {
+        assert_eq!(Color::gradient(0, 10), Color::Rgb(0, 64, 255));
+        assert_eq!(Color::gradient(10, 10), Color::Rgb(255, 64, 0));
+        assert_eq!(Color::gradient(20, 10), Color::Rgb(255, 64, 0));
+        assert_eq!(Color::gradient(0, 0), Color::Rgb(0, 64, 255));
+        assert_eq!(Color::gradient(10, 10).css(), "#ff4000");
+        assert_eq!(Color::Yellow.css(), "gold");
+    }
+}
@@ -0,0 +1,160 @@
+//! Table of the answers and timings of a run, with slow parts highlighted.
+#![allow(dead_code)]
+use crate::etc::render::{Color, colored};
+use std::time::Duration;
+
+/// Parts faster than this are shown in green.
+pub const FAST: Duration = Duration::from_millis(100);
+/// Parts slower than this are shown in red, the ones in between in yellow.
+pub const SLOW: Duration = Duration::from_secs(1);
+
+const HEADERS: [&str; 5] = ["Day", "Part 1", "Part 2", "Time 1", "Time 2"];
+
+/// Answers and timings of a day, `None` for parts that were not solved separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub day: u8,
+    pub answers: [Option<String>; 2],
+    pub times: [Option<Duration>; 2],
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    rows: Vec<Row>,
+    /// Total runtime, including the parts that were not timed separately.
+    total: Duration,
+    /// Use colours, for terminals.
+    color: bool,
+}
+
+impl Report {
+    pub fn new(color: bool) -> Self {
+        Report {
+            color,
+            ..Report::default()
+        }
+    }
+
+    /// Add the row of a day, solved in `elapsed`.
+    pub fn push(&mut self, row: Row, elapsed: Duration) {
+        self.rows.push(row);
+        self.total += elapsed;
+    }
+
+    fn time_color(time: Duration) -> Color {
+        if time < FAST {
+            Color::Green
+        } else if time < SLOW {
+            Color::Yellow
+        } else {
+            Color::Red
+        }
+    }
+
+    /// The aligned table, with a footer with the total runtime.
+    pub fn render(&self) -> String {
+        let cells: Vec<[String; 5]> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let answer = |part: usize| row.answers[part].clone().unwrap_or("-".to_owned());
+                let time =
+                    |part: usize| row.times[part].map_or("-".to_owned(), |t| format!("{:.1?}", t));
+                [
+                    format!("{:02}", row.day),
+                    answer(0),
+                    answer(1),
+                    time(0),
+                    time(1),
+                ]
+            })
+            .collect();
+        let mut widths = HEADERS.map(|header| header.len());
+        for row in &cells {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let line = |cells: [&str; 5], colors: [Option<Color>; 5]| {
+            let cells: Vec<String> = cells
+                .iter()
+                .zip(widths)
+                .zip(colors)
+                .map(|((cell, width), color)| {
+                    let cell = format!("{:<width$}", cell, width = width);
+                    match color {
+                        Some(color) if self.color => colored(&cell, color),
+                        _ => cell,
+                    }
+                })
+                .collect();
+            cells.join(" │ ").trim_end().to_owned() + "\n"
+        };
+        let rule: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+        let rule = rule.join("─┼─") + "\n";
+
+        let mut out = line(HEADERS, [None; 5]);
+        out.push_str(&rule);
+        for (row, cells) in self.rows.iter().zip(&cells) {
+            let color = |part: usize| row.times[part].map(Self::time_color);
+            let cells = [0, 1, 2, 3, 4].map(|i| cells[i].as_str());
+            out.push_str(&line(cells, [None, None, None, color(0), color(1)]));
+        }
+        out.push_str(&rule);
+        out.push_str(&format!(
+            "Total: {:.1?} for {} days\n",
+            self.total,
+            self.rows.len()
+        ));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Report, Row};
+    use std::time::Duration;
+
+    #[test]
+    fn table() {
+        let mut report = Report::new(false);
+        report.push(
+            Row {
+                day: 1,
+                answers: [Some("765748".to_owned()), Some("27732508".to_owned())],
+                times: [Some(Duration::from_micros(1500)), None],
+            },
+            Duration::from_millis(3),
+        );
+        report.push(
+            Row {
+                day: 17,
+                answers: [Some("6,0,6,3,0,2,3,1,6".to_owned()), None],
+                times: [Some(Duration::from_secs(2)), None],
+            },
+            Duration::from_secs(2),
+        );
+        assert_eq!(
+            report.render(),
+            "\
+Day │ Part 1            │ Part 2   │ Time 1 │ Time 2
+────┼───────────────────┼──────────┼────────┼───────
+01  │ 765748            │ 27732508 │ 1.5ms  │ -
+17  │ 6,0,6,3,0,2,3,1,6 │ -        │ 2.0s   │ -
+────┼───────────────────┼──────────┼────────┼───────
+Total: 2.0s for 2 days
+"
+        );
+
+        let mut report = Report::new(true);
+        report.push(
+            Row {
+                day: 2,
+                answers: [None, None],
+                times: [Some(Duration::from_millis(200)), None],
+            },
+            Duration::ZERO,
+        );
+        assert!(report.render().contains("\x1b[33m200.0ms\x1b[0m"));
+    }
+}
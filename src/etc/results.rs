@@ -0,0 +1,157 @@
+//! Persistent history of runs, stored as one JSON object per line.
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// File where results are recorded.
+pub const PATH: &str = "results.jsonl";
+
+/// Answer of one part of a day, on a given run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Commit of the code, if known.
+    pub commit: Option<String>,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    /// Time to solve the whole day, in microseconds.
+    pub micros: u64,
+    /// See [`crate::etc::checkpoint::input_hash`].
+    pub input_hash: u64,
+}
+
+impl Record {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("records serialize to JSON")
+    }
+
+    /// Read a record written by [`Record::to_json`], `None` if malformed.
+    pub fn from_json(line: &str) -> Option<Record> {
+        serde_json::from_str(line).ok()
+    }
+}
+
+/// File where answer submissions are recorded.
+pub const SUBMISSIONS_PATH: &str = "submissions.jsonl";
+
+/// Answer submitted to the website, with its verdict.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    /// See [`crate::etc::website::Verdict`].
+    pub verdict: String,
+}
+
+impl Submission {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("submissions serialize to JSON")
+    }
+
+    /// Read a submission written by [`Submission::to_json`], `None` if malformed.
+    pub fn from_json(line: &str) -> Option<Submission> {
+        serde_json::from_str(line).ok()
+    }
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Commit of the working directory, `None` outside of a git repository.
+pub fn git_commit() -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+/// Append records to the file at `path`.
+pub fn append(path: &str, records: &[Record]) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for record in records {
+        writeln!(file, "{}", record.to_json())?;
+    }
+    Ok(())
+}
+
+/// All records of the file at `path`, skipping malformed lines. A missing file has no records.
+pub fn load(path: &str) -> io::Result<Vec<Record>> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(text.lines().filter_map(Record::from_json).collect()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(err) => Err(err),
+    }
+}
+
+/// Append a submission to the file at `path`.
+pub fn append_submission(path: &str, submission: &Submission) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", submission.to_json())
+}
+
+/// All submissions of the file at `path`, skipping malformed lines. A missing file has none.
+pub fn load_submissions(path: &str) -> io::Result<Vec<Submission>> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(text.lines().filter_map(Submission::from_json).collect()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Record, Submission};
+
+    #[test]
+    fn json() {
+        let mut record = Record {
+            timestamp: 1733011200,
+            commit: Some("fa59b6a".to_owned()),
+            day: 17,
+            part: 1,
+            answer: "6,0,\"6\"\\3\n\u{1}é".to_owned(),
+            micros: 1234,
+            input_hash: u64::MAX,
+        };
+        let json = record.to_json();
+        assert_eq!(Record::from_json(&json), Some(record.clone()));
+        assert!(!json.contains('\n'));
+
+        record.commit = None;
+        let json = record.to_json();
+        assert!(json.contains(r#""commit":null"#));
+        assert_eq!(Record::from_json(&json), Some(record));
+
+        assert_eq!(Record::from_json(&json[..json.len() - 1]), None);
+        assert_eq!(Record::from_json(""), None);
+
+        let submission = Submission {
+            timestamp: 1733011200,
+            year: 2024,
+            day: 1,
+            part: 2,
+            answer: "27732508".to_owned(),
+            verdict: "too high".to_owned(),
+        };
+        assert_eq!(
+            Submission::from_json(&submission.to_json()),
+            Some(submission)
+        );
+    }
+}
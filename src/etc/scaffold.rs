@@ -0,0 +1,141 @@
+//! Scaffolding of the module of a new day, registered in the registry of its year.
+#![allow(dead_code)]
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Skeleton of a day module, with the methods every day provides.
+const TEMPLATE: &str = r#"use crate::Solution;
+use crate::days::{Day, Parsed};
+use crate::etc::rng::Rng;
+use crate::etc::shape;
+
+pub fn prepare(input: &str) -> Vec<&str> {
+    input.lines().collect()
+}
+
+fn solve_part1(_lines: &[&str]) -> Solution {
+    Solution::Todo()
+}
+
+fn solve_part2(_lines: &[&str]) -> Solution {
+    Solution::Todo()
+}
+
+pub struct DayNN;
+register_day!(NN, DayNN);
+
+impl Day for DayNN {
+    fn prepare<'a>(&self, input: &'a str) -> Parsed<'a> {
+        Parsed::new(
+            prepare(input),
+            |lines| solve_part1(lines),
+            |lines| solve_part2(lines),
+        )
+    }
+
+    /// Generate `size` lines of one number.
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| format!("{}\n", rng.between(0, 100)))
+            .collect()
+    }
+
+    fn looks_like(&self, input: &str) -> bool {
+        shape::all_lines(input, |_| true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "";
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(DayNN.part1(EXAMPLE_INPUT), Ok(Solution::Todo()));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(DayNN.part2(EXAMPLE_INPUT), Ok(Solution::Todo()));
+    }
+}
+"#;
+
+/// Replace the `NN` placeholders of a template by the day.
+fn fill(template: &str, day: u8) -> String {
+    template
+        .replace("dayNN", &module(day))
+        .replace("DayNN", &format!("Day{:02}", day))
+        .replace("NN", &day.to_string())
+}
+
+fn module(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+/// Add the module of a day to the `days!` list of `days/yYYYY/mod.rs`, keeping days in order.
+pub fn register_module(mod_rs: &str, day: u8) -> String {
+    let mut lines: Vec<&str> = mod_rs.lines().collect();
+    let entry = format!("    {},", module(day));
+    let listed = |line: &&str| line.starts_with("    day");
+    let position = lines
+        .iter()
+        .position(|line| listed(line) && *line > entry.as_str())
+        .or_else(|| lines.iter().rposition(listed).map(|last| last + 1))
+        .unwrap_or(lines.len());
+    lines.insert(position, &entry);
+    lines.join("\n") + "\n"
+}
+
+/// Source file of the module of a day, in `src/days/yYYYY`.
+pub fn source_path(year: u16, day: u8) -> PathBuf {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    src.join("days")
+        .join(format!("y{}", year))
+        .join(format!("{}.rs", module(day)))
+}
+
+/// Create the module of a new day in `src/days/yYYYY`, register it, and create its empty input
+/// file.
+pub fn new_day(year: u16, day: u8, input_dir: &str) -> io::Result<()> {
+    let path = source_path(year, day);
+    if path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", path.display()),
+        ));
+    }
+    std::fs::write(&path, fill(TEMPLATE, day))?;
+    let mod_rs = path.with_file_name("mod.rs");
+    std::fs::write(
+        &mod_rs,
+        register_module(&std::fs::read_to_string(&mod_rs)?, day),
+    )?;
+    let input_dir = Path::new(input_dir).join(year.to_string());
+    std::fs::create_dir_all(&input_dir)?;
+    let input = input_dir.join(format!("{}.txt", module(day)));
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(input)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TEMPLATE, fill, register_module};
+
+    #[test]
+    fn registration() {
+        let mod_rs = include_str!("../days/y2024/mod.rs");
+        for day in [1, 7, 25] {
+            let unregistered = mod_rs.replace(&format!("    day{:02},\n", day), "");
+            assert_eq!(register_module(&unregistered, day), mod_rs);
+        }
+        let registered = register_module(mod_rs, 26);
+        assert!(registered.ends_with("    day25,\n    day26,\n}\n"));
+        assert!(fill(TEMPLATE, 26).contains("pub struct Day26;\nregister_day!(26, Day26);\n"));
+    }
+}
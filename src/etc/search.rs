@@ -0,0 +1,190 @@
+//! Generic searches.
+#![allow(dead_code)]
+use crate::etc::grid::Topology;
+use crate::{Grid, Point};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Distance of every cell from the nearest source, moving in the four cardinal directions on
+/// passable cells, processing each level's frontier in parallel. Unreachable cells are `None`.
+///
+/// Only worth it on very large grids, [`pathfinding::bfs`](crate::etc::pathfinding::bfs) is
+/// the sequential search.
+pub fn par_bfs<T, P>(grid: &Grid<T>, sources: &[Point], passable: P) -> Grid<Option<u64>>
+where
+    T: Sync,
+    P: Fn(&T) -> bool + Sync,
+{
+    let visited: Vec<AtomicBool> = (0..grid.size()).map(|_| AtomicBool::new(false)).collect();
+    // claim a passable cell, return `true` if it was not visited yet
+    let visit = |pos: &Point, item: &T| {
+        passable(item) && !visited[grid.unchecked_index(pos)].swap(true, Ordering::Relaxed)
+    };
+
+    let mut distances = Grid::<Option<u64>>::default(grid.lines, grid.columns);
+    let mut frontier: Vec<Point> = sources
+        .iter()
+        .filter(|pos| grid.get(pos).is_some_and(|item| visit(pos, item)))
+        .copied()
+        .collect();
+    let mut dist = 0;
+    while !frontier.is_empty() {
+        for pos in &frontier {
+            distances.update(pos, Some(dist));
+        }
+        frontier = frontier
+            .par_iter()
+            .flat_map_iter(|pos| grid.neighbours(pos, Topology::Orthogonal))
+            .filter(|(at, item)| visit(at, item))
+            .map(|(at, _)| at)
+            .collect();
+        dist += 1;
+    }
+    distances
+}
+
+/// First value of `lo..hi` where the predicate is false, `hi` if it is true everywhere.
+///
+/// The predicate must be monotone over the range: true up to some value, then false. It is
+/// evaluated a logarithmic number of times.
+pub fn partition_point<P>(mut lo: usize, mut hi: usize, mut predicate: P) -> usize
+where
+    P: FnMut(usize) -> bool,
+{
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if predicate(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Number of paths from a state to terminal states, memoized by state.
+///
+/// States must form a directed acyclic graph. A terminal state counts for one path and is not
+/// expanded, a non-terminal state without successors is a dead end.
+pub struct CountMemo<S, F, T> {
+    memo: HashMap<S, u64>,
+    successors: F,
+    is_terminal: T,
+}
+
+impl<S, F, I, T> CountMemo<S, F, T>
+where
+    S: Hash + Eq + Clone,
+    F: Fn(&S) -> I,
+    I: IntoIterator<Item = S>,
+    T: Fn(&S) -> bool,
+{
+    pub fn new(successors: F, is_terminal: T) -> Self {
+        CountMemo {
+            memo: Default::default(),
+            successors,
+            is_terminal,
+        }
+    }
+
+    /// Count the paths from `state`, reusing the counts of previous calls.
+    pub fn count(&mut self, state: S) -> u64 {
+        if (self.is_terminal)(&state) {
+            return 1;
+        }
+        if let Some(count) = self.memo.get(&state) {
+            return *count;
+        }
+        let successors: Vec<S> = (self.successors)(&state).into_iter().collect();
+        let count = successors.into_iter().map(|next| self.count(next)).sum();
+        self.memo.insert(state, count);
+        count
+    }
+}
+
+/// Number of paths from `state` to terminal states, see [`CountMemo`].
+pub fn count_memo<S, F, I, T>(state: S, successors: F, is_terminal: T) -> u64
+where
+    S: Hash + Eq + Clone,
+    F: Fn(&S) -> I,
+    I: IntoIterator<Item = S>,
+    T: Fn(&S) -> bool,
+{
+    CountMemo::new(successors, is_terminal).count(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CountMemo, count_memo, par_bfs, partition_point};
+    use crate::etc::grid::Topology;
+    use crate::etc::pathfinding;
+    use crate::etc::rng::Rng;
+    use crate::{Grid, Point};
+
+    #[test]
+    fn parallel() {
+        let mut rng = Rng::new(18);
+        let (lines, columns) = (200, 300);
+        let grid = Grid::from_fn(lines, columns, |_| rng.chance(0.3));
+        let sources = [Point(0, 0), Point(100, 100), Point(199, 299)];
+        // nearest source of each cell, searching from every passable source in turn
+        let mut expected = Grid::<Option<u64>>::default(lines, columns);
+        for source in sources.iter().filter(|pos| !grid[**pos]) {
+            let search = pathfinding::bfs(
+                *source,
+                |pos| {
+                    grid.neighbours(pos, Topology::Orthogonal)
+                        .filter(|(_, wall)| !**wall)
+                        .map(|(at, _)| at)
+                        .collect::<Vec<_>>()
+                },
+                |_| false,
+            );
+            for (pos, dist) in search.distances {
+                let nearest = expected[pos].map_or(dist, |other| other.min(dist));
+                expected.update(&pos, Some(nearest));
+            }
+        }
+        assert_eq!(par_bfs(&grid, &sources, |wall| !wall).items, expected.items);
+    }
+
+    #[test]
+    fn count() {
+        // ways to climb 30 stairs by 1 or 2 steps
+        let ways = count_memo(
+            0,
+            |n| [n + 1, n + 2].into_iter().filter(|m| *m <= 30),
+            |n| *n == 30,
+        );
+        assert_eq!(ways, 1346269);
+
+        // lattice paths in a 16x16 grid, with dead ends outside the grid
+        let mut paths = CountMemo::new(
+            |&(x, y): &(u32, u32)| {
+                [(x + 1, y), (x, y + 1)]
+                    .into_iter()
+                    .filter(|p| p.0 <= 16 && p.1 <= 16)
+            },
+            |p| *p == (16, 16),
+        );
+        assert_eq!(paths.count((0, 0)), 601080390);
+        assert_eq!(paths.count((15, 15)), 2);
+        assert_eq!(paths.count((16, 16)), 1);
+    }
+
+    #[test]
+    fn partition() {
+        let squares_below = |limit: usize| partition_point(0, 100, |n| n * n < limit);
+        assert_eq!(squares_below(50), 8);
+        assert_eq!(squares_below(0), 0);
+        assert_eq!(squares_below(100_000), 100);
+        let mut probes = 0;
+        partition_point(10, 1_000_010, |n| {
+            probes += 1;
+            n < 123_456
+        });
+        assert!(probes <= 20);
+    }
+}
@@ -0,0 +1,163 @@
+//! Heuristics recognizing the shape of puzzle inputs, to catch inputs pasted in the wrong file.
+#![allow(dead_code)]
+
+use crate::etc::parse;
+
+/// Match a line against a pattern, where `#` stands for an integer with an optional minus sign,
+/// `*` for a non-empty run of ASCII alphanumeric characters, and any other character for itself.
+pub fn matches(line: &str, pattern: &str) -> bool {
+    let mut line = line.as_bytes();
+    for p in pattern.bytes() {
+        let len = match p {
+            b'#' => {
+                let sign = usize::from(line.first() == Some(&b'-'));
+                let digits = line[sign..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_digit())
+                    .count();
+                if digits == 0 {
+                    return false;
+                }
+                sign + digits
+            }
+            b'*' => {
+                let len = line
+                    .iter()
+                    .take_while(|b| b.is_ascii_alphanumeric())
+                    .count();
+                if len == 0 {
+                    return false;
+                }
+                len
+            }
+            _ if line.first() == Some(&p) => 1,
+            _ => return false,
+        };
+        line = &line[len..];
+    }
+    line.is_empty()
+}
+
+/// Whether the text has at least one non-blank line, and all of them satisfy the predicate once
+/// trimmed.
+pub fn all_lines<P>(text: &str, predicate: P) -> bool
+where
+    P: Fn(&str) -> bool,
+{
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .peekable();
+    lines.peek().is_some() && lines.all(predicate)
+}
+
+/// Whether the text is a non-empty list of integers with the given separator.
+pub fn is_int_list(text: &str, separator: &str) -> bool {
+    !text.is_empty() && text.split(separator).all(|item| matches(item, "#"))
+}
+
+/// Whether the lines form a non-empty rectangle of characters from the given alphabet.
+pub fn is_grid(lines: &[&str], alphabet: &str) -> bool {
+    let Some(width) = lines.first().map(|line| line.chars().count()) else {
+        return false;
+    };
+    width > 0
+        && lines
+            .iter()
+            .all(|line| line.chars().count() == width && line.chars().all(|c| alphabet.contains(c)))
+}
+
+/// The trimmed lines of the text, in blocks separated by blank lines, see [`parse::blocks`].
+pub fn blocks(text: &str) -> Vec<Vec<&str>> {
+    parse::blocks(text)
+        .into_iter()
+        .map(|block| block.lines().map(str::trim).collect())
+        .collect()
+}
+
+/// Common mistakes making a file unusable as a puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Problem {
+    /// Nothing but whitespace.
+    Empty,
+    /// A web page, e.g. an error page served for a bad session cookie.
+    Html,
+    /// The message the website serves instead of the input when not logged in.
+    LoggedOut,
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::Empty => write!(f, "the input is empty"),
+            Problem::Html => write!(
+                f,
+                "the input is a web page, fetch it again with a valid session cookie"
+            ),
+            Problem::LoggedOut => write!(
+                f,
+                "the input is the website asking to log in, fetch it again with a session cookie"
+            ),
+        }
+    }
+}
+
+/// The first problem found in a puzzle input, if any.
+pub fn problem(text: &str) -> Option<Problem> {
+    let start = text.trim_start().to_ascii_lowercase();
+    if start.is_empty() {
+        Some(Problem::Empty)
+    } else if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        Some(Problem::Html)
+    } else if start.starts_with("puzzle inputs differ by user") {
+        Some(Problem::LoggedOut)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Problem, all_lines, blocks, is_grid, is_int_list, matches, problem};
+
+    #[test]
+    fn patterns() {
+        assert!(matches("p=0,4 v=3,-3", "p=#,# v=#,#"));
+        assert!(matches("kh-tc", "*-*"));
+        assert!(!matches("p=0,4 v=3,-3", "p=#,#"));
+        assert!(!matches("Button A: X+94", "Button A: X=#"));
+        assert!(!matches("x-", "*-*"));
+        assert!(!matches("--1", "#"));
+    }
+
+    #[test]
+    fn texts() {
+        assert!(all_lines("1 2\n\n  3 4\n", |line| is_int_list(line, " ")));
+        assert!(!all_lines("\n  \n", |_| true));
+        assert!(!is_int_list("1  2", " "));
+        assert!(is_grid(&["..#", "#.."], ".#"));
+        assert!(!is_grid(&["..#", "#."], ".#"));
+        assert!(!is_grid(&["..#", "#.O"], ".#"));
+        assert!(!is_grid(&[], ".#"));
+        assert_eq!(
+            blocks("\n  a\n  b\n\n\n  c\n"),
+            vec![vec!["a", "b"], vec!["c"]]
+        );
+    }
+
+    #[test]
+    fn problems() {
+        assert_eq!(problem("3   4\n4   3\n"), None);
+        assert_eq!(problem(" \n\n"), Some(Problem::Empty));
+        assert_eq!(
+            problem("<!DOCTYPE html>\n<html lang=\"en-us\">"),
+            Some(Problem::Html)
+        );
+        assert_eq!(
+            problem("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            Some(Problem::LoggedOut)
+        );
+        assert_eq!(problem("3   4\r\n4   3\r\n"), None);
+    }
+}
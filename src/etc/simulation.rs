@@ -0,0 +1,208 @@
+//! Step-by-step simulations.
+#![allow(dead_code)]
+
+/// A simulation advancing one step at a time.
+pub trait Simulation {
+    /// Observable state of the simulation.
+    type State;
+
+    /// Advance the simulation by one step.
+    ///
+    /// Stepping a simulation that `is_done()` has no effect.
+    fn step(&mut self);
+
+    /// Current state of the simulation.
+    fn state(&self) -> &Self::State;
+
+    /// Whether the simulation reached its end.
+    fn is_done(&self) -> bool;
+}
+
+/// Driver for a [`Simulation`], counting the steps.
+///
+/// Step 0 is the initial state, step `k` is the state after `k` calls to `Simulation::step`.
+pub struct Harness<S: Simulation> {
+    simulation: S,
+    steps: usize,
+}
+
+impl<S: Simulation> Harness<S> {
+    pub fn new(simulation: S) -> Self {
+        Harness {
+            simulation,
+            steps: 0,
+        }
+    }
+
+    /// Number of steps executed so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn simulation(&self) -> &S {
+        &self.simulation
+    }
+
+    pub fn simulation_mut(&mut self) -> &mut S {
+        &mut self.simulation
+    }
+
+    pub fn into_inner(self) -> S {
+        self.simulation
+    }
+
+    pub fn state(&self) -> &S::State {
+        self.simulation.state()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.simulation.is_done()
+    }
+
+    /// Execute one step, return `false` if the simulation was already done.
+    pub fn step(&mut self) -> bool {
+        if self.simulation.is_done() {
+            false
+        } else {
+            self.simulation.step();
+            self.steps += 1;
+            true
+        }
+    }
+
+    /// Step until the simulation is done, return the total number of steps.
+    ///
+    /// Never returns for a simulation that never ends.
+    pub fn run(&mut self) -> usize {
+        while self.step() {}
+        self.steps
+    }
+
+    /// Step forward until step `k` is reached.
+    ///
+    /// Return `false` if the simulation ended before step `k`, or if step `k` is in the past.
+    pub fn seek(&mut self, k: usize) -> bool {
+        while self.steps < k {
+            if !self.step() {
+                return false;
+            }
+        }
+        self.steps == k
+    }
+
+    /// Step until the current state satisfies the predicate, including the current state.
+    ///
+    /// Return the step at which the predicate holds, or `None` if the simulation ended before.
+    pub fn run_until<P>(&mut self, mut predicate: P) -> Option<usize>
+    where
+        P: FnMut(&S::State) -> bool,
+    {
+        loop {
+            if predicate(self.simulation.state()) {
+                return Some(self.steps);
+            }
+            if !self.step() {
+                return None;
+            }
+        }
+    }
+
+    /// Feed the current state and then every `every`th state to the frame sink `f`, until the
+    /// simulation is done or `limit` steps were executed.
+    pub fn for_each_frame<F>(&mut self, every: usize, limit: Option<usize>, mut f: F)
+    where
+        F: FnMut(usize, &S::State),
+    {
+        assert!(every > 0, "frame interval must be positive");
+        loop {
+            if self.steps.is_multiple_of(every) {
+                f(self.steps, self.simulation.state());
+            }
+            if limit.is_some_and(|limit| self.steps >= limit) || !self.step() {
+                break;
+            }
+        }
+    }
+
+    /// Record the current state and then every `every`th state, until the simulation is done or
+    /// `limit` steps were executed.
+    pub fn record(&mut self, every: usize, limit: Option<usize>) -> Vec<(usize, S::State)>
+    where
+        S::State: Clone,
+    {
+        let mut frames = vec![];
+        self.for_each_frame(every, limit, |step, state| {
+            frames.push((step, state.clone()))
+        });
+        frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Harness, Simulation};
+
+    /// Count up to some limit.
+    struct Counter {
+        value: u64,
+        limit: u64,
+    }
+
+    impl Simulation for Counter {
+        type State = u64;
+
+        fn step(&mut self) {
+            self.value += 1;
+        }
+
+        fn state(&self) -> &u64 {
+            &self.value
+        }
+
+        fn is_done(&self) -> bool {
+            self.value >= self.limit
+        }
+    }
+
+    fn counter(limit: u64) -> Harness<Counter> {
+        Harness::new(Counter { value: 0, limit })
+    }
+
+    #[test]
+    fn run() {
+        let mut h = counter(10);
+        assert_eq!(h.run(), 10);
+        assert!(h.is_done());
+        assert_eq!(*h.state(), 10);
+        assert!(!h.step());
+        assert_eq!(h.steps(), 10);
+    }
+
+    #[test]
+    fn seek() {
+        let mut h = counter(10);
+        assert!(h.seek(4));
+        assert_eq!(*h.state(), 4);
+        assert!(h.seek(4));
+        assert!(!h.seek(3));
+        assert!(!h.seek(11));
+        assert_eq!(h.steps(), 10);
+    }
+
+    #[test]
+    fn run_until() {
+        let mut h = counter(10);
+        assert_eq!(h.run_until(|v| *v == 0), Some(0));
+        assert_eq!(h.run_until(|v| *v == 7), Some(7));
+        assert_eq!(h.run_until(|v| *v == 12), None);
+    }
+
+    #[test]
+    fn record() {
+        let mut h = counter(10);
+        assert_eq!(h.record(3, None), vec![(0, 0), (3, 3), (6, 6), (9, 9)]);
+        let mut h = counter(10);
+        assert_eq!(h.record(2, Some(5)), vec![(0, 0), (2, 2), (4, 4)]);
+        assert_eq!(h.steps(), 5);
+    }
+}
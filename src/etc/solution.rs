@@ -1,12 +1,34 @@
+use crate::etc::grid::GridParseError;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter, Result};
+use std::num::ParseIntError;
+use std::time::Duration;
 use Solution::*;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Solution {
     U64(u64),
     Usize(usize),
-    Str(String),
+    I64(i64),
+    I128(i128),
+    U128(u128),
+    /// A text answer, borrowed when it is known at compile time.
+    Str(Cow<'static, str>),
     Todo(),
+    /// A part that is not solved, or has nothing to solve, with the reason.
+    Pending(String),
+}
+
+impl Solution {
+    pub fn pending(reason: &str) -> Self {
+        Pending(reason.to_owned())
+    }
+
+    /// Whether this is an answer to the part, rather than a placeholder.
+    pub fn is_answer(&self) -> bool {
+        !matches!(self, Todo() | Pending(_))
+    }
 }
 
 impl Display for Solution {
@@ -14,8 +36,12 @@ impl Display for Solution {
         match self {
             U64(x) => x.fmt(f),
             Usize(x) => x.fmt(f),
+            I64(x) => x.fmt(f),
+            I128(x) => x.fmt(f),
+            U128(x) => x.fmt(f),
             Str(x) => x.fmt(f),
             Todo() => "TODO".fmt(f),
+            Pending(reason) => write!(f, "(pending: {})", reason),
         }
     }
 }
@@ -32,11 +58,25 @@ macro_rules! impl_from {
 
 impl_from!(u64, U64);
 impl_from!(usize, Usize);
-impl_from!(String, Str);
+impl_from!(i64, I64);
+impl_from!(i128, I128);
+impl_from!(u128, U128);
+
+impl From<String> for Solution {
+    fn from(sol: String) -> Self {
+        Self::Str(Cow::Owned(sol))
+    }
+}
 
 impl From<&str> for Solution {
     fn from(sol: &str) -> Self {
-        Self::Str(sol.to_owned())
+        Self::Str(Cow::Owned(sol.to_owned()))
+    }
+}
+
+impl From<Cow<'static, str>> for Solution {
+    fn from(sol: Cow<'static, str>) -> Self {
+        Self::Str(sol)
     }
 }
 
@@ -45,3 +85,111 @@ impl From<()> for Solution {
         Self::Todo()
     }
 }
+
+/// Answers of a day, with the time to solve each part.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DayResult {
+    pub day: u8,
+    pub part1: Solution,
+    pub part2: Solution,
+    /// Time to solve each part, `None` for parts that were not timed separately.
+    pub times: [Option<Duration>; 2],
+    /// Remarks on the answers, e.g. the answers of variants that disagree.
+    pub notes: Vec<String>,
+}
+
+impl DayResult {
+    /// The answers of a day, without timings nor notes.
+    pub fn new(day: u8, part1: Solution, part2: Solution) -> Self {
+        DayResult {
+            day,
+            part1,
+            part2,
+            times: [None; 2],
+            notes: vec![],
+        }
+    }
+}
+
+/// Error of a day without an implementation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NotImplemented {
+    pub day: u8,
+}
+
+impl Display for NotImplemented {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "day {} is not implemented", self.day)
+    }
+}
+
+/// Error of a day that cannot solve its input.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DayError {
+    /// The input does not have the shape of the puzzle inputs.
+    Malformed(String),
+    /// A well-formed input that the solver does not handle, e.g. a program of another shape.
+    Unsupported(String),
+}
+
+impl Display for DayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            DayError::Malformed(msg) => write!(f, "malformed input: {}", msg),
+            DayError::Unsupported(msg) => write!(f, "unsupported input: {}", msg),
+        }
+    }
+}
+
+impl From<GridParseError> for DayError {
+    fn from(err: GridParseError) -> Self {
+        DayError::Malformed(err.to_string())
+    }
+}
+
+impl From<ParseIntError> for DayError {
+    fn from(err: ParseIntError) -> Self {
+        DayError::Malformed(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DayResult, Solution};
+    use std::borrow::Cow;
+    use std::time::Duration;
+
+    #[test]
+    fn display() {
+        assert_eq!(Solution::from(-42i64).to_string(), "-42");
+        assert_eq!(Solution::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Solution::from(i128::MIN).to_string(), i128::MIN.to_string());
+        assert_eq!(
+            Solution::from(Cow::Borrowed("co,de,ka,ta")),
+            Solution::from("co,de,ka,ta".to_owned())
+        );
+        assert_eq!(
+            Solution::pending("not solved yet").to_string(),
+            "(pending: not solved yet)"
+        );
+    }
+    #[test]
+    fn json() {
+        let result = DayResult {
+            day: 13,
+            part1: Solution::from(-1i64),
+            part2: Solution::pending("not run"),
+            times: [Some(Duration::from_micros(1500)), None],
+            notes: vec![],
+        };
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            json,
+            r#"{"day":13,"part1":{"I64":-1},"part2":{"Pending":"not run"},"times":[{"secs":0,"nanos":1500000},null],"notes":[]}"#
+        );
+        assert_eq!(serde_json::from_str::<DayResult>(&json).unwrap(), result);
+        let answer = Solution::from("cqk,fph".to_owned());
+        let json = serde_json::to_string(&answer).unwrap();
+        assert_eq!(serde_json::from_str::<Solution>(&json).unwrap(), answer);
+    }
+}
@@ -0,0 +1,303 @@
+//! Visualizations of days: frames of their simulations, animated in the terminal or saved to
+//! files.
+#![allow(dead_code)]
+use crate::etc::render::{self, Color, Style};
+use crate::{Grid, Point};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Time each frame stays on screen by default, in the terminal or in GIFs.
+pub const FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// Size in GIF pixels of the pixels of images, tiny otherwise.
+pub const GIF_SCALE: usize = 4;
+
+/// A frame of a visualization.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Frame {
+    /// Text, possibly with the escape codes of [`render`].
+    Text(String),
+    /// An image, one RGB pixel per cell.
+    Image(Grid<[u8; 3]>),
+    /// An SVG drawing, see [`svg`].
+    Svg(String),
+    /// A graph in the DOT language of Graphviz.
+    Dot(String),
+}
+
+impl Frame {
+    /// Extension of the file of the frame.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Frame::Text(_) => "txt",
+            Frame::Image(_) => "ppm",
+            Frame::Svg(_) => "svg",
+            Frame::Dot(_) => "dot",
+        }
+    }
+
+    /// Content of the file of the frame, images being binary PPM files.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Frame::Text(text) | Frame::Svg(text) | Frame::Dot(text) => text.as_bytes().to_vec(),
+            Frame::Image(image) => {
+                let mut bytes =
+                    format!("P6\n{} {}\n255\n", image.columns, image.lines).into_bytes();
+                bytes.extend(image.iter().flatten());
+                bytes
+            }
+        }
+    }
+
+    /// The frame drawn in the terminal, images one pixel per character, graphs as their source.
+    pub fn to_terminal(&self) -> String {
+        match self {
+            Frame::Text(text) | Frame::Dot(text) => text.clone(),
+            Frame::Image(image) => render::render(image, |_, &[r, g, b]| {
+                Style::plain(' ').bg(Color::Rgb(r, g, b))
+            }),
+            Frame::Svg(_) => "An SVG drawing, save it to a directory to see it\n".to_owned(),
+        }
+    }
+}
+
+/// Size in SVG units of the cells of grids drawn by [`svg`].
+const SVG_CELL: usize = 12;
+
+/// Draw a grid as SVG, styling each cell like [`render::render`] does in the terminal: the
+/// background colour fills the cell, and the glyph is drawn in the foreground colour, light grey
+/// by default, on a dark background like the one of terminals.
+pub fn svg<T, F>(grid: &Grid<T>, style: F) -> String
+where
+    F: Fn(Point, &T) -> Style,
+{
+    let (width, height) = (grid.columns * SVG_CELL, grid.lines * SVG_CELL);
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         font-family=\"monospace\" font-size=\"{}\" text-anchor=\"middle\">\n\
+         <rect width=\"{width}\" height=\"{height}\" fill=\"#1e1e1e\"/>\n",
+        SVG_CELL - 2
+    );
+    grid.for_each_with_position(|pos, cell| {
+        let style = style(pos, cell);
+        let (x, y) = (pos.1 as usize * SVG_CELL, pos.0 as usize * SVG_CELL);
+        if let Some(bg) = style.bg {
+            out.push_str(&format!(
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{SVG_CELL}\" height=\"{SVG_CELL}\" fill=\"{}\"/>\n",
+                bg.css()
+            ));
+        }
+        if !style.glyph.is_whitespace() {
+            let glyph = match style.glyph {
+                '<' => "&lt;".to_owned(),
+                '>' => "&gt;".to_owned(),
+                '&' => "&amp;".to_owned(),
+                glyph => glyph.to_string(),
+            };
+            out.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" fill=\"{}\"{}>{glyph}</text>\n",
+                x + SVG_CELL / 2,
+                y + SVG_CELL - 3,
+                style.fg.unwrap_or(Color::Rgb(204, 204, 204)).css(),
+                if style.bold { " font-weight=\"bold\"" } else { "" },
+            ));
+        }
+    });
+    out.push_str("</svg>\n");
+    out
+}
+
+/// A day showing how it gets its answers, frame after frame, see [`crate::days::Day::visualizer`].
+pub trait Visualize {
+    /// Feed the frames of the visualization of the input to `sink`, in order.
+    ///
+    /// Simulations keep one frame `every` that many steps when given, the day choosing otherwise.
+    fn frames(&self, input: &str, every: Option<usize>, sink: &mut dyn FnMut(Frame));
+}
+
+/// Show the frames in the terminal, each one replacing the previous one. Return the number of
+/// frames.
+pub fn animate(
+    visualizer: &dyn Visualize,
+    input: &str,
+    every: Option<usize>,
+    delay: Duration,
+) -> usize {
+    let mut count = 0;
+    let mut stdout = io::stdout();
+    visualizer.frames(input, every, &mut |frame| {
+        // clear the screen and move to its top-left corner
+        let _ = write!(stdout, "\x1b[2J\x1b[H{}", frame.to_terminal());
+        let _ = stdout.flush();
+        std::thread::sleep(delay);
+        count += 1;
+    });
+    count
+}
+
+/// Write the frames to `frame_NNNNN.ext` files of a directory, created if needed. Return the
+/// number of frames.
+pub fn save(
+    visualizer: &dyn Visualize,
+    input: &str,
+    every: Option<usize>,
+    dir: &Path,
+) -> io::Result<usize> {
+    std::fs::create_dir_all(dir)?;
+    let mut count = 0;
+    let mut result = Ok(());
+    visualizer.frames(input, every, &mut |frame| {
+        if result.is_ok() {
+            let path = dir.join(format!("frame_{:05}.{}", count, frame.extension()));
+            result = std::fs::write(path, frame.to_bytes());
+            count += 1;
+        }
+    });
+    result.map(|()| count)
+}
+
+/// Write the frames to an animated GIF looping forever, each pixel being a `scale` x `scale`
+/// square. Return the number of frames.
+///
+/// All frames must be images, the size of the animation being the one of the first frame. No
+/// file is written without frames.
+#[cfg(feature = "gif")]
+pub fn save_gif(
+    visualizer: &dyn Visualize,
+    input: &str,
+    every: Option<usize>,
+    path: &Path,
+    delay: Duration,
+    scale: usize,
+) -> io::Result<usize> {
+    let mut encoder = None;
+    let mut count = 0;
+    let mut result = Ok(());
+    // GIF delays are in hundredths of a second
+    let delay = (delay.as_millis() / 10).try_into().unwrap_or(u16::MAX);
+    visualizer.frames(input, every, &mut |frame| {
+        if result.is_ok() {
+            result = write_gif_frame(path, &mut encoder, &frame, delay, scale);
+            count += 1;
+        }
+    });
+    result.map(|()| count)
+}
+
+/// Without the `gif` feature, GIFs cannot be written.
+#[cfg(not(feature = "gif"))]
+pub fn save_gif(
+    _visualizer: &dyn Visualize,
+    _input: &str,
+    _every: Option<usize>,
+    _path: &Path,
+    _delay: Duration,
+    _scale: usize,
+) -> io::Result<usize> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "GIF export needs the gif feature",
+    ))
+}
+
+/// Append an image to a GIF, creating it with the size of the image if needed.
+#[cfg(feature = "gif")]
+fn write_gif_frame(
+    path: &Path,
+    encoder: &mut Option<gif::Encoder<std::fs::File>>,
+    frame: &Frame,
+    delay: u16,
+    scale: usize,
+) -> io::Result<()> {
+    let Frame::Image(image) = frame else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "only images can be written to a GIF",
+        ));
+    };
+    let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "image too large for a GIF");
+    let width: u16 = (image.columns * scale)
+        .try_into()
+        .map_err(|_| too_large())?;
+    let height: u16 = (image.lines * scale).try_into().map_err(|_| too_large())?;
+    let pixels: Vec<u8> = (0..image.lines * scale)
+        .flat_map(|y| (0..image.columns * scale).map(move |x| (y / scale, x / scale)))
+        .flat_map(|(y, x)| image[crate::Point(y as i64, x as i64)])
+        .collect();
+    if encoder.is_none() {
+        let file = std::fs::File::create(path)?;
+        let mut started = gif::Encoder::new(file, width, height, &[]).map_err(io::Error::other)?;
+        started
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+        *encoder = Some(started);
+    }
+    let mut gif_frame = gif::Frame::from_rgb(width, height, &pixels);
+    gif_frame.delay = delay;
+    encoder
+        .as_mut()
+        .unwrap()
+        .write_frame(&gif_frame)
+        .map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Frame, Visualize, save, svg};
+    use crate::Grid;
+    use crate::etc::render::{Color, Style};
+
+    /// Counting down from the number of the input.
+    struct Countdown;
+
+    impl Visualize for Countdown {
+        fn frames(&self, input: &str, _every: Option<usize>, sink: &mut dyn FnMut(Frame)) {
+            for n in (0..input.parse::<u8>().unwrap()).rev() {
+                sink(Frame::Text(format!("{}\n", n)));
+            }
+            sink(Frame::Image(Grid::from_fn(1, 2, |_| [255, 0, 0])));
+        }
+    }
+
+    #[test]
+    fn frames() {
+        let image = Frame::Image(Grid::from_fn(1, 2, |pos| [pos.1 as u8, 2, 3]));
+        assert_eq!(image.to_bytes(), b"P6\n2 1\n255\n\x00\x02\x03\x01\x02\x03");
+        assert_eq!(
+            image.to_terminal(),
+            "\x1b[48;2;0;2;3m \x1b[0m\x1b[48;2;1;2;3m \x1b[0m\n"
+        );
+        let text = Frame::Text("#.\n".to_owned());
+        assert_eq!(
+            (text.extension(), text.to_terminal().as_str()),
+            ("txt", "#.\n")
+        );
+    }
+
+    #[test]
+    fn drawing() {
+        let grid = Grid::new("#<\n");
+        let drawing = svg(&grid, |_, &c| match c {
+            '#' => Style::plain(' ').bg(Color::Rgb(1, 2, 3)),
+            c => Style::plain(c).fg(Color::Red).bold(),
+        });
+        assert!(drawing.starts_with("<svg "));
+        assert!(drawing.contains(r##"<rect x="0" y="0" width="12" height="12" fill="#010203"/>"##));
+        assert!(drawing.contains(r#"fill="red" font-weight="bold">&lt;</text>"#));
+        assert_eq!(Frame::Svg(drawing).extension(), "svg");
+    }
+
+    #[test]
+    fn saved() {
+        let dir = std::env::temp_dir().join("aoc24-rust-viz");
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(save(&Countdown, "3", None, &dir).unwrap(), 4);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("frame_00000.txt")).unwrap(),
+            "2\n"
+        );
+        assert!(dir.join("frame_00003.ppm").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,52 @@
+//! Polling files for changes, to re-run days while working on them.
+#![allow(dead_code)]
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Delay between two polls of the watched files.
+pub const INTERVAL: Duration = Duration::from_millis(250);
+
+/// Modification times of files, `None` for missing ones.
+pub fn modified(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/// Block until one of the files is modified, created or removed since the given modification
+/// times, see [`modified`].
+pub fn wait_for_change(paths: &[PathBuf], since: &[Option<SystemTime>]) {
+    while modified(paths) == since {
+        std::thread::sleep(INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{modified, wait_for_change};
+    use std::fs::File;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn changes() {
+        let path = std::env::temp_dir().join("aoc24-rust-watch.txt");
+        let missing = std::env::temp_dir().join("aoc24-rust-watch-missing.txt");
+        let paths = [path.clone(), missing];
+        std::fs::write(&path, "1").unwrap();
+        let since = modified(&paths);
+        assert!(since[0].is_some());
+        assert_eq!(since[1], None);
+
+        let touch = path.clone();
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            let file = File::options().write(true).open(touch).unwrap();
+            file.set_modified(SystemTime::UNIX_EPOCH).unwrap();
+        });
+        wait_for_change(&paths, &since);
+        writer.join().unwrap();
+        assert_eq!(modified(&paths)[0], Some(SystemTime::UNIX_EPOCH));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,200 @@
+//! Talking to the Advent of Code website with `curl`, authenticated by the session cookie.
+#![allow(dead_code)]
+use crate::etc::config;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies the tool to the website maintainers, as they ask automated tools to.
+const USER_AGENT: &str = "aoc24-rust (https://github.com/quentin/aoc24-rust)";
+
+/// Environment variable holding the session cookie, the file at `session_path` of the
+/// configuration is read otherwise.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+pub fn input_url(year: u16, day: u8) -> String {
+    format!("{}/{}/day/{}/input", BASE_URL, year, day)
+}
+
+/// The session cookie of the website.
+pub fn session() -> Result<String, String> {
+    if let Ok(session) = std::env::var(SESSION_VAR) {
+        return Ok(session.trim().to_owned());
+    }
+    let path = config::get().session_path.as_ref().ok_or_else(|| {
+        format!(
+            "no session cookie, set {} or session_path in {}",
+            SESSION_VAR,
+            config::PATH
+        )
+    })?;
+    std::fs::read_to_string(path)
+        .map(|session| session.trim().to_owned())
+        .map_err(|err| format!("cannot read the session cookie from {}: {}", path, err))
+}
+
+pub fn answer_url(year: u16, day: u8) -> String {
+    format!("{}/{}/day/{}/answer", BASE_URL, year, day)
+}
+
+/// Body of a request to the website, a POST of the form fields if any, a GET otherwise.
+pub fn request(url: &str, session: &str, form: &[(&str, &str)]) -> Result<String, String> {
+    let mut command = Command::new("curl");
+    // the cookie is read from the standard input, to keep it out of the process list
+    command.args(["--silent", "--show-error", "--fail", "--header", "@-"]);
+    command.args(["--user-agent", USER_AGENT]);
+    for (name, value) in form {
+        command.args(["--data-urlencode", &format!("{}={}", name, value)]);
+    }
+    let mut child = command
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("cannot run curl: {}", err))?;
+    let mut stdin = child.stdin.take().unwrap();
+    writeln!(stdin, "Cookie: session={}", session).map_err(|err| err.to_string())?;
+    drop(stdin);
+    let output = child.wait_with_output().map_err(|err| err.to_string())?;
+    if !output.status.success() {
+        return Err(format!(
+            "{}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|err| err.to_string())
+}
+
+/// Download the puzzle input of a day.
+pub fn fetch_input(year: u16, day: u8) -> Result<String, String> {
+    request(&input_url(year, day), &session()?, &[])
+}
+
+/// Outcome of an answer submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint.
+    Wrong,
+    /// Submitted too soon after the previous answer, with the time left to wait.
+    RateLimited(String),
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+    /// A response the verdict could not be read from.
+    Unknown,
+}
+
+impl Verdict {
+    /// Read the verdict in the page answering a submission.
+    pub fn parse(page: &str) -> Self {
+        if page.contains("That's the right answer") {
+            Verdict::Correct
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if page.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if page.contains("You gave an answer too recently") {
+            let wait = page
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map_or("some time", |(wait, _)| wait);
+            Verdict::RateLimited(wait.to_owned())
+        } else if page.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether the answer is known to be wrong.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited(wait) => write!(f, "rate limited, {} left to wait", wait),
+            Verdict::WrongLevel => write!(f, "already solved or locked"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Submit the answer of a part of a day.
+pub fn submit(year: u16, day: u8, part: u8, answer: &str) -> Result<Verdict, String> {
+    let level = part.to_string();
+    let page = request(
+        &answer_url(year, day),
+        &session()?,
+        &[("level", &level), ("answer", answer)],
+    )?;
+    Ok(Verdict::parse(&page))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Verdict, answer_url, input_url};
+
+    #[test]
+    fn urls() {
+        assert_eq!(
+            input_url(2024, 7),
+            "https://adventofcode.com/2024/day/7/input"
+        );
+        assert_eq!(
+            answer_url(2024, 25),
+            "https://adventofcode.com/2024/day/25/answer"
+        );
+    }
+
+    #[test]
+    fn verdicts() {
+        let page = |text: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", text);
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's the right answer! You are one gold star closer to finding the Chief \
+                 Historian."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too high. Please wait one minute \
+                 before trying again."
+            )),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::parse(&page("That's not the right answer. If you're stuck...")),
+            Verdict::Wrong
+        );
+        let limited = Verdict::parse(&page(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 38s left to wait.",
+        ));
+        assert_eq!(limited, Verdict::RateLimited("38s".to_owned()));
+        assert_eq!(limited.to_string(), "rate limited, 38s left to wait");
+        assert_eq!(
+            Verdict::parse(&page("You don't seem to be solving the right level.")),
+            Verdict::WrongLevel
+        );
+        assert_eq!(Verdict::parse("<html></html>"), Verdict::Unknown);
+        assert!(Verdict::TooLow.is_wrong() && !Verdict::Correct.is_wrong());
+    }
+}
@@ -1,226 +1,1259 @@
+mod cli;
 mod days;
 mod etc;
+#[cfg(feature = "tui")]
+mod tui;
 
-use days::*;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use cli::{Cli, Command, RunArgs, VizTarget};
+use days::y2024;
+use days::{DEFAULT_YEAR, Day};
+use etc::answers::{self, Expected};
+use etc::bench;
+use etc::checkpoint;
+use etc::config::{self, Config};
+use etc::crypt;
+use etc::examples;
 use etc::grid::{Grid, Point};
-use etc::solution::Solution;
-use std::env;
-
-pub type SolutionPair = (Solution, Solution);
-
-fn solve_day(day: u8) -> SolutionPair {
-    let input = std::fs::read_to_string(format!("./input/day{:0>2}.txt", day)).unwrap();
-    match day {
-        1 => day01::solve(input),
-        2 => day02::solve(input),
-        3 => day03::solve(input),
-        4 => day04::solve(input),
-        5 => day05::solve(input),
-        6 => day06::solve(input),
-        7 => day07::solve(input),
-        8 => day08::solve(input),
-        9 => day09::solve(input),
-        10 => day10::solve(input),
-        11 => day11::solve(input),
-        12 => day12::solve(input),
-        13 => day13::solve(input),
-        14 => day14::solve(input),
-        15 => day15::solve(input),
-        16 => day16::solve(input),
-        17 => day17::solve(input),
-        18 => day18::solve(input),
-        19 => day19::solve(input),
-        20 => day20::solve(input),
-        21 => day21::solve(input),
-        22 => day22::solve(input),
-        23 => day23::solve(input),
-        24 => day24::solve(input),
-        25 => day25::solve(input),
-        _ => unimplemented!(),
+use etc::parse;
+use etc::render;
+use etc::report::{Report, Row};
+use etc::results;
+use etc::rng::Rng;
+use etc::scaffold;
+use etc::shape;
+use etc::solution::{DayError, DayResult, NotImplemented, Solution};
+use etc::viz;
+use etc::watch;
+use etc::website::{self, Verdict};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+
+/// A named implementation of a day.
+pub type Variant = (&'static str, fn(String) -> Result<DayResult, DayError>);
+
+/// Read the puzzle input of a day from the first of its candidate paths that exists, see
+/// [`find_input`], decrypting it if needed. Without any, the input embedded in the binary is read,
+/// if any.
+fn read_input(year: u16, profile: Option<&str>, day: u8) -> Result<String, String> {
+    let candidates = input_paths(year, profile, day);
+    match find_input(&candidates) {
+        Some(path) if path.ends_with(crypt::EXTENSION) => {
+            let input = crypt::key()
+                .and_then(|key| crypt::decrypt(&path, &key))
+                .map_err(|err| format!("Cannot decrypt {}: {}", path, err))?;
+            load_input(day, &path, &input)
+        }
+        Some(path) => read_input_file(day, &path),
+        None => match embedded_input(year, day).filter(|_| profile.is_none()) {
+            Some(input) => load_input(day, "the embedded inputs", input),
+            None => Err(format!(
+                "No input for day {}, tried {}",
+                day,
+                candidates.join(", ")
+            )),
+        },
     }
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        panic!("Please provide the day(s)");
+/// Input baked into the binary with the `embed-inputs` feature.
+#[cfg(feature = "embed-inputs")]
+fn embedded_input(year: u16, day: u8) -> Option<&'static str> {
+    etc::embedded::get(year, day)
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+fn embedded_input(_year: u16, _day: u8) -> Option<&'static str> {
+    None
+}
+
+/// Candidate paths of the puzzle input of a day, in order: the `input_path` templates of the
+/// configuration, `<input directory>/<year>/dayNN.txt`, and `<input directory>/dayNN.txt` for
+/// inputs of the default year saved before years were supported. The inputs of a profile are in
+/// the `<profile>` subdirectory of the input directory instead.
+fn input_paths(year: u16, profile: Option<&str>, day: u8) -> Vec<String> {
+    let config = config::get();
+    let mut paths: Vec<String> = config
+        .input_paths
+        .iter()
+        .filter_map(|template| config::expand(template, year, day, profile).ok().flatten())
+        .collect();
+    let mut dir = config.input_dir.clone();
+    if let Some(profile) = profile {
+        dir = format!("{}/{}", dir, profile);
+    }
+    paths.push(format!("{}/{}/day{:0>2}.txt", dir, year, day));
+    if year == DEFAULT_YEAR {
+        paths.push(format!("{}/day{:0>2}.txt", dir, day));
+    }
+    paths
+}
+
+/// The first candidate path of an input that exists, or its encrypted version, see
+/// [`crypt::encrypted_path`].
+fn find_input(candidates: &[String]) -> Option<String> {
+    candidates
+        .iter()
+        .flat_map(|path| [path.clone(), crypt::encrypted_path(path)])
+        .find(|path| Path::new(path).exists())
+}
+
+/// Path of the puzzle input of a day: the first candidate that exists, or the preferred one.
+fn input_path(year: u16, profile: Option<&str>, day: u8) -> String {
+    let mut candidates = input_paths(year, profile, day);
+    find_input(&candidates).unwrap_or_else(|| candidates.swap_remove(0))
+}
+
+/// Read the puzzle input of a day from any file.
+fn read_input_file(day: u8, path: &str) -> Result<String, String> {
+    let input =
+        std::fs::read_to_string(path).map_err(|err| format!("Cannot read {}: {}", path, err))?;
+    load_input(day, path, &input)
+}
+
+/// Normalize an input, see [`parse::normalize`], rejecting inputs the days cannot parse with the
+/// problem named, and warning about inputs of other days.
+fn load_input(day: u8, origin: &str, input: &str) -> Result<String, String> {
+    if let Some(problem) = shape::problem(input) {
+        return Err(format!(
+            "Invalid input of day {} in {}: {}",
+            day, origin, problem
+        ));
+    }
+    let input = parse::normalize(input);
+    warn_wrong_day(day, &input);
+    Ok(input)
+}
+
+/// Where the puzzle inputs come from.
+#[derive(Debug, Clone, PartialEq)]
+enum InputSource {
+    /// The `dayNN.txt` files of a year in the input directory, or in the directory of a profile.
+    Directory(u16, Option<String>),
+    File(String),
+    /// The standard input, holding the input of a single day.
+    Stdin,
+    /// An example of the puzzle texts, see [`examples::get`].
+    Example(u8),
+}
+
+impl InputSource {
+    fn read(&self, day: u8) -> Result<String, String> {
+        match self {
+            InputSource::Directory(year, profile) => read_input(*year, profile.as_deref(), day),
+            InputSource::File(path) => read_input_file(day, path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|err| format!("Cannot read the standard input: {}", err))?;
+                load_input(day, "the standard input", &input)
+            }
+            InputSource::Example(index) => examples::get(day, *index as usize)
+                .map(str::to_owned)
+                .ok_or_else(|| format!("Day {} has no example {}", day, index)),
+        }
+    }
+}
+
+/// The solver of a day.
+fn solver(day: u8) -> Result<&'static dyn Day, NotImplemented> {
+    y2024::day(day).ok_or(NotImplemented { day })
+}
+
+/// The implemented days, in order.
+fn implemented() -> impl Iterator<Item = u8> {
+    y2024::DAYS.iter().map(|(day, _)| *day)
+}
+
+type Answers = Vec<(&'static str, Solution)>;
+
+/// Answer to a part agreed on by all variants, see [`cross_validate`].
+type CrossValidated = Result<Solution, Answers>;
+
+/// The answer to a part if all variants agree, otherwise the answer of every variant.
+///
+/// Variants that do not solve the part, answering `Solution::Todo()` or a pending solution, are
+/// ignored.
+fn cross_validate(answers: Answers) -> Result<Solution, Answers> {
+    let answers: Answers = answers
+        .into_iter()
+        .filter(|(_, answer)| answer.is_answer())
+        .collect();
+    match answers.first() {
+        None => Ok(Solution::Todo()),
+        Some((_, first)) if answers.iter().all(|(_, answer)| answer == first) => Ok(first.clone()),
+        Some(_) => Err(answers),
+    }
+}
+
+/// Solve a day with all its variants, see [`cross_validate`], failing if any variant fails.
+fn solve_paranoid(
+    solver: &dyn Day,
+    input: String,
+) -> Result<(CrossValidated, CrossValidated), DayError> {
+    let answers = solver
+        .variants()
+        .iter()
+        .map(|(name, solve)| {
+            let result = solve(input.clone())?;
+            Ok(((*name, result.part1), (*name, result.part2)))
+        })
+        .collect::<Result<Vec<_>, DayError>>()?;
+    let (p1, p2): (Answers, Answers) = answers.into_iter().unzip();
+    Ok((cross_validate(p1), cross_validate(p2)))
+}
+
+/// Show the frames of the visualization of a day requested with `--viz`, see [`viz::Visualize`],
+/// GIF paths being suffixed with the day when `several` days are visualized.
+fn visualize(day: u8, solver: &dyn Day, input: &str, args: &RunArgs, several: bool) {
+    let Some(target) = &args.viz else {
+        return;
+    };
+    let every = args.viz_every.map(|every| every as usize);
+    let delay = args
+        .viz_delay
+        .map_or(viz::FRAME_DELAY, Duration::from_millis);
+    let Some(visualizer) = solver.visualizer() else {
+        eprintln!("Day {} has no visualization", day);
+        return;
+    };
+    match target {
+        VizTarget::Terminal => {
+            viz::animate(visualizer, input, every, delay);
+        }
+        VizTarget::Directory(dir) => {
+            let dir = Path::new(dir).join(format!("day{:02}", day));
+            match viz::save(visualizer, input, every, &dir) {
+                Ok(count) => println!("   {} frames written to {}", count, dir.display()),
+                Err(err) => eprintln!("Cannot write the frames to {}: {}", dir.display(), err),
+            }
+        }
+        VizTarget::Gif(path) => {
+            let path = match path.strip_suffix(".gif") {
+                Some(stem) if several => format!("{}-day{:02}.gif", stem, day),
+                _ => path.clone(),
+            };
+            let written = viz::save_gif(
+                visualizer,
+                input,
+                every,
+                Path::new(&path),
+                delay,
+                viz::GIF_SCALE,
+            );
+            match written {
+                Ok(count) => println!("   {} frames written to {}", count, path),
+                Err(err) => eprintln!("Cannot write the frames to {}: {}", path, err),
+            }
+        }
+    }
+}
+
+fn print_part(part: u8, answer: &Result<Solution, Answers>) {
+    match answer {
+        Ok(answer) => println!("   Part {}: {}", part, answer),
+        Err(answers) => {
+            println!("   Part {}: variants disagree", part);
+            for (name, answer) in answers {
+                println!("      {}: {}", name, answer);
+            }
+        }
+    }
+}
+
+/// Print whether answers match the expected ones, return the number of mismatches.
+fn check_answers(
+    expected: Option<&Expected>,
+    answers: &[(u8, Result<Solution, Answers>)],
+) -> usize {
+    let mut mismatches = 0;
+    for (part, answer) in answers {
+        let expected = expected.and_then(|expected| expected[*part as usize - 1].as_ref());
+        match (expected, answer) {
+            (_, Ok(Solution::Pending(reason))) => println!("   Check {}: SKIP, {}", part, reason),
+            (None, _) => println!("   Check {}: no expected answer", part),
+            (Some(expected), Ok(answer)) if answer.to_string() == *expected => {
+                println!("   Check {}: PASS", part)
+            }
+            (Some(expected), _) => {
+                println!("   Check {}: FAIL, expected {}", part, expected);
+                mismatches += 1;
+            }
+        }
+    }
+    mismatches
+}
+
+/// Row of the table of a run, see [`Report`].
+fn report_row(
+    day: u8,
+    answers: &[(u8, Result<Solution, Answers>)],
+    times: [Option<Duration>; 2],
+) -> Row {
+    let mut row = Row {
+        day,
+        answers: [None, None],
+        times,
+    };
+    for (part, answer) in answers {
+        row.answers[*part as usize - 1] = Some(match answer {
+            Ok(answer) => answer.to_string(),
+            Err(_) => "variants disagree".to_owned(),
+        });
+    }
+    row
+}
+
+/// Result of a day for the JSON output format, parts that were not solved being pending.
+fn day_result(
+    day: u8,
+    answers: &[(u8, Result<Solution, Answers>)],
+    times: [Option<Duration>; 2],
+) -> DayResult {
+    let mut parts = [Solution::pending("not run"), Solution::pending("not run")];
+    let mut notes = vec![];
+    for (part, answer) in answers {
+        parts[*part as usize - 1] = match answer {
+            Ok(answer) => answer.clone(),
+            Err(answers) => {
+                for (name, answer) in answers {
+                    notes.push(format!("part {} of {}: {}", part, name, answer));
+                }
+                Solution::pending("variants disagree")
+            }
+        };
     }
+    let [part1, part2] = parts;
+    DayResult {
+        day,
+        part1,
+        part2,
+        times,
+        notes,
+    }
+}
+
+/// Whether the input has the expected shape for the day, see [`warn_wrong_day`].
+fn looks_like(day: u8, input: &str) -> bool {
+    solver(day).is_ok_and(|solver| solver.looks_like(input))
+}
+
+/// Warn when an input does not look like the puzzle input of its day, pointing to the days it
+/// looks like instead.
+fn warn_wrong_day(day: u8, input: &str) {
+    if looks_like(day, input) {
+        return;
+    }
+    let candidates: Vec<String> = implemented()
+        .filter(|other| looks_like(*other, input))
+        .map(|other| other.to_string())
+        .collect();
+    if candidates.is_empty() {
+        eprintln!(
+            "Warning: the input of day {} does not look like a puzzle input",
+            day
+        );
+    } else {
+        eprintln!(
+            "Warning: the input of day {} looks like the input of day {}",
+            day,
+            candidates.join(" or ")
+        );
+    }
+}
 
-    let days: Vec<u8> = args[1..]
+/// Append the answers of a day to the results database, skipping unsolved or disputed parts.
+fn record_answers(
+    day: u8,
+    commit: &Option<String>,
+    input_hash: u64,
+    micros: u64,
+    answers: &[(u8, Result<Solution, Answers>)],
+) {
+    let records: Vec<results::Record> = answers
         .iter()
-        .map(|x| {
-            x.parse()
-                .unwrap_or_else(|v| panic!("Not a valid day: {}", v))
+        .filter_map(|(part, answer)| match answer {
+            Ok(answer) if answer.is_answer() => Some(results::Record {
+                timestamp: results::now(),
+                commit: commit.clone(),
+                day,
+                part: *part,
+                answer: answer.to_string(),
+                micros,
+                input_hash,
+            }),
+            _ => None,
         })
         .collect();
+    if let Err(err) = results::append(results::PATH, &records) {
+        eprintln!("Could not record results: {}", err);
+    }
+}
+
+/// Random input of a day, see [`Day::generate`].
+fn generate_day(day: u8, size: usize, rng: &mut Rng) -> Result<String, NotImplemented> {
+    Ok(solver(day)?.generate(size, rng))
+}
+
+/// Print a synthetic puzzle input.
+fn generate(day: u8, size: usize, seed: u64) {
+    match generate_day(day, size, &mut Rng::new(seed)) {
+        Ok(input) => print!("{}", input),
+        Err(err) => eprintln!("{}", err),
+    }
+}
+
+/// Print the implemented days, with their variants and whether they explain or visualize their
+/// answers.
+fn list() {
+    for (day, solver) in y2024::DAYS {
+        let mut line = format!("Day {:02}", day);
+        let names: Vec<&str> = solver.variants().iter().map(|(name, _)| *name).collect();
+        if !names.is_empty() {
+            line.push_str(&format!(", variants: {}", names.join(" ")));
+        }
+        if solver.explainer().is_some() {
+            line.push_str(", explained");
+        }
+        if solver.visualizer().is_some() {
+            line.push_str(", visualized");
+        }
+        println!("{}", line);
+    }
+}
+
+/// Download the puzzle input of a day, unless it is already in the input directory.
+fn fetch(year: u16, day: u8, force: bool) {
+    let cached = fs::metadata(input_path(year, None, day)).is_ok_and(|file| file.len() > 0);
+    if cached && !force {
+        println!("The input of day {} is already there", day);
+        return;
+    }
+    let path = input_paths(year, None, day).swap_remove(0);
+    let written = website::fetch_input(year, day).and_then(|input| {
+        if let Some(problem) = shape::problem(&input) {
+            return Err(problem.to_string());
+        }
+        warn_wrong_day(day, &input);
+        fs::create_dir_all(Path::new(&path).parent().unwrap())
+            .and_then(|()| fs::write(&path, input))
+            .map_err(|err| err.to_string())
+    });
+    match written {
+        Ok(()) => println!("Saved the input of day {} to {}", day, path),
+        Err(err) => eprintln!("Could not fetch the input of day {}: {}", day, err),
+    }
+}
+
+/// Encrypt the plain inputs of days next to them, see [`crypt`].
+fn encrypt(year: u16, days: &[u8]) {
+    let key = crypt::key().unwrap_or_else(|msg| exit_with(&msg));
+    for day in days {
+        let path = input_path(year, None, *day);
+        if path.ends_with(crypt::EXTENSION) {
+            println!("The input of day {} is already encrypted", day);
+            continue;
+        }
+        let encrypted = crypt::encrypted_path(&path);
+        let written = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|input| crypt::encrypt(&input, &encrypted, &key));
+        match written {
+            Ok(()) => println!("Encrypted the input of day {} to {}", day, encrypted),
+            Err(err) => eprintln!("Could not encrypt {}: {}", path, err),
+        }
+    }
+}
+
+/// Solve a part of a day and submit its answer, unless it was already submitted.
+fn submit(year: u16, day: u8, part: u8) {
+    let solver = match solver(day) {
+        Ok(solver) => solver,
+        Err(err) => return eprintln!("{}", err),
+    };
+    let input = match InputSource::Directory(year, None).read(day) {
+        Ok(input) => input,
+        Err(err) => return eprintln!("{}", err),
+    };
+    let answer = match part {
+        1 => solver.part1(&input),
+        _ => solver.part2(&input),
+    };
+    let answer = match answer {
+        Ok(answer) => answer,
+        Err(err) => return eprintln!("Cannot solve part {} of day {}: {}", part, day, err),
+    };
+    if !answer.is_answer() {
+        return eprintln!("Part {} of day {} has no answer: {}", part, day, answer);
+    }
+    let answer = answer.to_string();
+    let submissions = results::load_submissions(results::SUBMISSIONS_PATH).unwrap_or_default();
+    if let Some(previous) = submissions
+        .iter()
+        .find(|s| (s.year, s.day, s.part) == (year, day, part) && s.answer == answer)
+    {
+        return println!(
+            "Answer {} was already submitted: {}",
+            answer, previous.verdict
+        );
+    }
+    println!("Submitting {} for part {} of day {}", answer, part, day);
+    let verdict = match website::submit(year, day, part, &answer) {
+        Ok(verdict) => verdict,
+        Err(err) => return eprintln!("Could not submit: {}", err),
+    };
+    println!("The answer is {}", verdict);
+    // answers that were not judged may be submitted again
+    if verdict == Verdict::Correct || verdict.is_wrong() {
+        let submission = results::Submission {
+            timestamp: results::now(),
+            year,
+            day,
+            part,
+            answer,
+            verdict: verdict.to_string(),
+        };
+        if let Err(err) = results::append_submission(results::SUBMISSIONS_PATH, &submission) {
+            eprintln!("Could not record the submission: {}", err);
+        }
+    }
+}
+
+fn main() {
+    let command = Cli::parse().into_command();
+    config::set(Config::load().unwrap_or_else(|msg| exit_with(&msg)));
+    if let Some(threads) = config::get().threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .unwrap();
+    }
+    match command {
+        Command::Run(args) => run(&args, None),
+        Command::Bench { runs, run: args } => run(&args, Some(runs)),
+        Command::Gen { day, size, seed } => generate(day, size, seed),
+        Command::List => list(),
+        Command::Fetch { day, year, force } => fetch(year, day, force),
+        Command::Submit { day, part, year } => submit(year, day, part),
+        Command::Encrypt { days, year } => encrypt(year, &days),
+        #[cfg(feature = "tui")]
+        Command::Dashboard { year } => {
+            tui::dashboard(year).unwrap_or_else(|err| eprintln!("Dashboard failed: {}", err))
+        }
+        Command::New { day, year } => {
+            match scaffold::new_day(year, day, &config::get().input_dir) {
+                Ok(()) => println!("Created day {}, rebuild to solve it", day),
+                Err(err) => eprintln!("Could not create day {}: {}", day, err),
+            }
+        }
+    }
+}
+
+/// Run again with the same arguments in a child process, rebuilt by cargo, every time the source
+/// or input file of one of the days changes.
+fn watch_days(year: u16, days: &[u8], source: &InputSource) -> ! {
+    let mut paths: Vec<PathBuf> = days
+        .iter()
+        .map(|day| scaffold::source_path(year, *day))
+        .collect();
+    match source {
+        InputSource::File(path) => paths.push(PathBuf::from(path)),
+        InputSource::Directory(_, profile) => paths.extend(
+            days.iter()
+                .map(|day| PathBuf::from(input_path(year, profile.as_deref(), *day))),
+        ),
+        InputSource::Stdin | InputSource::Example(_) => {}
+    }
+    let args: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| arg != "--watch")
+        .collect();
+    let profile: &[&str] = if cfg!(debug_assertions) {
+        &[]
+    } else {
+        &["--release"]
+    };
+    loop {
+        let since = watch::modified(&paths);
+        let status = process::Command::new(env!("CARGO"))
+            .args(["run", "--quiet"])
+            .args(profile)
+            .arg("--")
+            .args(&args)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .status();
+        if let Err(err) = status {
+            eprintln!("Could not run cargo: {}", err);
+        }
+        println!("\nWatching {} files, press Ctrl-C to stop", paths.len());
+        watch::wait_for_change(&paths, &since);
+    }
+}
 
+/// Print an error that prevents running at all, and exit with a failure status.
+fn exit_with(msg: &str) -> ! {
+    eprintln!("{}", msg);
+    process::exit(1)
+}
+
+/// Message of a panic payload.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(msg) => *msg,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map_or("the solver panicked".to_owned(), |msg| msg.to_string()),
+    }
+}
+
+/// Solve days for every file of a directory, printing one row per file. Files that the solver
+/// panics on are reported, and the next ones solved.
+fn solve_directory(dir: &str, days: &[u8]) {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|err| exit_with(&format!("Cannot read {}: {}", dir, err)))
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    let width = files
+        .iter()
+        .map(|path| path.file_name().unwrap().len())
+        .max()
+        .unwrap_or(0);
+    // panics are reported in the rows
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
     for day in days {
-        let (p1, p2) = solve_day(day);
+        let solver = match solver(*day) {
+            Ok(solver) => solver,
+            Err(err) => {
+                eprintln!("\n{}", err);
+                continue;
+            }
+        };
         println!("\n=== Day {:02} ===", day);
-        println!("   Part 1: {}", p1);
-        println!("   Part 2: {}", p2);
+        for path in &files {
+            let name = path.file_name().unwrap().to_string_lossy();
+            let input = match InputSource::File(path.to_string_lossy().into_owned()).read(*day) {
+                Ok(input) => input,
+                Err(err) => {
+                    println!("   {:<width$}  failed: {}", name, err, width = width);
+                    continue;
+                }
+            };
+            let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+                let start = Instant::now();
+                let answers = solver.solve(input);
+                (answers, start.elapsed())
+            }));
+            match solved {
+                Ok((Ok(result), elapsed)) => println!(
+                    "   {:<width$}  {:<16} {:<16} {:.1?}",
+                    name,
+                    result.part1.to_string(),
+                    result.part2.to_string(),
+                    elapsed,
+                    width = width
+                ),
+                Ok((Err(err), _)) => {
+                    println!("   {:<width$}  failed: {}", name, err, width = width)
+                }
+                Err(payload) => println!(
+                    "   {:<width$}  panicked: {}",
+                    name,
+                    panic_message(payload),
+                    width = width
+                ),
+            }
+        }
+    }
+    panic::set_hook(hook);
+}
+
+/// Solve day 3 reading the input file in chunks, see [`y2024::day03::solve_stream`].
+fn stream_day3(path: &str) {
+    let start = Instant::now();
+    let answers = fs::File::open(path)
+        .and_then(|file| y2024::day03::solve_stream(file, y2024::day03::STREAM_CHUNK));
+    println!("\n=== Day 03 ===");
+    match answers {
+        Ok((part1, part2)) => {
+            print_part(1, &Ok(part1.into()));
+            print_part(2, &Ok(part2.into()));
+            println!("   Time: {:?}", start.elapsed());
+        }
+        Err(err) => {
+            eprintln!("   Failed: Cannot read {}: {}", path, err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Solve days and print their answers, timing each part over `bench_runs` runs if given.
+fn run(args: &RunArgs, bench_runs: Option<usize>) {
+    let RunArgs {
+        part,
+        time,
+        paranoid,
+        explain,
+        record,
+        ..
+    } = *args;
+    if args.checkpoint {
+        checkpoint::set_mode(checkpoint::Mode::Save);
+    }
+    if args.resume {
+        checkpoint::set_mode(checkpoint::Mode::Resume);
+    }
+    render::set_debug(args.debug);
+    let source = match (&args.input, args.example) {
+        _ if args.reads_stdin() => InputSource::Stdin,
+        (_, Some(index)) => InputSource::Example(index),
+        (Some(path), None) => InputSource::File(path.clone()),
+        (None, None) => InputSource::Directory(args.year, args.profile.clone()),
+    };
+    let mut days = args.days();
+    if args.all {
+        days.extend(implemented());
+    }
+    if days.is_empty() {
+        Cli::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "provide the days to solve, or --all",
+            )
+            .exit();
+    }
+
+    if args.stream {
+        if days != [3] {
+            Cli::command()
+                .error(ErrorKind::ArgumentConflict, "only day 3 can be streamed")
+                .exit();
+        }
+        if let Some(path) = &args.input {
+            return stream_day3(path);
+        }
+    }
+    if source == InputSource::Stdin && days.len() != 1 {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "the standard input holds the input of a single day",
+            )
+            .exit();
+    }
+    if let Some(dir) = &args.input_dir {
+        return solve_directory(dir, &days);
+    }
+    if args.watch {
+        if source == InputSource::Stdin {
+            Cli::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "the standard input cannot be watched",
+                )
+                .exit();
+        }
+        watch_days(args.year, &days, &source);
+    }
+
+    let commit = if record { results::git_commit() } else { None };
+    let expected = if args.check {
+        answers::load(args.profile.as_deref()).unwrap_or_else(|msg| exit_with(&msg))
+    } else {
+        BTreeMap::new()
+    };
+    let format = args.format.as_ref().unwrap_or(&config::get().format);
+    let table = format == "table";
+    let json = format == "json";
+    let several = days.len() > 1;
+    let mut report = Report::new(io::stdout().is_terminal());
+    let mut mismatches = 0;
+    let mut total = Duration::ZERO;
+    let mut solved = 0;
+    let mut failed = 0;
+    for day in days {
+        let solver = match solver(day) {
+            Ok(solver) => solver,
+            Err(err) => {
+                eprintln!("\n{}", err);
+                continue;
+            }
+        };
+        let input = match source.read(day) {
+            Ok(input) => input,
+            Err(err) => {
+                if json {
+                    eprintln!("Day {:02} failed: {}", day, err);
+                } else {
+                    println!("\n=== Day {:02} ===", day);
+                    eprintln!("   Failed: {}", err);
+                }
+                failed += 1;
+                continue;
+            }
+        };
+        let input_hash = checkpoint::input_hash(&input);
+        let explanation = solver
+            .explainer()
+            .filter(|_| explain)
+            .map(|explain| explain(&input));
+        let viz_input = args.viz.as_ref().map(|_| input.clone());
+        let mut timings: Vec<(String, Duration)> = vec![];
+        let mut part_times = [None; 2];
+        let mut benchmarks: Vec<(u8, bench::Stats)> = vec![];
+        let start = Instant::now();
+        let answers = if paranoid && !solver.variants().is_empty() {
+            solve_paranoid(solver, input).map(|(p1, p2)| vec![(1, p1), (2, p2)])
+        } else if part.is_some() || time || bench_runs.is_some() || table || json {
+            // parsed once for both parts, the parts being timed without the parsing
+            let start = Instant::now();
+            let parsed = solver.prepare(&input);
+            if time {
+                timings.push(("parse".to_owned(), start.elapsed()));
+            }
+            let solve_part = |n: u8| match n {
+                1 => parsed.part1(),
+                _ => parsed.part2(),
+            };
+            [1, 2]
+                .into_iter()
+                .filter(|n| part.is_none_or(|part| part == *n))
+                .map(|n| {
+                    let start = Instant::now();
+                    let answer = match bench_runs {
+                        Some(runs) => {
+                            let (answer, samples) = bench::measure(runs, || solve_part(n));
+                            benchmarks.extend(bench::Stats::new(&samples).map(|stats| (n, stats)));
+                            answer
+                        }
+                        None => solve_part(n),
+                    };
+                    part_times[n as usize - 1] = Some(start.elapsed());
+                    timings.push((format!("part {}", n), start.elapsed()));
+                    Ok((n, Ok(answer?)))
+                })
+                .collect()
+        } else {
+            solver.solve(input).map(|result| {
+                part_times = result.times;
+                vec![(1, Ok(result.part1)), (2, Ok(result.part2))]
+            })
+        };
+        let answers: Vec<_> = match answers {
+            Ok(answers) => answers,
+            Err(err) => {
+                if json {
+                    eprintln!("Day {:02} failed: {}", day, err);
+                } else {
+                    println!("\n=== Day {:02} ===", day);
+                    eprintln!("   Failed: {}", err);
+                }
+                failed += 1;
+                continue;
+            }
+        };
+        let answers: Vec<_> = answers
+            .into_iter()
+            .filter(|(n, _)| part.is_none_or(|part| part == *n))
+            .collect();
+        let elapsed = start.elapsed();
+        total += elapsed;
+        solved += 1;
+        if config::get()
+            .time_budget
+            .is_some_and(|budget| elapsed > budget)
+        {
+            eprintln!(
+                "Warning: day {} took {:?}, over the time budget",
+                day, elapsed
+            );
+        }
+        let micros = elapsed.as_micros() as u64;
+        if record {
+            record_answers(day, &commit, input_hash, micros, &answers);
+        }
+        let details = args.check
+            || (time && !timings.is_empty())
+            || !benchmarks.is_empty()
+            || explanation.is_some();
+        if table {
+            report.push(report_row(day, &answers, part_times), elapsed);
+        }
+        if json {
+            let result = day_result(day, &answers, part_times);
+            println!("{}", serde_json::to_string(&result).unwrap());
+            continue;
+        }
+        if !table || details {
+            println!("\n=== Day {:02} ===", day);
+        }
+        if !table {
+            for (n, answer) in &answers {
+                print_part(*n, answer);
+            }
+        }
+        if args.check {
+            mismatches += check_answers(expected.get(&day), &answers);
+        }
+        if time && !timings.is_empty() {
+            let timings: Vec<String> = timings
+                .iter()
+                .map(|(step, elapsed)| format!("{} {:?}", step, elapsed))
+                .collect();
+            println!("   Time: {}", timings.join(", "));
+        }
+        for (n, stats) in benchmarks {
+            println!("   Part {} over {} runs: {}", n, bench_runs.unwrap(), stats);
+        }
+        if let Some(explanation) = explanation {
+            println!();
+            for line in explanation.lines() {
+                println!("   {}", line);
+            }
+        }
+        if let Some(input) = &viz_input {
+            visualize(day, solver, input, args, several);
+        }
+    }
+    if table {
+        print!("\n{}", report.render());
+    } else if args.all && !json {
+        println!("\nTotal: {:?} for {} days", total, solved);
+    }
+    if failed > 0 {
+        eprintln!("\n{} days could not be solved", failed);
+    }
+    if mismatches > 0 {
+        eprintln!("\n{} answers do not match {}", mismatches, answers::PATH);
+    }
+    if failed > 0 || mismatches > 0 {
+        std::process::exit(1);
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Solution;
-    use crate::solve_day;
+    use crate::etc::rng::Rng;
+    use crate::{
+        DEFAULT_YEAR, InputSource, NotImplemented, Solution, check_answers, cross_validate,
+        day_result, generate_day, implemented, input_paths, looks_like, read_input, solve_paranoid,
+        solver,
+    };
+    use std::time::Duration;
+
+    fn solve_day(day: u8) -> Result<(Solution, Solution), NotImplemented> {
+        let result = solver(day)?
+            .solve(read_input(DEFAULT_YEAR, None, day).unwrap())
+            .unwrap();
+        assert_eq!(result.day, day);
+        Ok((result.part1, result.part2))
+    }
+
+    #[test]
+    fn generated_puzzles() {
+        for day in 1..=25 {
+            let input = generate_day(day, 20, &mut Rng::new(day as u64)).unwrap();
+            assert_eq!(
+                input,
+                generate_day(day, 20, &mut Rng::new(day as u64)).unwrap()
+            );
+            let solver = solver(day).unwrap();
+            let answers = {
+                let parsed = solver.prepare(&input);
+                (parsed.part1().unwrap(), parsed.part2().unwrap())
+            };
+            assert_eq!(solver.part1(&input).as_ref(), Ok(&answers.0));
+            assert_eq!(solver.part2(&input).as_ref(), Ok(&answers.1));
+            let result = solver.solve(input).unwrap();
+            assert_eq!((result.part1, result.part2), answers, "day {}", day);
+            assert_eq!(result.day, day);
+        }
+    }
+
+    #[test]
+    fn input_file() {
+        let input = generate_day(1, 20, &mut Rng::new(1)).unwrap();
+        let path = std::env::temp_dir().join("aoc24-rust-input-file.txt");
+        std::fs::write(&path, &input).unwrap();
+        let source = InputSource::File(path.to_str().unwrap().to_owned());
+        assert_eq!(source.read(1), Ok(input));
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(
+            InputSource::Example(1).read(9).as_deref(),
+            Ok("2333133121414131402\n")
+        );
+        assert!(InputSource::Example(9).read(9).is_err());
+    }
+
+    #[test]
+    fn candidate_paths() {
+        assert_eq!(
+            input_paths(2024, Some("alice"), 7),
+            vec!["./input/alice/2024/day07.txt", "./input/alice/day07.txt"]
+        );
+    }
+
+    #[test]
+    fn invalid_input_file() {
+        let path = std::env::temp_dir().join("aoc24-rust-invalid-input-file.txt");
+        std::fs::write(&path, "<!DOCTYPE html>\n<html>\n").unwrap();
+        let err = InputSource::File(path.to_str().unwrap().to_owned())
+            .read(1)
+            .unwrap_err();
+        assert!(err.starts_with("Invalid input of day 1 in"), "{}", err);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn check() {
+        let expected = [Some("42".to_owned()), Some("ab".to_owned())];
+        let answers = vec![
+            (1, Ok(Solution::from(42u64))),
+            (2, Ok(Solution::from("ab"))),
+        ];
+        assert_eq!(check_answers(Some(&expected), &answers), 0);
+        let answers = vec![(1, Ok(Solution::from(41u64))), (2, Ok(Solution::Todo()))];
+        assert_eq!(check_answers(Some(&expected), &answers), 2);
+        assert_eq!(check_answers(None, &answers), 0);
+        let answers = vec![(2, Ok(Solution::pending("the last day has no part 2")))];
+        assert_eq!(check_answers(Some(&expected), &answers), 0);
+    }
+
+    #[test]
+    fn json() {
+        let answers = vec![(
+            2,
+            Err(vec![
+                ("fast", Solution::from(1u64)),
+                ("slow", Solution::from(2u64)),
+            ]),
+        )];
+        let result = day_result(6, &answers, [None, Some(Duration::from_millis(3))]);
+        assert_eq!(result.part1, Solution::pending("not run"));
+        assert_eq!(result.part2, Solution::pending("variants disagree"));
+        assert_eq!(result.notes, vec!["part 2 of fast: 1", "part 2 of slow: 2"]);
+        assert_eq!(result.times[1], Some(Duration::from_millis(3)));
+    }
+
+    #[test]
+    fn not_implemented() {
+        let err = NotImplemented { day: 26 };
+        assert_eq!(solver(26).err(), Some(err));
+        assert_eq!(solve_day(26), Err(err));
+        assert_eq!(generate_day(26, 20, &mut Rng::new(26)), Err(err));
+        assert_eq!(err.to_string(), "day 26 is not implemented");
+        assert!(implemented().eq(1..=25));
+    }
+
+    #[test]
+    fn wrong_day() {
+        for day in 1..=25 {
+            let input = generate_day(day, 20, &mut Rng::new(day as u64)).unwrap();
+            assert!(looks_like(day, &input), "day {}", day);
+        }
+        let robots = generate_day(14, 20, &mut Rng::new(14)).unwrap();
+        assert!(!looks_like(13, &robots));
+        // day 6 solves guards facing up only
+        assert!(!looks_like(6, "..#\n.>.\n..."));
+        let maze = generate_day(16, 20, &mut Rng::new(16)).unwrap();
+        assert_eq!(
+            (1..=25)
+                .filter(|day| looks_like(*day, &maze))
+                .collect::<Vec<_>>(),
+            vec![16, 20]
+        );
+    }
+
+    #[test]
+    fn paranoid() {
+        assert_eq!(
+            cross_validate(vec![("a", Solution::from(1u64)), ("b", Solution::Todo())]),
+            Ok(Solution::from(1u64))
+        );
+        assert_eq!(
+            cross_validate(vec![
+                ("a", Solution::from(1u64)),
+                ("b", Solution::from(2u64))
+            ]),
+            Err(vec![
+                ("a", Solution::from(1u64)),
+                ("b", Solution::from(2u64))
+            ])
+        );
+        assert_eq!(cross_validate(vec![]), Ok(Solution::Todo()));
+
+        // variants agree on generated inputs
+        for day in [3, 6, 13, 18] {
+            let input = generate_day(day, 20, &mut Rng::new(day as u64)).unwrap();
+            let (p1, p2) = solve_paranoid(solver(day).unwrap(), input).unwrap();
+            assert!(p1.is_ok() && p2.is_ok());
+        }
+    }
 
     #[test]
     fn my_puzzles() {
         assert_eq!(
-            solve_day(1),
+            solve_day(1).unwrap(),
             (Solution::from(765748u64), Solution::from(27732508u64))
         );
         assert_eq!(
-            solve_day(2),
+            solve_day(2).unwrap(),
             (Solution::from(479usize), Solution::from(531usize))
         );
         assert_eq!(
-            solve_day(3),
+            solve_day(3).unwrap(),
             (Solution::from(170807108u64), Solution::from(74838033u64))
         );
         assert_eq!(
-            solve_day(4),
+            solve_day(4).unwrap(),
             (Solution::from(2397usize), Solution::from(1824usize))
         );
         assert_eq!(
-            solve_day(5),
+            solve_day(5).unwrap(),
             (Solution::from(7024usize), Solution::from(4151usize))
         );
         assert_eq!(
-            solve_day(6),
+            solve_day(6).unwrap(),
             (Solution::from(4939usize), Solution::from(1434usize))
         );
         assert_eq!(
-            solve_day(7),
+            solve_day(7).unwrap(),
             (
                 Solution::from(4555081946288u64),
                 Solution::from(227921760109726u64)
             )
         );
         assert_eq!(
-            solve_day(8),
+            solve_day(8).unwrap(),
             (Solution::from(269usize), Solution::from(949usize))
         );
         assert_eq!(
-            solve_day(9),
+            solve_day(9).unwrap(),
             (
                 Solution::from(6201130364722u64),
                 Solution::from(6221662795602u64)
             )
         );
         assert_eq!(
-            solve_day(10),
+            solve_day(10).unwrap(),
             (
                 Solution::from(782usize),
                 Solution::from(1694usize)
             )
         );
         assert_eq!(
-            solve_day(11),
+            solve_day(11).unwrap(),
             (
                 Solution::from(183248usize),
                 Solution::from(218811774248729usize)
             )
         );
         assert_eq!(
-            solve_day(12),
+            solve_day(12).unwrap(),
             (
                 Solution::from(1456082u64),
                 Solution::from(872382u64)
             )
         );
         assert_eq!(
-            solve_day(13),
+            solve_day(13).unwrap(),
             (
-                Solution::from(39290u64),
-                Solution::from(73458657399094u64)
+                Solution::from(39290i64),
+                Solution::from(73458657399094i64)
             )
         );
         assert_eq!(
-            solve_day(14),
+            solve_day(14).unwrap(),
             (
                 Solution::from(228457125u64),
                 Solution::from(6493u64)
             )
         );
         assert_eq!(
-            solve_day(15),
+            solve_day(15).unwrap(),
             (
                 Solution::from(1499739u64),
                 Solution::from(1522215u64)
             )
         );
         assert_eq!(
-            solve_day(16),
+            solve_day(16).unwrap(),
             (
                 Solution::from(95476u64),
                 Solution::from(511u64)
             )
         );
         assert_eq!(
-            solve_day(17),
+            solve_day(17).unwrap(),
             (
                 Solution::from("6,0,6,3,0,2,3,1,6"),
                 Solution::from(236539226447469u64)
             )
         );
         assert_eq!(
-            solve_day(18),
+            solve_day(18).unwrap(),
             (
                 Solution::from(344u64),
                 Solution::from("46,18")
             )
         );
         assert_eq!(
-            solve_day(19),
+            solve_day(19).unwrap(),
             (
                 Solution::from(285usize),
                 Solution::from(636483903099279u64)
             )
         );
         assert_eq!(
-            solve_day(20),
+            solve_day(20).unwrap(),
             (
                 Solution::from(1422u64),
                 Solution::from(1009299u64)
             )
         );
         assert_eq!(
-            solve_day(21),
+            solve_day(21).unwrap(),
             (
                 Solution::from(246990u64),
-                Solution::Todo()
+                Solution::pending("not solved yet")
             )
         );
         assert_eq!(
-            solve_day(22),
+            solve_day(22).unwrap(),
             (
                 Solution::from(20332089158u64),
                 Solution::from(2191u64)
             )
         );
         assert_eq!(
-            solve_day(23),
+            solve_day(23).unwrap(),
             (
                 Solution::from(1000usize),
                 Solution::from("cf,ct,cv,cz,fi,lq,my,pa,sl,tt,vw,wz,yd")
             )
         );
         assert_eq!(
-            solve_day(24),
+            solve_day(24).unwrap(),
             (
                 Solution::from(46463754151024u64),
                 Solution::from("cqk,fph,gds,jrs,wrk,z15,z21,z34")
             )
         );
         assert_eq!(
-            solve_day(25),
+            solve_day(25).unwrap(),
             (
                 Solution::from(3249u64),
-                Solution::Todo()
+                Solution::pending("the last day has no part 2")
             )
         );
     }
@@ -0,0 +1,248 @@
+//! Interactive dashboard: a calendar of the days of a year, solved by a background worker.
+use crate::etc::solution::Solution;
+use crate::{InputSource, panic_message, solver};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+/// Days slower than this are highlighted.
+const SLOW: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Status {
+    NotImplemented,
+    Queued,
+    Running,
+    Solved {
+        answers: [Solution; 2],
+        elapsed: Duration,
+        explanation: Option<String>,
+    },
+    /// The input could not be read, or the solver failed on it or panicked.
+    Failed(String),
+}
+
+impl Status {
+    /// Text and colour of the calendar cell of a day.
+    fn summary(&self) -> (String, Color) {
+        match self {
+            Status::NotImplemented => ("-".to_owned(), Color::DarkGray),
+            Status::Queued => ("queued".to_owned(), Color::Gray),
+            Status::Running => ("running".to_owned(), Color::Cyan),
+            Status::Solved { elapsed, .. } if *elapsed > SLOW => {
+                (format!("{:.1?}", elapsed), Color::Yellow)
+            }
+            Status::Solved { elapsed, .. } => (format!("{:.1?}", elapsed), Color::Green),
+            Status::Failed(_) => ("failed".to_owned(), Color::Red),
+        }
+    }
+}
+
+/// Solve a day, catching panics of the solver.
+fn run_day(year: u16, day: u8) -> Status {
+    let Ok(solver) = solver(day) else {
+        return Status::NotImplemented;
+    };
+    let input = match InputSource::Directory(year, None).read(day) {
+        Ok(input) => input,
+        Err(err) => return Status::Failed(err),
+    };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let explanation = solver.explainer().map(|explain| explain(&input));
+        let start = Instant::now();
+        let result = solver.solve(input);
+        (result, start.elapsed(), explanation)
+    }));
+    match result {
+        Ok((Ok(result), elapsed, explanation)) => Status::Solved {
+            answers: [result.part1, result.part2],
+            elapsed,
+            explanation,
+        },
+        Ok((Err(err), _, _)) => Status::Failed(err.to_string()),
+        Err(payload) => Status::Failed(panic_message(payload)),
+    }
+}
+
+struct App {
+    status: Vec<Status>,
+    /// Index of the selected day.
+    selected: usize,
+    explain: bool,
+    requests: Sender<u8>,
+    updates: Receiver<(u8, Status)>,
+}
+
+impl App {
+    /// Start the background worker solving the days requested to it, one at a time.
+    fn new(year: u16) -> Self {
+        let (requests, pending) = mpsc::channel::<u8>();
+        let (notify, updates) = mpsc::channel();
+        std::thread::spawn(move || {
+            for day in pending {
+                if notify.send((day, Status::Running)).is_err()
+                    || notify.send((day, run_day(year, day))).is_err()
+                {
+                    break;
+                }
+            }
+        });
+        let status = (1..=25)
+            .map(|day| match solver(day) {
+                Ok(_) => Status::Queued,
+                Err(_) => Status::NotImplemented,
+            })
+            .collect();
+        App {
+            status,
+            selected: 0,
+            explain: false,
+            requests,
+            updates,
+        }
+    }
+
+    fn queue(&mut self, day: u8) {
+        let status = &mut self.status[day as usize - 1];
+        if *status != Status::NotImplemented && self.requests.send(day).is_ok() {
+            *status = Status::Queued;
+        }
+    }
+
+    fn run(mut self, mut terminal: DefaultTerminal) -> io::Result<()> {
+        for day in 1..=25 {
+            self.queue(day);
+        }
+        loop {
+            while let Ok((day, status)) = self.updates.try_recv() {
+                self.status[day as usize - 1] = status;
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+            if !event::poll(Duration::from_millis(100))? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Left | KeyCode::Char('h') => {
+                    self.selected = self.selected.saturating_sub(1)
+                }
+                KeyCode::Right | KeyCode::Char('l') => self.selected = (self.selected + 1).min(24),
+                KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(5),
+                KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 5).min(24),
+                KeyCode::Enter => self.queue(self.selected as u8 + 1),
+                KeyCode::Char('a') => (1..=25).for_each(|day| self.queue(day)),
+                KeyCode::Char('v') => self.explain = !self.explain,
+                _ => {}
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [calendar, details, help] = Layout::vertical([
+            Constraint::Min(15),
+            Constraint::Length(10),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let rows = Layout::vertical([Constraint::Ratio(1, 5); 5]).split(calendar);
+        for (r, row) in rows.iter().enumerate() {
+            let cells = Layout::horizontal([Constraint::Ratio(1, 5); 5]).split(*row);
+            for (c, cell) in cells.iter().enumerate() {
+                let index = r * 5 + c;
+                let (text, color) = self.status[index].summary();
+                let mut block = Block::bordered().title(format!(" Day {:02} ", index + 1));
+                if index == self.selected {
+                    block = block
+                        .border_type(BorderType::Double)
+                        .border_style(Style::new().add_modifier(Modifier::BOLD));
+                }
+                let paragraph = Paragraph::new(text)
+                    .style(Style::new().fg(color))
+                    .block(block);
+                frame.render_widget(paragraph, *cell);
+            }
+        }
+
+        let lines: Vec<Line> = match &self.status[self.selected] {
+            Status::Solved {
+                answers,
+                elapsed,
+                explanation,
+            } => {
+                let mut lines = vec![
+                    Line::from(format!("Part 1: {}", answers[0])),
+                    Line::from(format!("Part 2: {}", answers[1])),
+                    Line::from(format!("Solved in {:?}", elapsed)),
+                ];
+                match explanation {
+                    Some(explanation) if self.explain => {
+                        lines.push(Line::default());
+                        lines.extend(explanation.lines().map(|line| Line::from(line.to_owned())));
+                    }
+                    Some(_) => lines.push(Line::from("Press v for the explanation")),
+                    None => {}
+                }
+                lines
+            }
+            Status::Failed(msg) => vec![Line::from(format!("Failed: {}", msg))],
+            status => vec![Line::from(status.summary().0)],
+        };
+        let title = format!(" Day {:02} ", self.selected + 1);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered().title(title)),
+            details,
+        );
+        frame.render_widget(
+            Line::from("arrows select, enter re-runs, a re-runs all, v explains, q quits")
+                .style(Style::new().fg(Color::DarkGray)),
+            help,
+        );
+    }
+}
+
+/// Show the dashboard until the user quits, solving every implemented day in the background.
+pub fn dashboard(year: u16) -> io::Result<()> {
+    // panic messages of failed days would garble the screen, they are shown in the details pane
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let terminal = ratatui::init();
+    let result = App::new(year).run(terminal);
+    ratatui::restore();
+    panic::set_hook(hook);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Status, run_day};
+    use crate::Solution;
+    use ratatui::style::Color;
+    use std::time::Duration;
+
+    #[test]
+    fn status() {
+        assert_eq!(run_day(2024, 26), Status::NotImplemented);
+        let solved = Status::Solved {
+            answers: [Solution::from(1u64), Solution::Todo()],
+            elapsed: Duration::from_millis(1500),
+            explanation: None,
+        };
+        assert_eq!(solved.summary(), ("1.5s".to_owned(), Color::Yellow));
+        assert_eq!(Status::Running.summary().1, Color::Cyan);
+    }
+}